@@ -0,0 +1,76 @@
+use chrono::{DateTime, Utc};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use golem_certificate::{
+    validate_certificate_str, validate_manifest_str, validate_node_descriptor_str,
+    validate_node_descriptors_batch, validate_revocation_list_str, SignedNodeDescriptor,
+    ValidationOptions,
+};
+
+// Fixtures are valid in 2023-2024
+fn timestamp() -> DateTime<Utc> {
+    "2024-01-01T00:00:00Z".parse().unwrap()
+}
+
+fn read(path: &str) -> String {
+    std::fs::read_to_string(format!("tests/resources/{path}")).unwrap()
+}
+
+fn certificates(c: &mut Criterion) {
+    let mut group = c.benchmark_group("validate_certificate");
+    for fixture in [
+        "happy_path.signed.json",
+        "happy_path_smartcard_root.signed.json",
+        "happy_path_smartcard_leaf.signed.json",
+    ] {
+        let data = read(&format!("certificate/{fixture}"));
+        group.bench_with_input(BenchmarkId::from_parameter(fixture), &data, |b, data| {
+            b.iter(|| validate_certificate_str(data, Some(timestamp())).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn node_descriptors(c: &mut Criterion) {
+    let mut group = c.benchmark_group("validate_node_descriptor");
+    for fixture in ["happy_path.signed.json", "node_signed.signed.json"] {
+        let data = read(&format!("node_descriptor/{fixture}"));
+        group.bench_with_input(BenchmarkId::from_parameter(fixture), &data, |b, data| {
+            b.iter(|| validate_node_descriptor_str(data, Some(timestamp())).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn node_descriptors_batch(c: &mut Criterion) {
+    let mut group = c.benchmark_group("validate_node_descriptors_batch");
+    let signed_node_descriptor: SignedNodeDescriptor =
+        serde_json::from_str(&read("node_descriptor/happy_path.signed.json")).unwrap();
+    let options = ValidationOptions::new().at(timestamp());
+    for size in [10, 100] {
+        let batch = vec![signed_node_descriptor.clone(); size];
+        group.bench_with_input(BenchmarkId::from_parameter(size), &batch, |b, batch| {
+            b.iter(|| validate_node_descriptors_batch(batch, &options))
+        });
+    }
+    group.finish();
+}
+
+fn other_documents(c: &mut Criterion) {
+    let manifest = read("manifest/allowed.signed.json");
+    c.bench_function("validate_manifest/allowed.signed.json", |b| {
+        b.iter(|| validate_manifest_str(&manifest, Some(timestamp())).unwrap())
+    });
+    let revocation_list = read("revocation_list/empty.signed.json");
+    c.bench_function("validate_revocation_list/empty.signed.json", |b| {
+        b.iter(|| validate_revocation_list_str(&revocation_list, Some(timestamp())).unwrap())
+    });
+}
+
+criterion_group!(
+    benches,
+    certificates,
+    node_descriptors,
+    node_descriptors_batch,
+    other_documents
+);
+criterion_main!(benches);
@@ -0,0 +1,77 @@
+/* C API of Golem Certificate, generated by cbindgen from capi/src/lib.rs. Do not edit. */
+
+#ifndef GOLEM_CERTIFICATE_H
+#define GOLEM_CERTIFICATE_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Outcome of a validation, the output contains the json of the validated document or of the error.
+typedef enum GcertStatus {
+  // The document is valid, the output is the validated document
+  GCERT_STATUS_OK = 0,
+  // The document is not valid, the output is the error
+  GCERT_STATUS_VALIDATION_FAILED = 1,
+  // The input is not valid json
+  GCERT_STATUS_INVALID_JSON = 2,
+  // The timestamp is not in RFC 3339 format
+  GCERT_STATUS_INVALID_TIMESTAMP = 3,
+  // A required argument is null or not UTF-8. The output is not set when it is the output that is null
+  GCERT_STATUS_INVALID_ARGUMENT = 4,
+  // Unexpected failure of the library
+  GCERT_STATUS_INTERNAL_ERROR = 5,
+} GcertStatus;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Deserializes and validates certificate.
+// On success the output is the json of `ValidatedCertificate`, with the certificate chain fingerprints
+// ordered from the certificate to the root. Otherwise it is `{"kind": kind, "error": message, "position": position}`,
+// where the kind is the variant of the Rust `golem_certificate::Error`, or the status in camel case
+// (e.g. `InvalidTimestamp`) when the failure is not raised by validation,
+// and the position in the chain is either null or `{"depth", "fingerprint", "displayName"}`.
+// The output has to be released with `gcert_free_string`.
+//
+// # Safety
+// `data` and `timestamp` have to be null or point to null-terminated strings,
+// `output` has to be null or point to memory where a pointer can be written.
+//
+// # Arguments
+// * `data` serialized certificate
+// * `timestamp` optional RFC 3339 timestamp to verify validity, not checked when null
+// * `output` receives the result
+enum GcertStatus gcert_validate_certificate(const char *data,
+                                            const char *timestamp,
+                                            char **output);
+
+// Deserializes and validates node descriptor.
+// On success the output is the json of `ValidatedNodeDescriptor`, with the certificate chain fingerprints
+// ordered from the signing certificate to the root. Otherwise it is the error, see `gcert_validate_certificate`.
+// The output has to be released with `gcert_free_string`.
+//
+// # Safety
+// See `gcert_validate_certificate`.
+//
+// # Arguments
+// * `data` serialized node descriptor
+// * `timestamp` optional RFC 3339 timestamp to verify validity, not checked when null
+// * `output` receives the result
+enum GcertStatus gcert_validate_node_descriptor(const char *data,
+                                                const char *timestamp,
+                                                char **output);
+
+// Releases a string returned by the library, null is ignored.
+//
+// # Safety
+// `string` has to be null or returned by the library and not released yet.
+void gcert_free_string(char *string);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* GOLEM_CERTIFICATE_H */
@@ -0,0 +1,178 @@
+//! C API of Golem Certificate, the header `include/golem_certificate.h` is generated by cbindgen
+//! with `cbindgen --config cbindgen.toml --output include/golem_certificate.h` in this directory.
+//! Strings passed in are null-terminated UTF-8, strings returned have to be released with [`gcert_free_string`].
+
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use chrono::{DateTime, Utc};
+use golem_certificate as gcert;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+/// Outcome of a validation, the output contains the json of the validated document or of the error.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum GcertStatus {
+    /// The document is valid, the output is the validated document
+    Ok = 0,
+    /// The document is not valid, the output is the error
+    ValidationFailed = 1,
+    /// The input is not valid json
+    InvalidJson = 2,
+    /// The timestamp is not in RFC 3339 format
+    InvalidTimestamp = 3,
+    /// A required argument is null or not UTF-8. The output is not set when it is the output that is null
+    InvalidArgument = 4,
+    /// Unexpected failure of the library
+    InternalError = 5,
+}
+
+/// Deserializes and validates certificate.
+/// On success the output is the json of `ValidatedCertificate`, with the certificate chain fingerprints
+/// ordered from the certificate to the root. Otherwise it is `{"kind": kind, "error": message, "position": position}`,
+/// where the kind is the variant of the Rust `golem_certificate::Error`, or the status in camel case
+/// (e.g. `InvalidTimestamp`) when the failure is not raised by validation,
+/// and the position in the chain is either null or `{"depth", "fingerprint", "displayName"}`.
+/// The output has to be released with `gcert_free_string`.
+///
+/// # Safety
+/// `data` and `timestamp` have to be null or point to null-terminated strings,
+/// `output` has to be null or point to memory where a pointer can be written.
+///
+/// # Arguments
+/// * `data` serialized certificate
+/// * `timestamp` optional RFC 3339 timestamp to verify validity, not checked when null
+/// * `output` receives the result
+#[no_mangle]
+pub unsafe extern "C" fn gcert_validate_certificate(
+    data: *const c_char,
+    timestamp: *const c_char,
+    output: *mut *mut c_char,
+) -> GcertStatus {
+    validate(data, timestamp, output, gcert::validate_certificate_str)
+}
+
+/// Deserializes and validates node descriptor.
+/// On success the output is the json of `ValidatedNodeDescriptor`, with the certificate chain fingerprints
+/// ordered from the signing certificate to the root. Otherwise it is the error, see `gcert_validate_certificate`.
+/// The output has to be released with `gcert_free_string`.
+///
+/// # Safety
+/// See `gcert_validate_certificate`.
+///
+/// # Arguments
+/// * `data` serialized node descriptor
+/// * `timestamp` optional RFC 3339 timestamp to verify validity, not checked when null
+/// * `output` receives the result
+#[no_mangle]
+pub unsafe extern "C" fn gcert_validate_node_descriptor(
+    data: *const c_char,
+    timestamp: *const c_char,
+    output: *mut *mut c_char,
+) -> GcertStatus {
+    validate(data, timestamp, output, gcert::validate_node_descriptor_str)
+}
+
+/// Releases a string returned by the library, null is ignored.
+///
+/// # Safety
+/// `string` has to be null or returned by the library and not released yet.
+#[no_mangle]
+pub unsafe extern "C" fn gcert_free_string(string: *mut c_char) {
+    if !string.is_null() {
+        drop(CString::from_raw(string));
+    }
+}
+
+struct Failure {
+    status: GcertStatus,
+    error: Value,
+}
+
+impl Failure {
+    fn new(status: GcertStatus, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            error: json!({ "kind": format!("{status:?}"), "error": message.into(), "position": null }),
+        }
+    }
+}
+
+impl From<gcert::Error> for Failure {
+    fn from(error: gcert::Error) -> Self {
+        let status = match error {
+            gcert::Error::InvalidJson(_) => GcertStatus::InvalidJson,
+            _ => GcertStatus::ValidationFailed,
+        };
+        let position = match &error {
+            gcert::Error::InChain { position, .. } => json!({
+                "depth": position.depth,
+                "fingerprint": position.fingerprint,
+                "displayName": position.display_name,
+            }),
+            _ => Value::Null,
+        };
+        Self {
+            status,
+            error: json!({ "kind": error.kind(), "error": error.to_string(), "position": position }),
+        }
+    }
+}
+
+unsafe fn validate<T: Serialize>(
+    data: *const c_char,
+    timestamp: *const c_char,
+    output: *mut *mut c_char,
+    validate: fn(&str, Option<DateTime<Utc>>) -> gcert::Result<T>,
+) -> GcertStatus {
+    if output.is_null() {
+        return GcertStatus::InvalidArgument;
+    }
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        let data = read_str(data, "data")?
+            .ok_or_else(|| Failure::new(GcertStatus::InvalidArgument, "Argument `data` is null"))?;
+        let timestamp = read_str(timestamp, "timestamp")?
+            .map(parse_timestamp)
+            .transpose()?;
+        let validated = validate(data, timestamp)?;
+        serde_json::to_value(validated)
+            .map_err(|e| Failure::new(GcertStatus::InternalError, e.to_string()))
+    }));
+    let (status, json) = match result {
+        Ok(Ok(validated)) => (GcertStatus::Ok, validated),
+        Ok(Err(failure)) => (failure.status, failure.error),
+        Err(_) => {
+            let failure = Failure::new(GcertStatus::InternalError, "Unexpected failure");
+            (failure.status, failure.error)
+        }
+    };
+    // Json escapes control characters, so it does not contain the null character
+    *output = CString::new(json.to_string())
+        .unwrap_or_default()
+        .into_raw();
+    status
+}
+
+unsafe fn read_str<'a>(string: *const c_char, name: &str) -> Result<Option<&'a str>, Failure> {
+    if string.is_null() {
+        return Ok(None);
+    }
+    CStr::from_ptr(string).to_str().map(Some).map_err(|_| {
+        Failure::new(
+            GcertStatus::InvalidArgument,
+            format!("Argument `{name}` is not UTF-8"),
+        )
+    })
+}
+
+fn parse_timestamp(timestamp: &str) -> Result<DateTime<Utc>, Failure> {
+    DateTime::parse_from_rfc3339(timestamp)
+        .map(|timestamp| timestamp.with_timezone(&Utc))
+        .map_err(|e| {
+            Failure::new(
+                GcertStatus::InvalidTimestamp,
+                format!("Invalid timestamp {timestamp}: {e}"),
+            )
+        })
+}
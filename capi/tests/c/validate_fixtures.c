@@ -0,0 +1,112 @@
+/* Validates the documents of tests/resources through the C API.
+ * Usage: validate_fixtures <path to tests/resources>
+ * Exits with 0 when every case returns the expected status. */
+
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+#include "golem_certificate.h"
+
+typedef GcertStatus (*validate_fn)(const char *, const char *, char **);
+
+static const char *resources;
+static int failures = 0;
+
+static char *read_file(const char *relative_path) {
+    char path[4096];
+    snprintf(path, sizeof(path), "%s/%s", resources, relative_path);
+    FILE *file = fopen(path, "rb");
+    if (file == NULL) {
+        fprintf(stderr, "cannot open %s\n", path);
+        exit(2);
+    }
+    fseek(file, 0, SEEK_END);
+    long size = ftell(file);
+    fseek(file, 0, SEEK_SET);
+    char *data = malloc(size + 1);
+    size_t read = fread(data, 1, size, file);
+    data[read] = '\0';
+    fclose(file);
+    return data;
+}
+
+static void expect(const char *name, GcertStatus status, GcertStatus expected_status,
+                   const char *output, const char *expected_output) {
+    int passed = status == expected_status && output != NULL && strstr(output, expected_output) != NULL;
+    printf("%s %s: status %d, output %s\n", passed ? "ok  " : "FAIL", name, status,
+           output != NULL ? output : "(null)");
+    if (!passed) {
+        failures++;
+    }
+}
+
+static void check_data(const char *name, validate_fn validate, const char *data, const char *timestamp,
+                       GcertStatus expected_status, const char *expected_output) {
+    char *output = NULL;
+    GcertStatus status = validate(data, timestamp, &output);
+    expect(name, status, expected_status, output, expected_output);
+    gcert_free_string(output);
+}
+
+static void check(validate_fn validate, const char *relative_path, const char *timestamp,
+                  GcertStatus expected_status, const char *expected_output) {
+    char *data = read_file(relative_path);
+    check_data(relative_path, validate, data, timestamp, expected_status, expected_output);
+    free(data);
+}
+
+int main(int argc, char **argv) {
+    if (argc != 2) {
+        fprintf(stderr, "Usage: %s <path to tests/resources>\n", argv[0]);
+        return 2;
+    }
+    resources = argv[1];
+    const char *valid = "2024-01-01T00:00:00Z";
+
+    check(gcert_validate_certificate, "certificate/happy_path.signed.json", valid, GCERT_STATUS_OK,
+          "\"certificateChainFingerprints\":[\"181eece864b9a8cd");
+    check(gcert_validate_certificate, "certificate/happy_path_smartcard_leaf.signed.json", NULL, GCERT_STATUS_OK,
+          "\"keyUsage\"");
+    check(gcert_validate_certificate, "certificate/expired.signed.json", valid, GCERT_STATUS_VALIDATION_FAILED,
+          "\"error\":\"Expired");
+    check(gcert_validate_certificate, "certificate/expired.signed.json", valid, GCERT_STATUS_VALIDATION_FAILED,
+          "\"kind\":\"Expired\"");
+    check(gcert_validate_certificate, "certificate/invalid_signature.signed.json", NULL,
+          GCERT_STATUS_VALIDATION_FAILED, "\"position\":{\"depth\":0");
+    check(gcert_validate_certificate, "certificate/invalid_permissions.signed.json", NULL,
+          GCERT_STATUS_VALIDATION_FAILED, "\"kind\":\"PermissionsExtended\"");
+    check(gcert_validate_certificate, "certificate/not_signed.json", NULL, GCERT_STATUS_VALIDATION_FAILED,
+          "\"error\"");
+
+    check(gcert_validate_node_descriptor, "node_descriptor/happy_path.signed.json", valid, GCERT_STATUS_OK,
+          "\"nodeId\":\"0x338e02f29b63155beec8253af7ad367dd44b40c6\"");
+    check(gcert_validate_node_descriptor, "node_descriptor/node_signed.signed.json", valid, GCERT_STATUS_OK,
+          "\"possessionProven\":true");
+    check(gcert_validate_node_descriptor, "node_descriptor/expired.signed.json", valid,
+          GCERT_STATUS_VALIDATION_FAILED, "Expired");
+    check(gcert_validate_node_descriptor, "node_descriptor/invalid_cert_chain_signature.signed.json", NULL,
+          GCERT_STATUS_VALIDATION_FAILED, "\"position\":{\"depth\":");
+    check(gcert_validate_node_descriptor, "node_descriptor/cert_cannot_sign_node.signed.json", NULL,
+          GCERT_STATUS_VALIDATION_FAILED, "\"kind\":\"NodeSignNotPermitted\"");
+
+    check(gcert_validate_node_descriptor, "node_descriptor/happy_path.signed.json", "yesterday",
+          GCERT_STATUS_INVALID_TIMESTAMP, "Invalid timestamp yesterday");
+    check(gcert_validate_node_descriptor, "node_descriptor/happy_path.signed.json", "yesterday",
+          GCERT_STATUS_INVALID_TIMESTAMP, "\"kind\":\"InvalidTimestamp\"");
+    check_data("invalid json", gcert_validate_certificate, "{", NULL, GCERT_STATUS_INVALID_JSON,
+               "\"kind\":\"InvalidJson\"");
+    check_data("null data", gcert_validate_certificate, NULL, NULL, GCERT_STATUS_INVALID_ARGUMENT,
+               "Argument `data` is null");
+
+    GcertStatus status = gcert_validate_certificate("{}", NULL, NULL);
+    expect("null output", status, GCERT_STATUS_INVALID_ARGUMENT, "", "");
+    gcert_free_string(NULL);
+
+    if (failures > 0) {
+        printf("%d case(s) failed\n", failures);
+        return 1;
+    }
+    printf("all cases passed\n");
+    return 0;
+}
@@ -0,0 +1,55 @@
+// The program is compiled with a unix C compiler and linked through its rpath
+#![cfg(unix)]
+
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Builds the shared library, which cargo does not build for integration tests, and returns its directory.
+/// The test binary is in the `deps` subdirectory of the library directory.
+fn build_library() -> PathBuf {
+    let mut build = Command::new(env!("CARGO"));
+    build.args(["build", "--lib", "--package", env!("CARGO_PKG_NAME")]);
+    if !cfg!(debug_assertions) {
+        build.arg("--release");
+    }
+    let build = build.status().unwrap();
+    assert!(build.success());
+    let test_binary = env::current_exe().unwrap();
+    test_binary
+        .parent()
+        .unwrap()
+        .parent()
+        .unwrap()
+        .to_path_buf()
+}
+
+#[test]
+fn validate_fixtures_from_c_program() {
+    let crate_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let library_dir = build_library();
+    let program = Path::new(env!("CARGO_TARGET_TMPDIR")).join("validate_fixtures");
+    let compiler = env::var("CC").unwrap_or_else(|_| "cc".into());
+
+    let compilation = Command::new(compiler)
+        .arg(crate_dir.join("tests/c/validate_fixtures.c"))
+        .arg("-I")
+        .arg(crate_dir.join("include"))
+        .arg("-L")
+        .arg(&library_dir)
+        .arg(format!("-Wl,-rpath,{}", library_dir.display()))
+        .arg("-lgolem_certificate_capi")
+        .arg("-o")
+        .arg(&program)
+        .status()
+        .expect("C compiler is available");
+    assert!(compilation.success());
+
+    let output = Command::new(&program)
+        .arg(crate_dir.join("../tests/resources"))
+        .output()
+        .unwrap();
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{stdout}");
+}
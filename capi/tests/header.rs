@@ -0,0 +1,25 @@
+use std::path::Path;
+
+// The header is kept in the repository, so it can be used without building the crate
+#[test]
+fn committed_header_is_up_to_date() {
+    let crate_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let config = cbindgen::Config::from_file(crate_dir.join("cbindgen.toml")).unwrap();
+    let mut generated = Vec::new();
+    cbindgen::Builder::new()
+        .with_crate(crate_dir)
+        .with_config(config)
+        .generate()
+        .expect("Unable to generate the C header")
+        .write(&mut generated);
+
+    // Checkouts on Windows may convert line endings
+    let committed = std::fs::read_to_string(crate_dir.join("include/golem_certificate.h"))
+        .unwrap()
+        .replace("\r\n", "\n");
+    assert!(
+        committed == String::from_utf8(generated).unwrap(),
+        "include/golem_certificate.h is outdated, regenerate it with \
+         `cbindgen --config cbindgen.toml --output include/golem_certificate.h` in the capi directory"
+    );
+}
@@ -1,18 +1,25 @@
+use std::fs;
+use std::io::Write;
 use std::path::{Path, PathBuf};
 
 use anyhow::{anyhow, Result};
-use chrono::{DateTime, Utc};
-use clap::{Args, Parser};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::{DateTime, Duration, Utc};
+use clap::{Args, Parser, ValueEnum};
 use hex::ToHex;
-use serde::Serialize;
-use serde_json::Value;
+use serde_json::{json, Value};
 
+use gcert::schemas::{
+    revocation_list::RevocationList, validity_period::ValidityPeriod,
+    SIGNED_REVOCATION_LIST_SCHEMA_ID,
+};
 use golem_certificate as gcert;
 
 mod utils;
 use utils::{
-    deserialize_from_file, determine_file_type, save_json_with_extension, save_signed_json,
-    FileType,
+    create_private_file, describe_lint_warning, describe_validation_error, deserialize_from_file,
+    determine_file_type, load_signing_key, prompt_new_passphrase, save_json_to_file,
+    save_json_with_extension, save_private_json_to_file, save_signed_json, FileType,
 };
 
 #[cfg(feature = "tui")]
@@ -28,32 +35,42 @@ use smartcard::{smartcard, SmartcardCommand};
 #[derive(Parser)]
 enum GolemCertificateCli {
     #[command(about = "Creates a new key pair")]
-    CreateKeyPair {
-        #[arg(
-            help = "Path to save the keypair to. Public key is saved with extension set to .pub.json, signing key is saved with extension .key.json"
-        )]
-        key_pair_path: PathBuf,
-    },
+    CreateKeyPair(CreateKeyPairArguments),
+    #[command(about = "Recovers a key pair from the words of its BIP39 mnemonic")]
+    RecoverKeyPair(RecoverKeyPairArguments),
+    #[command(
+        about = "Converts a key between the key json format, PKCS#8/SPKI PEM, OpenSSH and JWK"
+    )]
+    ConvertKey(ConvertKeyArguments),
     #[command(about = "Prints fingerprint of the signed property of the input file")]
     Fingerprint {
-        #[arg(help = "Path to a certificate or node descriptor")]
+        #[arg(help = "Path to a certificate, node descriptor, revocation list or manifest")]
         input_file_path: PathBuf,
     },
     #[command(about = "Creates self-signed certificate")]
     SelfSignCertificate(SelfSignArguments),
-    #[command(about = "Signs a certificate or node descriptor")]
+    #[command(about = "Signs a certificate, node descriptor, revocation list or manifest")]
     Sign(SignArguments),
     #[command(
-        about = "Verifies the signature and other constraints of the input certificate or node descriptor"
-    )]
-    Verify {
-        #[arg(help = "Path to a signed certificate or node descriptor")]
-        signed_file_path: PathBuf,
-        #[arg(value_parser = parse_timestamp)]
-        #[arg(
-            help = "Optional RFC 3339 formatted timestamp (ex: 2020-01-01T13:42:33Z) to verify validity. 'now' can be used to refer to current time."
-        )]
-        timestamp: Option<DateTime<Utc>>,
+        about = "Encodes a computation manifest json to base64 and signs it with a certificate permitted to sign manifests"
+    )]
+    SignManifest(SignManifestArguments),
+    #[command(
+        about = "Counter-signs a signed node descriptor with the secp256k1 key of its node identity, proving possession of the node key"
+    )]
+    SignNodeIdentity(SignNodeIdentityArguments),
+    #[command(
+        about = "Verifies the signature and other constraints of the input certificate, node descriptor, revocation list or manifest"
+    )]
+    Verify(VerifyArguments),
+    #[command(
+        about = "Creates an unsigned revocation list. Use the sign command to sign it with a certificate permitted to sign revocation lists"
+    )]
+    CreateRevocationList(CreateRevocationListArguments),
+    #[command(about = "Verifies the revocation list and prints its details")]
+    InspectRevocationList {
+        #[arg(help = "Path to a signed revocation list")]
+        revocation_list_path: PathBuf,
     },
     #[cfg(feature = "tui")]
     #[command(about = "Starts Golem Certificate Manager")]
@@ -66,6 +83,103 @@ enum GolemCertificateCli {
     },
 }
 
+#[derive(Args)]
+struct CreateKeyPairArguments {
+    #[arg(
+        help = "Path to save the keypair to. Public key is saved with extension set to .pub.json, signing key is saved with extension .key.json"
+    )]
+    key_pair_path: PathBuf,
+    #[arg(long)]
+    #[arg(help = "Encrypt the signing key with a passphrase, asked for interactively")]
+    encrypt: bool,
+    #[arg(long)]
+    #[arg(
+        help = "Derive the key pair from a new BIP39 mnemonic. The words are printed to be written down, recover the key pair with the recover-key-pair command"
+    )]
+    mnemonic: bool,
+    #[arg(long, conflicts_with = "mnemonic")]
+    #[arg(
+        help = "Create a secp256k1 key pair of a node identity, used by the sign-node-identity command. The node id is printed"
+    )]
+    secp256k1: bool,
+    #[arg(long, requires = "mnemonic", default_value_t = gcert::mnemonic::DEFAULT_WORD_COUNT)]
+    #[arg(help = "Number of words of the mnemonic: 12, 15, 18, 21 or 24")]
+    words: usize,
+    #[command(flatten)]
+    derivation: MnemonicDerivationArguments,
+}
+
+#[derive(Args)]
+struct RecoverKeyPairArguments {
+    #[arg(
+        help = "Path to save the recovered keypair to. Public key is saved with extension set to .pub.json, signing key is saved with extension .key.json"
+    )]
+    key_pair_path: PathBuf,
+    #[arg(long)]
+    #[arg(help = "Encrypt the signing key with a passphrase, asked for interactively")]
+    encrypt: bool,
+    #[command(flatten)]
+    derivation: MnemonicDerivationArguments,
+}
+
+#[derive(Args)]
+struct MnemonicDerivationArguments {
+    #[arg(long, default_value = gcert::mnemonic::DEFAULT_DERIVATION_PATH)]
+    #[arg(
+        help = "SLIP-0010 derivation path of the key, only hardened indexes are supported (ex: m/0'/1')"
+    )]
+    derivation_path: String,
+    #[arg(long)]
+    #[arg(help = "Protect the mnemonic with a BIP39 passphrase, asked for interactively")]
+    mnemonic_passphrase: bool,
+}
+
+#[derive(Args)]
+struct ConvertKeyArguments {
+    #[arg(
+        help = "Path to the key to convert. The format is detected from the contents, key json files can be encrypted"
+    )]
+    input_path: PathBuf,
+    #[arg(help = "Path to save the converted key to")]
+    output_path: PathBuf,
+    #[arg(long, value_enum)]
+    #[arg(help = "Format of the converted key")]
+    to: KeyFileFormat,
+    #[arg(long)]
+    #[arg(help = "Export only the public key of a private key")]
+    public: bool,
+    #[arg(long, value_enum)]
+    #[arg(
+        help = "Type of a key json input. Defaults to private for .key.json and public for .pub.json files"
+    )]
+    input_key_type: Option<KeyType>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum KeyFileFormat {
+    KeyJson,
+    Pem,
+    Openssh,
+    Jwk,
+}
+
+impl KeyFileFormat {
+    fn key_format(&self) -> Option<gcert::KeyFormat> {
+        match self {
+            KeyFileFormat::KeyJson => None,
+            KeyFileFormat::Pem => Some(gcert::KeyFormat::Pem),
+            KeyFileFormat::Openssh => Some(gcert::KeyFormat::OpenSsh),
+            KeyFileFormat::Jwk => Some(gcert::KeyFormat::Jwk),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+enum KeyType {
+    Private,
+    Public,
+}
+
 #[derive(Args)]
 struct SelfSignArguments {
     #[arg(
@@ -85,11 +199,110 @@ struct SignArguments {
     #[arg(help = "Path to the signing certificate")]
     certificate_path: PathBuf,
     #[arg(
-        help = "Path to the signing key associated with the public key in the signing certificate"
+        help = "Path to the signing key associated with the public key in the signing certificate. The passphrase is asked for when the key is encrypted"
     )]
     signing_key_path: PathBuf,
 }
 
+#[derive(Args)]
+struct SignManifestArguments {
+    #[arg(
+        help = "Path to the computation manifest json. Signed manifest is saved to the same path with extension set to .signed.json"
+    )]
+    manifest_path: PathBuf,
+    #[arg(help = "Path to the signing certificate")]
+    certificate_path: PathBuf,
+    #[arg(
+        help = "Path to the signing key associated with the public key in the signing certificate. The passphrase is asked for when the key is encrypted"
+    )]
+    signing_key_path: PathBuf,
+}
+
+#[derive(Args)]
+struct SignNodeIdentityArguments {
+    #[arg(
+        help = "Path to the signed node descriptor. It is replaced with the counter-signed node descriptor"
+    )]
+    node_descriptor_path: PathBuf,
+    #[arg(
+        help = "Path to the secp256k1 signing key of the node identity. The passphrase is asked for when the key is encrypted"
+    )]
+    node_signing_key_path: PathBuf,
+}
+
+#[derive(Args)]
+struct VerifyArguments {
+    #[arg(help = "Path to a signed certificate, node descriptor, revocation list or manifest")]
+    signed_file_path: PathBuf,
+    #[arg(value_parser = parse_timestamp)]
+    #[arg(
+        help = "Optional RFC 3339 formatted timestamp (ex: 2020-01-01T13:42:33Z) to verify validity. 'now' can be used to refer to current time."
+    )]
+    timestamp: Option<DateTime<Utc>>,
+    #[arg(long)]
+    #[arg(
+        help = "Path to a trusted root certificate or public key, or to a directory of them. When set, the document has to be anchored in one of the trusted roots"
+    )]
+    trust_store: Option<PathBuf>,
+    #[arg(long = "revocation-list")]
+    #[arg(
+        help = "Path to a signed revocation list. The document is rejected if its chain contains a revoked certificate. Can be used multiple times"
+    )]
+    revocation_lists: Vec<PathBuf>,
+    #[arg(long)]
+    #[arg(
+        help = "Reject node descriptors that are not counter-signed with the key of the node identity"
+    )]
+    require_node_signature: bool,
+    #[arg(long, value_name = "SECONDS", default_value_t = 0)]
+    #[arg(
+        help = "Accept documents that are not valid yet or expired by at most this many seconds at the timestamp"
+    )]
+    clock_skew: u32,
+    #[arg(long)]
+    #[arg(
+        help = "Verify validity at the timestamp of every certificate in the chain, not just of the document"
+    )]
+    check_whole_chain: bool,
+    #[arg(long)]
+    #[arg(
+        help = "Reject documents with more certificates above them in the chain, 0 accepts only self-signed certificates"
+    )]
+    max_chain_depth: Option<usize>,
+}
+
+impl VerifyArguments {
+    fn validation_options(&self) -> Result<gcert::ValidationOptions> {
+        let mut options = gcert::ValidationOptions::from(self.timestamp)
+            .with_clock_skew(Duration::seconds(self.clock_skew.into()))
+            .with_whole_chain_checked(self.check_whole_chain)
+            .with_node_signature_required(self.require_node_signature);
+        if let Some(max_chain_depth) = self.max_chain_depth {
+            options = options.with_max_chain_depth(max_chain_depth);
+        }
+        if let Some(trust_store) = &self.trust_store {
+            options = options.with_trust_store(gcert::TrustStore::from_path(trust_store)?);
+        }
+        Ok(options)
+    }
+}
+
+#[derive(Args)]
+struct CreateRevocationListArguments {
+    #[arg(
+        help = "Path to save the revocation list to. Extension is set to .json, the signed revocation list is saved with extension .signed.json"
+    )]
+    revocation_list_path: PathBuf,
+    #[arg(long, value_parser = parse_timestamp)]
+    #[arg(help = "RFC 3339 formatted start of the validity period. Defaults to 'now'")]
+    not_before: Option<DateTime<Utc>>,
+    #[arg(long, value_parser = parse_timestamp)]
+    #[arg(help = "RFC 3339 formatted end of the validity period")]
+    not_after: DateTime<Utc>,
+    #[arg(help = "Fingerprints of the revoked certificates")]
+    revoked_certificates: Vec<String>,
+}
+
 fn parse_timestamp(timestamp: &str) -> Result<DateTime<Utc>> {
     if timestamp == "now" {
         Ok(Utc::now())
@@ -98,34 +311,124 @@ fn parse_timestamp(timestamp: &str) -> Result<DateTime<Utc>> {
     }
 }
 
-fn create_key_pair(key_pair_path: &Path) -> Result<()> {
-    let key_pair = gcert::create_key_pair();
+fn create_key_pair(arguments: &CreateKeyPairArguments) -> Result<()> {
+    let key_pair = if arguments.mnemonic {
+        let passphrase = if arguments.derivation.mnemonic_passphrase {
+            prompt_new_passphrase("mnemonic passphrase")?
+        } else {
+            String::new()
+        };
+        let mnemonic_key_pair = gcert::create_key_pair_with_mnemonic(
+            arguments.words,
+            &passphrase,
+            &arguments.derivation.derivation_path,
+        )?;
+        println!("Write down the mnemonic and keep it safe, it recovers the key pair:");
+        println!("{}", mnemonic_key_pair.mnemonic);
+        mnemonic_key_pair.key_pair
+    } else if arguments.secp256k1 {
+        let key_pair = gcert::create_secp256k1_key_pair();
+        println!("Node id: {}", key_pair.public_key.node_id()?);
+        key_pair
+    } else {
+        gcert::create_key_pair()
+    };
+    save_key_pair(&arguments.key_pair_path, &key_pair, arguments.encrypt)
+}
+
+fn recover_key_pair(arguments: &RecoverKeyPairArguments) -> Result<()> {
+    let mnemonic = rpassword::prompt_password("Enter mnemonic words: ")?;
+    let passphrase = if arguments.derivation.mnemonic_passphrase {
+        rpassword::prompt_password("Enter mnemonic passphrase: ")?
+    } else {
+        String::new()
+    };
+    let key_pair = gcert::recover_key_pair(
+        &mnemonic,
+        &passphrase,
+        &arguments.derivation.derivation_path,
+    )?;
+    save_key_pair(&arguments.key_pair_path, &key_pair, arguments.encrypt)
+}
+
+fn save_key_pair(key_pair_path: &Path, key_pair: &gcert::KeyPair, encrypt: bool) -> Result<()> {
     save_json_with_extension(key_pair_path, &key_pair.public_key, "pub.json")?;
-    save_json_with_extension(key_pair_path, &key_pair.private_key, "key.json")
+    if encrypt {
+        let passphrase = prompt_new_passphrase("key file passphrase")?;
+        let encrypted_key = gcert::EncryptedKey::encrypt(&key_pair.private_key, &passphrase)?;
+        save_private_json_to_file(key_pair_path.with_extension("key.json"), &encrypted_key)
+    } else {
+        save_private_json_to_file(
+            key_pair_path.with_extension("key.json"),
+            &key_pair.private_key,
+        )
+    }
+}
+
+/// Key json files do not record whether they hold a private or a public key, it is taken from the arguments or the file name.
+fn import_key_json(path: &Path, key_type: Option<KeyType>) -> Result<gcert::ImportedKey> {
+    let file_name = path.to_string_lossy();
+    let key_type = key_type
+        .or_else(|| {
+            if file_name.ends_with(".key.json") {
+                Some(KeyType::Private)
+            } else if file_name.ends_with(".pub.json") {
+                Some(KeyType::Public)
+            } else {
+                None
+            }
+        })
+        .ok_or_else(|| {
+            anyhow!("Cannot determine whether {file_name} holds a private or a public key, use --input-key-type")
+        })?;
+    match key_type {
+        KeyType::Private => Ok(gcert::ImportedKey::from_private_key(load_signing_key(
+            path,
+        )?)?),
+        KeyType::Public => Ok(gcert::ImportedKey {
+            public_key: deserialize_from_file(path)?,
+            private_key: None,
+        }),
+    }
+}
+
+fn convert_key(arguments: &ConvertKeyArguments) -> Result<()> {
+    let input = fs::read_to_string(&arguments.input_path)?;
+    let imported_key = match gcert::KeyFormat::detect(&input) {
+        Some(_) => gcert::import_key(&input)?,
+        None => import_key_json(&arguments.input_path, arguments.input_key_type)?,
+    };
+    let private_key = imported_key.private_key.filter(|_| !arguments.public);
+    let output = match (arguments.to.key_format(), &private_key) {
+        (Some(format), Some(private_key)) => gcert::export_private_key(private_key, format)?,
+        (Some(format), None) => gcert::export_public_key(&imported_key.public_key, format)?,
+        (None, private_key) => {
+            let key = private_key.as_ref().unwrap_or(&imported_key.public_key);
+            serde_json::to_string_pretty(key)? + "\n"
+        }
+    };
+    if private_key.is_some() {
+        create_private_file(&arguments.output_path)?.write_all(output.as_bytes())?;
+    } else {
+        fs::write(&arguments.output_path, output)?;
+    }
+    Ok(())
 }
 
 fn print_fingerprint(input_file_path: &Path) -> Result<()> {
     let input_json = deserialize_from_file::<Value>(input_file_path)?;
     let signed_property = determine_file_type(&input_json)?.signed_property();
     let signed_data = &input_json[signed_property];
-    let fingerprint = gcert::create_default_hash(signed_data)?;
+    let hash_algorithm: gcert::HashAlgorithm = input_json["signature"]["algorithm"]
+        .get("hash")
+        .map(|hash| serde_json::from_value(hash.clone()))
+        .transpose()?
+        .unwrap_or_default();
+    let fingerprint = gcert::create_hash(signed_data, &hash_algorithm)?;
     println!("{}", fingerprint.encode_hex::<String>());
     Ok(())
 }
 
-fn sign_json_value(
-    value: &Value,
-    signing_key_path: &Path,
-) -> Result<(gcert::SignatureAlgorithm, Vec<u8>)> {
-    let signing_key = deserialize_from_file(signing_key_path)?;
-    gcert::sign_json(value, &signing_key)
-}
-
-fn add_signature<S: Serialize>(value: &mut Value, signature: gcert::Signature<S>) -> Result<()> {
-    value["signature"] = serde_json::to_value(signature)?;
-    Ok(())
-}
-
 fn self_sign_certificate(self_sign_arguments: &SelfSignArguments) -> Result<()> {
     let mut certificate = deserialize_from_file::<Value>(&self_sign_arguments.certificate_path)?;
     let file_type = determine_file_type(&certificate)?;
@@ -136,57 +439,184 @@ fn self_sign_certificate(self_sign_arguments: &SelfSignArguments) -> Result<()>
             self_sign_arguments.certificate_path
         )),
     }?;
-    let signed_data = &certificate[signed_property];
-    let (algorithm, signature_value) =
-        sign_json_value(signed_data, &self_sign_arguments.signing_key_path)?;
-    let signature = gcert::Signature::create_self_signed(algorithm, signature_value);
-    add_signature(&mut certificate, signature)?;
-    save_signed_json(&self_sign_arguments.certificate_path, &certificate)
+    let certificate = serde_json::from_value(certificate[signed_property].take())?;
+    let signing_key = load_signing_key(&self_sign_arguments.signing_key_path)?;
+    let signed_certificate = gcert::CertificateBuilder::new(certificate).self_sign(&signing_key)?;
+    save_signed_json(&self_sign_arguments.certificate_path, &signed_certificate)
 }
 
+/// Documents are checked against the constraints of the signing certificate before signing.
 fn sign_json(sign_arguments: &SignArguments) -> Result<()> {
     let mut input_json = deserialize_from_file::<Value>(&sign_arguments.input_file_path)?;
-    let signed_property = determine_file_type(&input_json)?.signed_property();
-    let signed_data = &input_json[signed_property];
-    let (algorithm, signature_value) =
-        sign_json_value(signed_data, &sign_arguments.signing_key_path)?;
+    let file_type = determine_file_type(&input_json)?;
+    let signed_data = input_json[file_type.signed_property()].take();
+    let signing_key = load_signing_key(&sign_arguments.signing_key_path)?;
     let certificate = deserialize_from_file(&sign_arguments.certificate_path)?;
-    let signature = gcert::Signature::create(algorithm, signature_value, certificate);
-    add_signature(&mut input_json, signature)?;
-    save_signed_json(&sign_arguments.input_file_path, &input_json)
+    match file_type {
+        FileType::Certificate => {
+            let signed_certificate =
+                gcert::CertificateBuilder::new(serde_json::from_value(signed_data)?)
+                    .sign(certificate, &signing_key)?;
+            save_signed_json(&sign_arguments.input_file_path, &signed_certificate)
+        }
+        FileType::NodeDescriptor => {
+            let signed_node_descriptor =
+                gcert::NodeDescriptorBuilder::new(serde_json::from_value(signed_data)?)
+                    .sign(certificate, &signing_key)?;
+            save_signed_json(&sign_arguments.input_file_path, &signed_node_descriptor)
+        }
+        FileType::Manifest => {
+            let manifest = signed_data
+                .as_str()
+                .ok_or_else(|| anyhow!("Manifest has to be a base64 encoded string"))?;
+            let signed_manifest = gcert::sign_manifest(manifest, certificate, &signing_key)?;
+            save_signed_json(&sign_arguments.input_file_path, &signed_manifest)
+        }
+        FileType::RevocationList => {
+            let signed_revocation_list =
+                gcert::RevocationListBuilder::new(serde_json::from_value(signed_data)?)
+                    .sign(certificate, &signing_key)?;
+            save_signed_json(&sign_arguments.input_file_path, &signed_revocation_list)
+        }
+    }
 }
 
-/// Determines type of signed file (Certificate or Node Descriptor) and then verifies its signature.
+fn sign_manifest(arguments: &SignManifestArguments) -> Result<()> {
+    let manifest = STANDARD.encode(fs::read(&arguments.manifest_path)?);
+    let signing_key = load_signing_key(&arguments.signing_key_path)?;
+    let certificate = deserialize_from_file(&arguments.certificate_path)?;
+    let signed_manifest = gcert::sign_manifest(&manifest, certificate, &signing_key)?;
+    save_signed_json(&arguments.manifest_path, &signed_manifest)
+}
+
+/// The counter-signature does not change the signed node descriptor, so the file is replaced.
+fn sign_node_identity(arguments: &SignNodeIdentityArguments) -> Result<()> {
+    let signed_node_descriptor = deserialize_from_file(&arguments.node_descriptor_path)?;
+    let node_signing_key = load_signing_key(&arguments.node_signing_key_path)?;
+    let signed_node_descriptor =
+        gcert::sign_node_identity(signed_node_descriptor, &node_signing_key)?;
+    save_json_to_file(&arguments.node_descriptor_path, &signed_node_descriptor)
+}
+
+/// Determines type of signed file (Certificate, Node Descriptor, Revocation List or Manifest) and then verifies its signature.
 /// # Arguments
-/// * `signed_file` path to signed file
-/// * `timestamp` optional timestamp to verify validity
-fn verify_signature(signed_file: &Path, timestamp: Option<DateTime<Utc>>) -> Result<()> {
-    let signed_json = deserialize_from_file::<Value>(signed_file)?;
+/// * `verify_arguments` path to signed file, validation options, trusted roots and revocation lists
+fn verify_signature(verify_arguments: &VerifyArguments) -> Result<()> {
+    let VerifyArguments {
+        signed_file_path,
+        revocation_lists,
+        ..
+    } = verify_arguments;
+    let signed_json = deserialize_from_file::<Value>(signed_file_path)?;
+    for warning in gcert::lint_document(&signed_json) {
+        eprintln!("Warning: {}", describe_lint_warning(&warning));
+    }
+    let options = verify_arguments.validation_options()?;
+    let revocation_lists = load_revocation_lists(revocation_lists, &options)?;
+    let options = options.with_revocation_lists(revocation_lists);
     match determine_file_type(&signed_json)? {
-        FileType::Certificate => gcert::validate_certificate(signed_json, timestamp)
-            .map(|result| println!("{:?}", result))
-            .map_err(Into::into),
-        FileType::NodeDescriptor => gcert::validate_node_descriptor(signed_json, timestamp)
-            .map(|result| println!("{:?}", result))
-            .map_err(Into::into),
+        FileType::Certificate => {
+            let result = gcert::validate_certificate_with_options(signed_json, &options)
+                .map_err(validation_error)?;
+            println!("{:?}", result);
+        }
+        FileType::NodeDescriptor => {
+            let result = gcert::validate_node_descriptor_with_options(signed_json, &options)
+                .map_err(validation_error)?;
+            println!("{:?}", result);
+        }
+        FileType::RevocationList => {
+            let result = gcert::validate_revocation_list_with_options(signed_json, &options)
+                .map_err(validation_error)?;
+            println!("{:?}", result);
+        }
+        FileType::Manifest => {
+            let result = gcert::validate_manifest_with_options(signed_json, &options)
+                .map_err(validation_error)?;
+            println!("{:?}", result);
+        }
     }
+    Ok(())
+}
+
+fn validation_error(error: gcert::Error) -> anyhow::Error {
+    anyhow!(describe_validation_error(&error))
+}
+
+fn load_revocation_lists(
+    revocation_list_paths: &[PathBuf],
+    options: &gcert::ValidationOptions,
+) -> Result<gcert::RevocationLists> {
+    let mut revocation_lists = gcert::RevocationLists::new();
+    for path in revocation_list_paths {
+        let value = deserialize_from_file::<Value>(path)?;
+        let revocation_list = gcert::validate_revocation_list_with_options(value, options)
+            .map_err(|e| anyhow!("Invalid revocation list {:?}: {}", path, e))?;
+        revocation_lists.add(revocation_list);
+    }
+    Ok(revocation_lists)
+}
+
+fn create_revocation_list(arguments: &CreateRevocationListArguments) -> Result<()> {
+    let revocation_list = RevocationList {
+        revoked_certificates: arguments.revoked_certificates.iter().cloned().collect(),
+        validity_period: ValidityPeriod {
+            not_before: arguments.not_before.unwrap_or_else(Utc::now),
+            not_after: arguments.not_after,
+        },
+    };
+    let document = json!({
+        "$schema": SIGNED_REVOCATION_LIST_SCHEMA_ID,
+        "revocationList": revocation_list,
+    });
+    save_json_with_extension(&arguments.revocation_list_path, &document, "json")
+}
+
+fn inspect_revocation_list(revocation_list_path: &Path) -> Result<()> {
+    let signed_json = deserialize_from_file::<Value>(revocation_list_path)?;
+    let signed_revocation_list: gcert::SignedRevocationList =
+        serde_json::from_value(signed_json.clone())?;
+    let validated = gcert::validate_revocation_list(signed_json, None)?;
+    let signer = &signed_revocation_list.signature.signer.certificate["subject"]["displayName"];
+    println!("Signed by: {}", signer.as_str().unwrap_or("unknown"));
+    println!("Signer chain (leaf first):");
+    for fingerprint in validated.certificate_chain_fingerprints.iter() {
+        println!("  {}", fingerprint);
+    }
+    println!("Not before: {}", validated.validity_period.not_before);
+    println!("Not after:  {}", validated.validity_period.not_after);
+    let mut revoked = validated
+        .revoked_certificates
+        .into_iter()
+        .collect::<Vec<_>>();
+    revoked.sort();
+    println!("Revoked certificates:");
+    for fingerprint in revoked {
+        println!("  {}", fingerprint);
+    }
+    Ok(())
 }
 
 fn main() -> Result<()> {
     match GolemCertificateCli::parse() {
-        GolemCertificateCli::CreateKeyPair { key_pair_path } => create_key_pair(&key_pair_path),
+        GolemCertificateCli::CreateKeyPair(arguments) => create_key_pair(&arguments),
+        GolemCertificateCli::RecoverKeyPair(arguments) => recover_key_pair(&arguments),
+        GolemCertificateCli::ConvertKey(arguments) => convert_key(&arguments),
         GolemCertificateCli::Fingerprint { input_file_path } => print_fingerprint(&input_file_path),
         GolemCertificateCli::SelfSignCertificate(self_sign_arguments) => {
             self_sign_certificate(&self_sign_arguments)
         }
         GolemCertificateCli::Sign(sign_arguments) => sign_json(&sign_arguments),
-        GolemCertificateCli::Verify {
-            signed_file_path,
-            timestamp,
-        } => verify_signature(&signed_file_path, timestamp),
+        GolemCertificateCli::SignManifest(arguments) => sign_manifest(&arguments),
+        GolemCertificateCli::SignNodeIdentity(arguments) => sign_node_identity(&arguments),
+        GolemCertificateCli::Verify(verify_arguments) => verify_signature(&verify_arguments),
+        GolemCertificateCli::CreateRevocationList(arguments) => create_revocation_list(&arguments),
+        GolemCertificateCli::InspectRevocationList {
+            revocation_list_path,
+        } => inspect_revocation_list(&revocation_list_path),
         #[cfg(feature = "tui")]
         GolemCertificateCli::Ui => app::start(),
         #[cfg(feature = "smartcard")]
-        GolemCertificateCli::Smartcard { cmd } => smartcard(cmd).map_err(Into::into),
+        GolemCertificateCli::Smartcard { cmd } => smartcard(cmd),
     }
 }
@@ -147,7 +147,7 @@ impl DocumentEditor for CertificateEditor {
         if let Some(key) = self.public_key_editor.get_key() {
             let cert = Certificate {
                 key_usage: self.key_usage_editor.get_key_usage(),
-                permissions: self.permissions_editor.get_permissions(),
+                permissions: self.permissions_editor.get_permissions()?,
                 public_key: key,
                 subject: self.subject_editor.get_subject(),
                 validity_period: self.validity_period_editor.get_validity_period(),
@@ -158,15 +158,15 @@ impl DocumentEditor for CertificateEditor {
         }
     }
 
-    fn get_template_json(&self) -> Value {
+    fn get_template_json(&self) -> Result<Value> {
         let certificate = CertificateTemplate {
             key_usage: Some(self.key_usage_editor.get_key_usage()),
-            permissions: Some(self.permissions_editor.get_permissions()),
+            permissions: Some(self.permissions_editor.get_permissions()?),
             public_key: self.public_key_editor.get_key(),
             subject: Some(self.subject_editor.get_subject()),
             validity_period: Some(self.validity_period_editor.get_validity_period()),
         };
-        json!({ "$schema": SIGNED_CERTIFICATE_SCHEMA_ID, "certificate": certificate })
+        Ok(json!({ "$schema": SIGNED_CERTIFICATE_SCHEMA_ID, "certificate": certificate }))
     }
 
     fn create_signed_document(
@@ -1,13 +1,11 @@
+use std::collections::HashSet;
 use std::fmt::Write;
 
 use golem_certificate::{
     schemas::{
         certificate::{key_usage::KeyUsage, Certificate},
         node_descriptor::NodeDescriptor,
-        permissions::{
-            OutboundPermissions::{Unrestricted, Urls},
-            Permissions,
-        },
+        permissions::{EntryPermissions, OutboundPermissions, PermissionEntry, Permissions},
         subject::Subject,
         validity_period::ValidityPeriod,
     },
@@ -35,6 +33,7 @@ pub fn node_descriptor_to_string(
     let node_descriptor: NodeDescriptor =
         serde_json::from_value(signed_node_descriptor.node_descriptor.clone()).unwrap();
     write_node_id(&mut buf, &node_descriptor.node_id);
+    write_node_signature(&mut buf, signed_node_descriptor.node_signature.is_some());
     buf.add_empty_line();
     write_validity_period(&mut buf, &node_descriptor.validity_period);
     buf.add_empty_line();
@@ -181,28 +180,69 @@ fn write_permissions(buf: &mut StringBuffer, permissions: &Permissions) {
     match permissions {
         Permissions::All => writeln!(buf.buf_mut(), ": All").unwrap(),
         Permissions::Object(details) => {
-            if let Some(outbound) = &details.outbound {
-                writeln!(buf.buf_mut()).unwrap();
-                buf.increase_indent_level();
-                write!(buf.buf_mut_with_indent(), "Outbound").unwrap();
-                match outbound {
-                    Unrestricted => writeln!(buf.buf_mut(), ": Unrestricted").unwrap(),
-                    Urls(urls) => {
-                        let mut array = urls
-                            .iter()
-                            .map(|url| url.clone().into())
-                            .collect::<Vec<String>>();
-                        array.sort();
-                        writeln!(buf.buf_mut()).unwrap();
+            // `None` items for unrestricted categories
+            let categories = [
+                (
+                    "Outbound",
+                    details.outbound.as_ref().map(|outbound| match outbound {
+                        OutboundPermissions::Unrestricted => None,
+                        OutboundPermissions::Urls(urls) => Some(sorted_strings(urls)),
+                    }),
+                ),
+                ("Inbound", details.inbound.as_ref().map(entry_strings)),
+                ("Runtimes", details.runtimes.as_ref().map(entry_strings)),
+                (
+                    "Payment platforms",
+                    details.payment_platforms.as_ref().map(entry_strings),
+                ),
+            ];
+            if categories.iter().all(|(_, category)| category.is_none())
+                && details.additional_properties.is_empty()
+            {
+                writeln!(buf.buf_mut(), ": None").unwrap();
+                return;
+            }
+            writeln!(buf.buf_mut()).unwrap();
+            buf.increase_indent_level();
+            for (name, category) in categories {
+                match category {
+                    None => (),
+                    Some(None) => {
+                        writeln!(buf.buf_mut_with_indent(), "{name}: Unrestricted").unwrap()
+                    }
+                    Some(Some(items)) => {
+                        writeln!(buf.buf_mut_with_indent(), "{name}").unwrap();
                         buf.increase_indent_level();
-                        write_array(buf, &array, Direction::Vertical);
+                        write_array(buf, &items, Direction::Vertical);
                         buf.decrease_indent_level();
                     }
                 }
-                buf.decrease_indent_level();
-            } else {
-                writeln!(buf.buf_mut(), ": None").unwrap();
             }
+            let mut additional_properties =
+                details.additional_properties.iter().collect::<Vec<_>>();
+            additional_properties.sort_by_key(|(name, _)| *name);
+            for (name, value) in additional_properties {
+                writeln!(buf.buf_mut_with_indent(), "{name} (unknown): {value}").unwrap();
+            }
+            buf.decrease_indent_level();
+        }
+    }
+}
+
+fn sorted_strings<T: Ord + ToString>(set: &HashSet<T>) -> Vec<String> {
+    let mut items = set.iter().collect::<Vec<_>>();
+    items.sort();
+    items.into_iter().map(ToString::to_string).collect()
+}
+
+/// `None` when unrestricted, entries are already sorted
+fn entry_strings<T: PermissionEntry + ToString>(
+    permissions: &EntryPermissions<T>,
+) -> Option<Vec<String>> {
+    match permissions {
+        EntryPermissions::Unrestricted => None,
+        EntryPermissions::Entries(entries) => {
+            Some(entries.iter().map(ToString::to_string).collect())
         }
     }
 }
@@ -257,6 +297,16 @@ fn write_node_id(buf: &mut StringBuffer, node_id: &NodeId) {
     writeln!(buf.buf_mut_with_indent(), "Node ID: {}", node_id).unwrap();
 }
 
+fn write_node_signature(buf: &mut StringBuffer, node_signed: bool) {
+    let node_signature = if node_signed { "present" } else { "absent" };
+    writeln!(
+        buf.buf_mut_with_indent(),
+        "Node signature: {}",
+        node_signature
+    )
+    .unwrap();
+}
+
 fn write_object(object: &Map<String, Value>, buf: &mut StringBuffer) {
     for (key, value) in object.iter() {
         write!(buf.buf_mut_with_indent(), "{}", key).unwrap();
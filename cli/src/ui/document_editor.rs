@@ -31,7 +31,7 @@ pub trait DocumentEditor {
     fn editors_mut(&mut self) -> Vec<&mut dyn EditorComponent>;
     fn load_template(&mut self, template: Value);
     fn get_document(&self) -> Result<Value>;
-    fn get_template_json(&self) -> Value;
+    fn get_template_json(&self) -> Result<Value>;
     fn create_signed_document(
         &self,
         algorithm: SignatureAlgorithm,
@@ -179,8 +179,13 @@ impl SignedDocumentEditor {
                 self.save_json(&path, &value);
             }
         } else {
-            let value = self.document_editor.get_template_json();
-            self.save_json(&path, &value);
+            match self.document_editor.get_template_json() {
+                Ok(value) => self.save_json(&path, &value),
+                Err(err) => {
+                    let error = ModalMessage::new("Error saving file", err.to_string());
+                    self.error = Some(error);
+                }
+            }
         }
     }
 
@@ -2,11 +2,15 @@ use super::*;
 
 use std::{fs, path::PathBuf};
 
-use anyhow::Result;
-use golem_certificate::Key;
+use anyhow::{anyhow, Result};
+use golem_certificate::{import_key, EncryptedKey, Key, KeyFormat};
+use serde_json::Value;
 
 use crate::ui::{
-    modal::ModalWithComponent, open_file_dialog::OpenFileDialog, util::reduce_area_fixed,
+    modal::ModalWithComponent,
+    open_file_dialog::OpenFileDialog,
+    text_input::TextInput,
+    util::{reduce_area_fixed, AreaCalculators},
 };
 
 struct KeyFile {
@@ -14,10 +18,25 @@ struct KeyFile {
     key: Key,
 }
 
+enum LoadedKey {
+    Plain(KeyFile),
+    Encrypted {
+        filename: String,
+        encrypted_key: EncryptedKey,
+    },
+}
+
+struct PassphraseDialog {
+    filename: String,
+    encrypted_key: EncryptedKey,
+    dialog: ModalWithComponent<TextInput>,
+}
+
 pub struct KeyEditor {
     key_type: String,
     key: Option<KeyFile>,
     open_file_dialog: Option<ModalWithComponent<OpenFileDialog>>,
+    passphrase_dialog: Option<PassphraseDialog>,
     error_message: Option<ModalMessage>,
     active: bool,
 }
@@ -45,6 +64,7 @@ impl Default for KeyEditor {
             key_type: "Public".into(),
             key: None,
             open_file_dialog: None,
+            passphrase_dialog: None,
             error_message: None,
             active: false,
         }
@@ -75,17 +95,56 @@ impl EditorComponent for KeyEditor {
                 _ => self.error_message = None,
             }
             EditorEventResult::KeepActive
+        } else if let Some(passphrase_dialog) = self.passphrase_dialog.as_mut() {
+            if let Ok(status) = passphrase_dialog.dialog.handle_key_event(key_event) {
+                match status {
+                    ComponentStatus::Active => (),
+                    ComponentStatus::Closed => {
+                        let passphrase = passphrase_dialog.dialog.get_component().get_text();
+                        match passphrase_dialog.encrypted_key.decrypt(passphrase) {
+                            Ok(key) => {
+                                let filename = passphrase_dialog.filename.clone();
+                                self.key = Some(KeyFile { filename, key });
+                                self.passphrase_dialog = None;
+                            }
+                            Err(err) => {
+                                self.error_message =
+                                    Some(ModalMessage::new("Error decrypting key", err.to_string()))
+                            }
+                        }
+                    }
+                    ComponentStatus::Escaped => self.passphrase_dialog = None,
+                }
+            }
+            EditorEventResult::KeepActive
         } else if let Some(open_file_dialog) = self.open_file_dialog.as_mut() {
             if let Ok(status) = open_file_dialog.handle_key_event(key_event) {
                 match status {
                     ComponentStatus::Active => (),
                     ComponentStatus::Closed => {
                         if let Some(path) = open_file_dialog.get_component().selected.as_ref() {
-                            match load_key(path) {
-                                Ok(key) => {
+                            let private_key = self.key_type == "Signing";
+                            match load_key(path, private_key) {
+                                Ok(LoadedKey::Plain(key)) => {
                                     self.key = Some(key);
                                     self.open_file_dialog = None;
                                 }
+                                Ok(LoadedKey::Encrypted {
+                                    filename,
+                                    encrypted_key,
+                                }) => {
+                                    let dialog = ModalWithComponent::new(
+                                        format!("Passphrase for {}", filename),
+                                        TextInput::new(255, true),
+                                        passphrase_area(),
+                                    );
+                                    self.passphrase_dialog = Some(PassphraseDialog {
+                                        filename,
+                                        encrypted_key,
+                                        dialog,
+                                    });
+                                    self.open_file_dialog = None;
+                                }
                                 Err(err) => {
                                     self.error_message = Some(ModalMessage::new(
                                         "Error loading key",
@@ -158,6 +217,9 @@ impl EditorComponent for KeyEditor {
         if let Some(open_file_dialog) = self.open_file_dialog.as_mut() {
             cursor = open_file_dialog.render(area, buf);
         }
+        if let Some(passphrase_dialog) = self.passphrase_dialog.as_mut() {
+            cursor = passphrase_dialog.dialog.render(area, buf);
+        }
         if let Some(error_message) = self.error_message.as_mut() {
             cursor = error_message.render(area, buf)
         }
@@ -165,12 +227,38 @@ impl EditorComponent for KeyEditor {
     }
 }
 
-fn load_key(path: &PathBuf) -> Result<KeyFile> {
+/// Encrypted keys are returned as is, they are decrypted once the passphrase is entered.
+/// PEM, OpenSSH and JWK keys are converted, the public key is taken from private key files when it is expected.
+fn load_key(path: &PathBuf, private_key: bool) -> Result<LoadedKey> {
     let key_string = fs::read_to_string(path)?;
-    let key: Key = serde_json::from_str(&key_string)?;
     let filename: String = path
         .file_name()
         .map(|filename| filename.to_string_lossy().into())
         .unwrap_or("Unknown filename".into());
-    Ok(KeyFile { filename, key })
+    if KeyFormat::detect(&key_string).is_some() {
+        let imported_key = import_key(&key_string)?;
+        let key = if private_key {
+            imported_key
+                .private_key
+                .ok_or_else(|| anyhow!("File does not contain a private key"))?
+        } else {
+            imported_key.public_key
+        };
+        return Ok(LoadedKey::Plain(KeyFile { filename, key }));
+    }
+    let key_json: Value = serde_json::from_str(&key_string)?;
+    if EncryptedKey::is_encrypted_key(&key_json) {
+        let encrypted_key = serde_json::from_value(key_json)?;
+        Ok(LoadedKey::Encrypted {
+            filename,
+            encrypted_key,
+        })
+    } else {
+        let key = serde_json::from_value(key_json)?;
+        Ok(LoadedKey::Plain(KeyFile { filename, key }))
+    }
+}
+
+fn passphrase_area() -> AreaCalculators {
+    (Box::new(|_| 1), Box::new(|w| w.saturating_sub(8).min(60)))
 }
@@ -4,10 +4,11 @@ use std::collections::HashSet;
 
 use golem_certificate::schemas::certificate::key_usage::{KeyUsage, Usage};
 
-const KEY_USAGE_ORDER: [(Usage, &str); 3] = [
+const KEY_USAGE_ORDER: [(Usage, &str); 4] = [
     (Usage::SignCertificate, "Sign certificate"),
     (Usage::SignManifest, "Sign manifest"),
     (Usage::SignNode, "Sign node"),
+    (Usage::SignRevocationList, "Sign revocation list"),
 ];
 
 pub struct KeyUsageEditor {
@@ -1,59 +1,299 @@
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
+use anyhow::anyhow;
+use serde_json::Value;
+
 use super::*;
 
 use golem_certificate::schemas::permissions::{
-    OutboundPermissions, PermissionDetails, Permissions,
+    EntryPermissions, OutboundPermissions, PermissionDetails, PermissionEntry, Permissions,
+    UrlPattern,
 };
-use url::Url;
+
+const ITEM_INDENT: usize = 6;
 
 pub struct PermissionsEditor {
     highlight: Option<usize>,
-    permissions: Permissions,
-    urls: Vec<Url>,
-    url_editor: Option<TextInput>,
+    all: bool,
+    sections: [Section; 4],
+    /// unknown permissions of a loaded template are kept as they are
+    additional_properties: HashMap<String, Value>,
+    item_editor: Option<TextInput>,
     parse_error: Option<ModalMessage>,
 }
 
+#[derive(Clone, Copy, PartialEq)]
+enum Line {
+    All,
+    Category(usize),
+    ListTitle(usize),
+    Item(usize, usize),
+    AddItem(usize),
+}
+
+#[derive(Clone, Copy)]
+enum Category {
+    Outbound,
+    Inbound,
+    Runtimes,
+    PaymentPlatforms,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Mode {
+    None,
+    Unrestricted,
+    List,
+}
+
+struct Section {
+    category: Category,
+    mode: Mode,
+    items: Vec<String>,
+}
+
+impl Category {
+    fn title(&self) -> &'static str {
+        match self {
+            Category::Outbound => "Outbound",
+            Category::Inbound => "Inbound",
+            Category::Runtimes => "Runtimes",
+            Category::PaymentPlatforms => "Payment platforms",
+        }
+    }
+
+    fn list_title(&self) -> &'static str {
+        match self {
+            Category::Outbound => "Urls",
+            Category::Inbound => "Ports",
+            Category::Runtimes | Category::PaymentPlatforms => "Names",
+        }
+    }
+
+    fn item_name(&self) -> &'static str {
+        match self {
+            Category::Outbound => "URL",
+            Category::Inbound => "port",
+            Category::Runtimes => "runtime",
+            Category::PaymentPlatforms => "payment platform",
+        }
+    }
+
+    fn parse_error_title(&self) -> &'static str {
+        match self {
+            Category::Outbound => "Url pattern parse error",
+            Category::Inbound => "Port parse error",
+            Category::Runtimes => "Runtime name error",
+            Category::PaymentPlatforms => "Payment platform name error",
+        }
+    }
+
+    fn parse_item(&self, text: &str) -> Result<String, String> {
+        match self {
+            Category::Outbound => text
+                .parse::<UrlPattern>()
+                .map(|url| url.to_string())
+                .map_err(|e| e.to_string()),
+            Category::Inbound => text
+                .trim()
+                .parse::<u16>()
+                .map(|port| port.to_string())
+                .map_err(|_| "Port has to be a number between 0 and 65535".into()),
+            Category::Runtimes | Category::PaymentPlatforms => {
+                let name = text.trim();
+                if name.is_empty() || name.contains(char::is_whitespace) {
+                    Err("Name cannot be empty or contain whitespace".into())
+                } else {
+                    Ok(name.to_string())
+                }
+            }
+        }
+    }
+}
+
+/// Conversion between a permission category and the items edited as text.
+trait CategoryPermissions: Sized {
+    fn unrestricted() -> Self;
+    /// Items of the list, `None` when unrestricted
+    fn items(&self) -> Option<Vec<String>>;
+    /// Items are parsed by [`Category::parse_item`] before they are added to the list,
+    /// the error names an item that cannot be parsed nevertheless
+    fn from_items(items: &[String]) -> Result<Self, String>;
+}
+
+impl CategoryPermissions for OutboundPermissions {
+    fn unrestricted() -> Self {
+        OutboundPermissions::Unrestricted
+    }
+
+    fn items(&self) -> Option<Vec<String>> {
+        match self {
+            OutboundPermissions::Unrestricted => None,
+            OutboundPermissions::Urls(urls) => Some(to_strings(urls)),
+        }
+    }
+
+    fn from_items(items: &[String]) -> Result<Self, String> {
+        from_strings(items).map(OutboundPermissions::Urls)
+    }
+}
+
+impl<T: PermissionEntry + ToString + FromStr> CategoryPermissions for EntryPermissions<T> {
+    fn unrestricted() -> Self {
+        EntryPermissions::Unrestricted
+    }
+
+    fn items(&self) -> Option<Vec<String>> {
+        match self {
+            EntryPermissions::Unrestricted => None,
+            EntryPermissions::Entries(entries) => {
+                Some(entries.iter().map(ToString::to_string).collect())
+            }
+        }
+    }
+
+    fn from_items(items: &[String]) -> Result<Self, String> {
+        from_strings(items).map(EntryPermissions::Entries)
+    }
+}
+
+fn to_strings<T: ToString>(set: &HashSet<T>) -> Vec<String> {
+    let mut items: Vec<_> = set.iter().map(ToString::to_string).collect();
+    items.sort();
+    items
+}
+
+fn from_strings<T: FromStr, C: FromIterator<T>>(items: &[String]) -> Result<C, String> {
+    items
+        .iter()
+        .map(|item| item.parse().map_err(|_| item.clone()))
+        .collect()
+}
+
+impl Section {
+    fn new<T: CategoryPermissions>(category: Category, permissions: &Option<T>) -> Self {
+        let (mode, items) = match permissions.as_ref().map(CategoryPermissions::items) {
+            None => (Mode::None, vec![]),
+            Some(None) => (Mode::Unrestricted, vec![]),
+            Some(Some(items)) => (Mode::List, items),
+        };
+        Self {
+            category,
+            mode,
+            items,
+        }
+    }
+
+    fn permissions<T: CategoryPermissions>(&self) -> Result<Option<T>> {
+        match self.mode {
+            Mode::None => Ok(None),
+            Mode::Unrestricted => Ok(Some(T::unrestricted())),
+            Mode::List => T::from_items(&self.items).map(Some).map_err(|item| {
+                anyhow!(
+                    "{} permissions: invalid {} {item}",
+                    self.category.title(),
+                    self.category.item_name()
+                )
+            }),
+        }
+    }
+
+    fn next_mode(&mut self) {
+        self.mode = match self.mode {
+            Mode::None => Mode::Unrestricted,
+            Mode::Unrestricted => Mode::List,
+            Mode::List => Mode::None,
+        }
+    }
+}
+
 impl PermissionsEditor {
     pub fn new(permissions: Option<Permissions>) -> Self {
-        let default_url: Url = "https://golem.network".parse().unwrap();
-        let default_permissions = Permissions::Object(PermissionDetails {
-            outbound: Some(OutboundPermissions::Urls([default_url.clone()].into())),
-        });
-        let mut urls = vec![default_url];
+        let default_url: UrlPattern = "https://golem.network".parse().unwrap();
+        let default_details = PermissionDetails {
+            outbound: Some(OutboundPermissions::Urls([default_url].into())),
+            ..Default::default()
+        };
+        let (all, details) = match permissions {
+            None => (false, default_details),
+            Some(Permissions::All) => (
+                true,
+                PermissionDetails {
+                    outbound: Some(OutboundPermissions::Unrestricted),
+                    ..Default::default()
+                },
+            ),
+            Some(Permissions::Object(details)) => (false, details),
+        };
         Self {
             highlight: None,
-            permissions: permissions
-                .map(|p| match &p {
-                    Permissions::Object(PermissionDetails { outbound: None }) => {
-                        default_permissions.clone()
-                    }
-                    Permissions::Object(PermissionDetails {
-                        outbound: Some(OutboundPermissions::Urls(url_set)),
-                    }) => {
-                        urls = url_set.iter().map(|url| url.to_owned()).collect();
-                        urls.sort();
-                        p
-                    }
-                    _ => p,
-                })
-                .unwrap_or(default_permissions),
-            urls,
-            url_editor: None,
+            all,
+            sections: [
+                Section::new(Category::Outbound, &details.outbound),
+                Section::new(Category::Inbound, &details.inbound),
+                Section::new(Category::Runtimes, &details.runtimes),
+                Section::new(Category::PaymentPlatforms, &details.payment_platforms),
+            ],
+            additional_properties: details.additional_properties,
+            item_editor: None,
             parse_error: None,
         }
     }
 
-    pub fn get_permissions(&self) -> Permissions {
-        match &self.permissions {
-            Permissions::Object(PermissionDetails {
-                outbound: Some(OutboundPermissions::Urls(_)),
-            }) => {
-                let urls = self.urls.iter().map(|url| url.to_owned()).collect();
-                Permissions::Object(PermissionDetails {
-                    outbound: Some(OutboundPermissions::Urls(urls)),
-                })
+    pub fn get_permissions(&self) -> Result<Permissions> {
+        if self.all {
+            return Ok(Permissions::All);
+        }
+        let [outbound, inbound, runtimes, payment_platforms] = &self.sections;
+        Ok(Permissions::Object(PermissionDetails {
+            outbound: outbound.permissions()?,
+            inbound: inbound.permissions()?,
+            runtimes: runtimes.permissions()?,
+            payment_platforms: payment_platforms.permissions()?,
+            additional_properties: self.additional_properties.clone(),
+        }))
+    }
+
+    fn lines(&self) -> Vec<Line> {
+        let mut lines = vec![Line::All];
+        if !self.all {
+            for (idx, section) in self.sections.iter().enumerate() {
+                lines.push(Line::Category(idx));
+                if section.mode == Mode::List {
+                    lines.push(Line::ListTitle(idx));
+                    lines.extend((0..section.items.len()).map(|item| Line::Item(idx, item)));
+                    lines.push(Line::AddItem(idx));
+                }
+            }
+        }
+        lines
+    }
+
+    fn highlighted_line(&self) -> Option<Line> {
+        self.highlight
+            .and_then(|highlight| self.lines().get(highlight).copied())
+    }
+
+    fn close_item_editor(&mut self, text: &str) {
+        let (section_idx, item_idx) = match self.highlighted_line() {
+            Some(Line::Item(section, item)) => (section, Some(item)),
+            Some(Line::AddItem(section)) => (section, None),
+            _ => unreachable!("permission editor internal state error"),
+        };
+        let section = &mut self.sections[section_idx];
+        match section.category.parse_item(text) {
+            Ok(item) => {
+                match item_idx {
+                    Some(idx) => section.items[idx] = item,
+                    None => section.items.push(item),
+                }
+                self.item_editor = None;
+            }
+            Err(err) => {
+                self.parse_error =
+                    Some(ModalMessage::new(section.category.parse_error_title(), err))
             }
-            p => p.clone(),
         }
     }
 }
@@ -78,7 +318,7 @@ impl EditorComponent for PermissionsEditor {
     }
 
     fn handle_key_event(&mut self, key_event: KeyEvent) -> EditorEventResult {
-        let render_height = self.calculate_render_height();
+        let lines = self.lines();
         if let Some(parse_error) = self.parse_error.as_mut() {
             if let Ok(status) = parse_error.handle_key_event(key_event) {
                 match status {
@@ -87,90 +327,60 @@ impl EditorComponent for PermissionsEditor {
                 }
             }
             EditorEventResult::KeepActive
-        } else if let Some(editor) = self.url_editor.as_mut() {
+        } else if let Some(editor) = self.item_editor.as_mut() {
             if let Ok(status) = Component::handle_key_event(editor, key_event) {
                 match status {
                     ComponentStatus::Active => (),
-                    ComponentStatus::Closed => match Url::parse(editor.get_text()) {
-                        Ok(url) => {
-                            let idx = self.highlight.as_ref().unwrap() - 3;
-                            if idx == self.urls.len() {
-                                self.urls.push(url);
-                            } else {
-                                self.urls[idx] = url;
-                            }
-                            self.url_editor = None;
-                        }
-                        Err(err) => {
-                            self.parse_error =
-                                Some(ModalMessage::new("Url parse error", err.to_string()))
-                        }
-                    },
-                    ComponentStatus::Escaped => self.url_editor = None,
+                    ComponentStatus::Closed => {
+                        let text = editor.get_text().clone();
+                        self.close_item_editor(&text);
+                    }
+                    ComponentStatus::Escaped => self.item_editor = None,
                 }
             }
             EditorEventResult::KeepActive
         } else if let Some(highlight) = self.highlight {
+            let selectable = |idx: &usize| !matches!(lines[*idx], Line::ListTitle(_));
             match key_event.code {
                 KeyCode::Esc => EditorEventResult::Escaped,
-                KeyCode::Down => {
-                    if highlight < render_height - 1 {
-                        let inc = if highlight == 1 { 2 } else { 1 };
-                        self.highlight = Some(highlight + inc);
+                KeyCode::Down => match (highlight + 1..lines.len()).find(selectable) {
+                    Some(next) => {
+                        self.highlight = Some(next);
                         EditorEventResult::KeepActive
-                    } else {
+                    }
+                    None => {
                         self.highlight = None;
                         EditorEventResult::ExitBottom
                     }
-                }
-                KeyCode::Up => {
-                    if highlight > 0 {
-                        let dec = if highlight == 3 { 2 } else { 1 };
-                        self.highlight = Some(highlight - dec);
+                },
+                KeyCode::Up => match (0..highlight).rev().find(selectable) {
+                    Some(previous) => {
+                        self.highlight = Some(previous);
                         EditorEventResult::KeepActive
-                    } else {
+                    }
+                    None => {
                         self.highlight = None;
                         EditorEventResult::ExitTop
                     }
-                }
+                },
                 KeyCode::Enter => {
-                    if highlight == 0 {
-                        self.permissions = match &self.permissions {
-                            Permissions::All => Permissions::Object(PermissionDetails {
-                                outbound: Some(OutboundPermissions::Unrestricted),
-                            }),
-                            Permissions::Object(_) => Permissions::All,
-                        };
-                    } else if highlight == 1 {
-                        match &mut self.permissions {
-                            Permissions::All => {
-                                unreachable!("permission editor internal state error")
-                            }
-                            Permissions::Object(PermissionDetails { outbound }) => {
-                                match outbound.as_ref().unwrap() {
-                                    OutboundPermissions::Unrestricted => outbound
-                                        .insert(OutboundPermissions::Urls(Default::default())),
-                                    OutboundPermissions::Urls(_) => {
-                                        outbound.insert(OutboundPermissions::Unrestricted)
-                                    }
-                                }
-                            }
-                        };
-                    } else if highlight > 2 && highlight <= render_height {
-                        let idx = highlight - 3;
-                        let mut editor = TextInput::new(255, false);
-                        if idx < self.urls.len() {
-                            editor.set_text(self.urls[idx].to_string());
+                    match lines[highlight] {
+                        Line::All => self.all = !self.all,
+                        Line::Category(idx) => self.sections[idx].next_mode(),
+                        Line::ListTitle(_) => (),
+                        Line::Item(idx, item) => {
+                            let mut editor = TextInput::new(255, false);
+                            editor.set_text(self.sections[idx].items[item].clone());
+                            self.item_editor = Some(editor);
                         }
-                        self.url_editor = Some(editor);
+                        Line::AddItem(_) => self.item_editor = Some(TextInput::new(255, false)),
                     }
                     EditorEventResult::KeepActive
                 }
                 KeyCode::Delete | KeyCode::Backspace => {
-                    if highlight > 2 && highlight < render_height {
-                        let idx = highlight - 3;
-                        self.urls.remove(idx);
-                        if highlight > 3 {
+                    if let Line::Item(idx, item) = lines[highlight] {
+                        self.sections[idx].items.remove(item);
+                        if item > 0 {
                             self.highlight = Some(highlight - 1);
                         }
                     }
@@ -184,56 +394,47 @@ impl EditorComponent for PermissionsEditor {
     }
 
     fn calculate_render_height(&self) -> usize {
-        match &self.permissions {
-            Permissions::All => 1,
-            Permissions::Object(PermissionDetails { outbound }) => match outbound.as_ref() {
-                Some(outbound_details) => match outbound_details {
-                    OutboundPermissions::Unrestricted => 2,
-                    OutboundPermissions::Urls(_) => 4 + self.urls.len(),
-                },
-                None => 1,
-            },
-        }
+        self.lines().len()
     }
 
     fn get_text_output(&self, text: &mut String) {
-        write!(text, "Permissions").unwrap();
-        match &self.permissions {
-            Permissions::All => writeln!(text, ": All").unwrap(),
-            Permissions::Object(PermissionDetails { outbound: None }) => {
-                writeln!(text, "None").unwrap()
-            }
-            Permissions::Object(PermissionDetails {
-                outbound: Some(outbound_details),
-            }) => {
-                writeln!(text).unwrap();
-                write!(text, "  Outbound").unwrap();
-                match outbound_details {
-                    OutboundPermissions::Unrestricted => {
-                        writeln!(text, ": Unrestricted").unwrap();
-                    }
-                    OutboundPermissions::Urls(_) => {
-                        writeln!(text).unwrap();
-                        writeln!(text, "    Urls").unwrap();
-                        self.urls
-                            .iter()
-                            .for_each(|url| writeln!(text, "      {}", url).unwrap());
+        for line in self.lines() {
+            match line {
+                Line::All if self.all => writeln!(text, "Permissions: All").unwrap(),
+                Line::All => writeln!(text, "Permissions").unwrap(),
+                Line::Category(idx) => {
+                    let section = &self.sections[idx];
+                    write!(text, "  {}", section.category.title()).unwrap();
+                    match section.mode {
+                        Mode::None => writeln!(text, ": None").unwrap(),
+                        Mode::Unrestricted => writeln!(text, ": Unrestricted").unwrap(),
+                        Mode::List => writeln!(text).unwrap(),
                     }
                 }
+                Line::ListTitle(idx) => {
+                    writeln!(text, "    {}", self.sections[idx].category.list_title()).unwrap()
+                }
+                Line::Item(idx, item) => writeln!(
+                    text,
+                    "{:ITEM_INDENT$}{}",
+                    "", self.sections[idx].items[item]
+                )
+                .unwrap(),
+                Line::AddItem(_) => writeln!(text).unwrap(),
             }
         }
     }
 
     fn get_highlight_prefix(&self) -> Option<usize> {
-        self.highlight.map(|highlight| match highlight {
-            0 => 0,
-            1 => 2,
-            _ => 6,
+        self.highlighted_line().map(|line| match line {
+            Line::All => 0,
+            Line::Category(_) | Line::ListTitle(_) => 2,
+            Line::Item(..) | Line::AddItem(_) => ITEM_INDENT,
         })
     }
 
     fn get_editor(&mut self) -> Option<&mut TextInput> {
-        self.url_editor.as_mut()
+        self.item_editor.as_mut()
     }
 
     fn get_error_message(&mut self) -> Option<&mut ModalMessage> {
@@ -241,6 +442,13 @@ impl EditorComponent for PermissionsEditor {
     }
 
     fn get_empty_highlight_filler(&self) -> (String, String) {
-        ("      ".into(), "<Add another URL>".into())
+        let item_name = match self.highlighted_line() {
+            Some(Line::AddItem(idx)) => self.sections[idx].category.item_name(),
+            _ => "item",
+        };
+        (
+            " ".repeat(ITEM_INDENT),
+            format!("<Add another {item_name}>"),
+        )
     }
 }
@@ -9,7 +9,7 @@ use tui::{
     widgets::{Block, BorderType, Borders, Padding, Widget},
 };
 
-use crate::utils::save_json_to_file;
+use crate::utils::{save_json_to_file, save_private_json_to_file};
 
 use super::{
     component::*,
@@ -62,7 +62,7 @@ impl CreateKeyPairDialog {
                 ModalMultipleChoice::new("File exists", file_exists_message, OVERWRITE_CHOICES, 1);
             self.overwrite_dialog = Some(dialog);
         } else {
-            let result = save_json_to_file(&private_key_path, &self.keypair.private_key)
+            let result = save_private_json_to_file(&private_key_path, &self.keypair.private_key)
                 .and_then(|_| save_json_to_file(&public_key_path, &self.keypair.public_key));
             match result {
                 Ok(_) => {
@@ -134,19 +134,19 @@ impl DocumentEditor for NodeDescriptorEditor {
     fn get_document(&self) -> Result<Value> {
         let node_descriptor = NodeDescriptor {
             node_id: self.node_id.get_node_id(),
-            permissions: self.permissions.get_permissions(),
+            permissions: self.permissions.get_permissions()?,
             validity_period: self.validity_period.get_validity_period(),
         };
         serde_json::to_value(node_descriptor).map_err(Into::into)
     }
 
-    fn get_template_json(&self) -> Value {
+    fn get_template_json(&self) -> Result<Value> {
         let template = NodeDescriptorTemplate {
             node_id: Some(self.node_id.get_node_id()),
-            permissions: Some(self.permissions.get_permissions()),
+            permissions: Some(self.permissions.get_permissions()?),
             validity_period: Some(self.validity_period.get_validity_period()),
         };
-        json!({ "$schema": SIGNED_NODE_DESCRIPTOR_SCHEMA_ID, "nodeDescriptor": template })
+        Ok(json!({ "$schema": SIGNED_NODE_DESCRIPTOR_SCHEMA_ID, "nodeDescriptor": template }))
     }
 
     fn create_signed_document(
@@ -167,6 +167,7 @@ impl DocumentEditor for NodeDescriptorEditor {
                         value: signature_value,
                         signer: signed_cert,
                     },
+                    node_signature: None,
                 };
                 serde_json::to_value(signed_node_descriptor)
             }
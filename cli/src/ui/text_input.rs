@@ -53,30 +53,22 @@ impl TextInput {
 
     fn text_editing_key_event(&mut self, key_event: KeyEvent) {
         match key_event.code {
-            KeyCode::Char(c) => {
-                if self.text.len() < self.max_length {
-                    self.text.insert(self.cursor_position, c);
-                    self.cursor_position += 1;
-                }
+            KeyCode::Char(c) if self.text.len() < self.max_length => {
+                self.text.insert(self.cursor_position, c);
+                self.cursor_position += 1;
             }
-            KeyCode::Right => {
-                if self.cursor_position < self.text.len() {
-                    self.cursor_position += 1;
-                }
+            KeyCode::Right if self.cursor_position < self.text.len() => {
+                self.cursor_position += 1;
             }
             KeyCode::Left => {
                 self.cursor_position = self.cursor_position.saturating_sub(1);
             }
-            KeyCode::Backspace => {
-                if self.cursor_position > 0 {
-                    self.cursor_position -= 1;
-                    self.text.remove(self.cursor_position);
-                }
+            KeyCode::Backspace if self.cursor_position > 0 => {
+                self.cursor_position -= 1;
+                self.text.remove(self.cursor_position);
             }
-            KeyCode::Delete => {
-                if self.cursor_position < self.text.len() {
-                    self.text.remove(self.cursor_position);
-                }
+            KeyCode::Delete if self.cursor_position < self.text.len() => {
+                self.text.remove(self.cursor_position);
             }
             _ => (),
         }
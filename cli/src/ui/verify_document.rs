@@ -13,6 +13,8 @@ use tui::{
     widgets::{Block, BorderType, Borders, Padding, Widget},
 };
 
+use crate::utils::describe_validation_error;
+
 use super::{
     certificate::SignedCertificateDetails,
     component::*,
@@ -107,7 +109,7 @@ fn verify_json(json: Value) -> Result<VerifiedDocument, String> {
         }),
         Err(e) => Err(e),
     }
-    .map_err(|err| err.to_string())
+    .map_err(|err| describe_validation_error(&err))
 }
 
 fn show_cert_details(path: &Path, cert: &SignedCertificate) -> Box<dyn Component> {
@@ -3,12 +3,41 @@ use std::io::{BufWriter, Write};
 use std::path::Path;
 
 use anyhow::{anyhow, Result};
-use golem_certificate::schemas::{SIGNED_CERTIFICATE_SCHEMA_ID, SIGNED_NODE_DESCRIPTOR_SCHEMA_ID};
+use golem_certificate::{
+    schemas::{
+        SIGNED_CERTIFICATE_SCHEMA_ID, SIGNED_MANIFEST_SCHEMA_ID, SIGNED_NODE_DESCRIPTOR_SCHEMA_ID,
+        SIGNED_NODE_DESCRIPTOR_V2_SCHEMA_ID, SIGNED_REVOCATION_LIST_SCHEMA_ID,
+    },
+    EncryptedKey, Error, Key, LintWarning,
+};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 pub fn save_json_to_file<C: ?Sized + Serialize>(path: impl AsRef<Path>, content: &C) -> Result<()> {
-    let mut writer = BufWriter::new(fs::File::create(path)?);
+    write_json(fs::File::create(path)?, content)
+}
+
+/// Private keys are saved readable and writable only by the owner on unix.
+pub fn save_private_json_to_file<C: ?Sized + Serialize>(
+    path: impl AsRef<Path>,
+    content: &C,
+) -> Result<()> {
+    write_json(create_private_file(path)?, content)
+}
+
+pub fn create_private_file(path: impl AsRef<Path>) -> Result<fs::File> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let file = options.open(path)?;
+    #[cfg(unix)]
+    file.set_permissions(std::os::unix::fs::PermissionsExt::from_mode(0o600))?;
+    Ok(file)
+}
+
+fn write_json<C: ?Sized + Serialize>(file: fs::File, content: &C) -> Result<()> {
+    let mut writer = BufWriter::new(file);
     serde_json::to_writer_pretty(&mut writer, content)?;
     let _ = writer.write(b"\n")?;
     writer.flush()?;
@@ -18,6 +47,8 @@ pub fn save_json_to_file<C: ?Sized + Serialize>(path: impl AsRef<Path>, content:
 pub enum FileType {
     Certificate,
     NodeDescriptor,
+    RevocationList,
+    Manifest,
 }
 
 impl FileType {
@@ -25,6 +56,8 @@ impl FileType {
         match self {
             FileType::Certificate => "certificate",
             FileType::NodeDescriptor => "nodeDescriptor",
+            FileType::RevocationList => "revocationList",
+            FileType::Manifest => "manifest",
         }
         .to_string()
     }
@@ -35,7 +68,11 @@ pub fn determine_file_type(json_data: &Value) -> Result<FileType> {
         .as_str()
         .map(|schema| match schema {
             SIGNED_CERTIFICATE_SCHEMA_ID => Ok(FileType::Certificate),
-            SIGNED_NODE_DESCRIPTOR_SCHEMA_ID => Ok(FileType::NodeDescriptor),
+            SIGNED_NODE_DESCRIPTOR_SCHEMA_ID | SIGNED_NODE_DESCRIPTOR_V2_SCHEMA_ID => {
+                Ok(FileType::NodeDescriptor)
+            }
+            SIGNED_REVOCATION_LIST_SCHEMA_ID => Ok(FileType::RevocationList),
+            SIGNED_MANIFEST_SCHEMA_ID => Ok(FileType::Manifest),
             _ => Err(anyhow!("Unknown json structure {schema}")),
         })
         .unwrap_or_else(|| Err(anyhow!("Unknown json structure, missing $schema property")))
@@ -59,3 +96,63 @@ pub fn deserialize_from_file<T: for<'de> Deserialize<'de>>(path: &Path) -> Resul
     let json_string = fs::read_to_string(path)?;
     serde_json::from_str(&json_string).map_err(Into::into)
 }
+
+/// Loads a signing key file, asking for the passphrase when the key is encrypted.
+pub fn load_signing_key(path: &Path) -> Result<Key> {
+    let key_json = deserialize_from_file::<Value>(path)?;
+    if EncryptedKey::is_encrypted_key(&key_json) {
+        let encrypted_key: EncryptedKey = serde_json::from_value(key_json)?;
+        let passphrase =
+            rpassword::prompt_password(format!("Enter passphrase for {}: ", path.display()))?;
+        encrypted_key.decrypt(&passphrase).map_err(Into::into)
+    } else {
+        serde_json::from_value(key_json).map_err(Into::into)
+    }
+}
+
+/// Asks for a new passphrase twice to protect against typos.
+pub fn prompt_new_passphrase(name: &str) -> Result<String> {
+    let passphrase = rpassword::prompt_password(format!("Enter new {name}: "))?;
+    let confirmation = rpassword::prompt_password(format!("Repeat {name}: "))?;
+    if passphrase != confirmation {
+        Err(anyhow!("Passphrases do not match"))
+    } else {
+        Ok(passphrase)
+    }
+}
+
+/// Describes a validation error on multiple lines, showing which link of the chain failed.
+pub fn describe_validation_error(error: &Error) -> String {
+    match error {
+        Error::InChain { position, error } => {
+            let mut description = describe_validation_error(error);
+            let link = if position.depth == 0 {
+                "validated document"
+            } else {
+                "signing certificate chain"
+            };
+            description.push_str(&format!("\nChain depth: {} ({link})", position.depth));
+            if let Some(display_name) = &position.display_name {
+                description.push_str(&format!("\nCertificate: {display_name}"));
+            }
+            if let Some(fingerprint) = &position.fingerprint {
+                description.push_str(&format!("\nFingerprint: {fingerprint}"));
+            }
+            description
+        }
+        error => error.to_string(),
+    }
+}
+
+/// Describes a lint warning of a document on a single line.
+pub fn describe_lint_warning(warning: &LintWarning) -> String {
+    match warning {
+        LintWarning::NonNormalizedUrl {
+            depth,
+            url,
+            normalized,
+        } => format!(
+            "Outbound URL {url} at chain depth {depth} is not normalized, it is compared as {normalized}"
+        ),
+    }
+}
@@ -1,28 +1,36 @@
 const fs = require('fs');
-const canonicalize = require('canonicalize');
-const elliptic = require('elliptic');
+const { validateCertificate, validateNodeDescriptor, fingerprint } = require('golem-certificate-wasm');
 
-const filename = process.argv[process.argv.length - 1];
-console.log("Reading certificate from file " + filename);
-
-const certificate_data = fs.readFileSync(filename);
-const certificate = JSON.parse(certificate_data);
-const signing_certificate = certificate.signature.signer === "self" ? certificate : certificate.signature.signer;
-
-if (certificate.signature.algorithm.hash !== "sha512"
-    || certificate.signature.algorithm.encryption !== "EdDSA"
-    || signing_certificate.certificate.publicKey.parameters.scheme !== "Ed25519") {
-    console.log("Unsupported signature type");
+const usage = "Usage: node . <certificate|node-descriptor> <path> [RFC 3339 timestamp]";
+const [type, filename, timestamp] = process.argv.slice(2);
+if (!filename) {
+    console.log(usage);
     process.exit(1);
 }
 
-const encoder = new TextEncoder();
-const signed_bytes = encoder.encode(canonicalize(certificate.certificate)); // encode the string into bytes with UTF-8 encoding
+console.log("Reading " + type + " from file " + filename);
+const data = fs.readFileSync(filename, 'utf8');
 
-const result = elliptic.eddsa('ed25519').verify(signed_bytes, certificate.signature.value, signing_certificate.certificate.publicKey.key);
-
-if (result) {
-    console.log("The signature is valid.");
-} else {
-    console.log("Signature verification failed.");
+try {
+    switch (type) {
+        case 'certificate': {
+            const certificate = validateCertificate(data, timestamp);
+            console.log("The certificate is valid, fingerprint: " + fingerprint(data));
+            console.log(JSON.stringify(certificate, null, 2));
+            break;
+        }
+        case 'node-descriptor': {
+            const nodeDescriptor = validateNodeDescriptor(data, timestamp);
+            console.log("The node descriptor of " + nodeDescriptor.nodeId + " is valid.");
+            console.log(JSON.stringify(nodeDescriptor, null, 2));
+            break;
+        }
+        default:
+            console.log(usage);
+            process.exit(1);
+    }
+} catch (error) {
+    // Errors of the library are GolemCertificateError objects with the kind of the error and its position in the chain
+    console.log("Validation failed (" + error.kind + "): " + error.message);
+    process.exit(1);
 }
@@ -0,0 +1,4 @@
+// Symbols of the interpreter are resolved when it loads the module, which has to be allowed explicitly on macOS
+fn main() {
+    pyo3_build_config::add_extension_module_link_args();
+}
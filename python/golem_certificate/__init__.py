@@ -0,0 +1,8 @@
+"""Python bindings of Golem Certificate.
+
+Documents are accepted as json strings or as the objects ``json.loads`` returns,
+errors are raised as subclasses of ``GolemCertificateError`` named after the variants of ``golem_certificate::Error``.
+"""
+
+from ._golem_certificate import *  # noqa: F401,F403
+from ._golem_certificate import __all__  # noqa: F401
@@ -0,0 +1,88 @@
+import datetime
+from typing import Any, Dict, List, Optional, Union
+
+Json = Union[str, Dict[str, Any]]
+
+class ChainPosition:
+    """Position in the certificate chain where validation failed, 0 is the validated document."""
+
+    depth: int
+    fingerprint: Optional[str]
+    display_name: Optional[str]
+
+class GolemCertificateError(Exception):
+    """Base class of the errors of Golem Certificate."""
+
+    position: Optional[ChainPosition]
+
+class Expired(GolemCertificateError): ...
+class NotValidYet(GolemCertificateError): ...
+class ValidityPeriodExtended(GolemCertificateError): ...
+class PermissionsExtended(GolemCertificateError): ...
+class UnknownPermissionExtended(GolemCertificateError): ...
+class KeyUsageExtended(GolemCertificateError): ...
+class CertSignNotPermitted(GolemCertificateError): ...
+class NodeSignNotPermitted(GolemCertificateError): ...
+class ManifestSignNotPermitted(GolemCertificateError): ...
+class InvalidUrlPattern(GolemCertificateError): ...
+class InvalidManifest(GolemCertificateError): ...
+class RevocationListSignNotPermitted(GolemCertificateError): ...
+class Revoked(GolemCertificateError): ...
+class InvalidSignature(GolemCertificateError): ...
+class UnsupportedSignatureAlgorithm(GolemCertificateError): ...
+class InvalidSignatureValue(GolemCertificateError): ...
+class InvalidPublicKey(GolemCertificateError): ...
+class InvalidPrivateKey(GolemCertificateError): ...
+class KeyMismatch(GolemCertificateError): ...
+class NodeIdMismatch(GolemCertificateError): ...
+class MissingNodeSignature(GolemCertificateError): ...
+class InvalidKeyFormat(GolemCertificateError): ...
+class InvalidMnemonic(GolemCertificateError): ...
+class InvalidDerivationPath(GolemCertificateError): ...
+class InvalidPassphrase(GolemCertificateError): ...
+class InvalidEncryptedKey(GolemCertificateError): ...
+class SigningBackend(GolemCertificateError): ...
+class InvalidJson(GolemCertificateError): ...
+class JcsSerializationError(GolemCertificateError): ...
+class JsonDoesNotConformToSchema(GolemCertificateError): ...
+class UntrustedRoot(GolemCertificateError): ...
+class ChainTooDeep(GolemCertificateError): ...
+class InvalidTrustAnchor(GolemCertificateError): ...
+class UnsupportedSchema(GolemCertificateError): ...
+
+class KeyPair:
+    public_key: Dict[str, Any]
+    private_key: Dict[str, Any]
+
+class ValidityPeriod:
+    not_before: datetime.datetime
+    not_after: datetime.datetime
+
+class ValidatedCertificate:
+    certificate_chain_fingerprints: List[str]
+    """Fingerprints of the chain, from the certificate to the root"""
+    permissions: Any
+    key_usage: Any
+    validity_period: ValidityPeriod
+    subject: Dict[str, Any]
+
+class ValidatedNodeDescriptor:
+    certificate_chain_fingerprints: List[str]
+    """Fingerprints of the chain, from the signing certificate to the root"""
+    permissions: Any
+    node_id: str
+    possession_proven: bool
+    """Node descriptor was counter-signed with the key of the node identity"""
+
+def create_key_pair() -> KeyPair: ...
+def sign_certificate(
+    certificate: Json, private_key: Json, signer: Optional[Json] = None
+) -> Dict[str, Any]: ...
+def sign_node_descriptor(node_descriptor: Json, signer: Json, private_key: Json) -> Dict[str, Any]: ...
+def fingerprint(signed_certificate: Json) -> str: ...
+def validate_certificate(
+    data: Json, timestamp: Optional[datetime.datetime] = None
+) -> ValidatedCertificate: ...
+def validate_node_descriptor(
+    data: Json, timestamp: Optional[datetime.datetime] = None
+) -> ValidatedNodeDescriptor: ...
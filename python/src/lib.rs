@@ -0,0 +1,359 @@
+//! Python bindings of Golem Certificate, build with `maturin build` in this directory.
+//! Documents are accepted as json strings or as the objects `json.loads` returns,
+//! signed documents are returned as such objects. Errors are raised as exceptions mirroring `golem_certificate::Error`.
+
+use chrono::{DateTime, Utc};
+use golem_certificate as gcert;
+use golem_certificate::schemas::{
+    certificate::Certificate, node_descriptor::NodeDescriptor, validity_period,
+};
+use pyo3::create_exception;
+use pyo3::exceptions::PyException;
+use pyo3::prelude::*;
+use pyo3::types::PyString;
+use serde::{de::DeserializeOwned, Serialize};
+
+create_exception!(
+    golem_certificate,
+    GolemCertificateError,
+    PyException,
+    "Base class of the errors of Golem Certificate, `position` is the position in the certificate chain or None."
+);
+
+/// Declares an exception for every variant of `golem_certificate::Error` except `InChain`,
+/// whose position is set on the exception of the wrapped error.
+macro_rules! errors {
+    ($($variant:ident),* $(,)?) => {
+        $(create_exception!(golem_certificate, $variant, GolemCertificateError);)*
+
+        fn add_errors(module: &Bound<'_, PyModule>) -> PyResult<()> {
+            let py = module.py();
+            module.add("GolemCertificateError", py.get_type::<GolemCertificateError>())?;
+            $(module.add(stringify!($variant), py.get_type::<$variant>())?;)*
+            Ok(())
+        }
+
+        fn new_error(error: &gcert::Error, message: String) -> PyErr {
+            match error {
+                gcert::Error::InChain { error, .. } => new_error(error, message),
+                $(gcert::Error::$variant { .. } => $variant::new_err(message),)*
+            }
+        }
+    };
+}
+
+errors!(
+    Expired,
+    NotValidYet,
+    ValidityPeriodExtended,
+    PermissionsExtended,
+    UnknownPermissionExtended,
+    KeyUsageExtended,
+    CertSignNotPermitted,
+    NodeSignNotPermitted,
+    ManifestSignNotPermitted,
+    InvalidUrlPattern,
+    InvalidManifest,
+    RevocationListSignNotPermitted,
+    Revoked,
+    InvalidSignature,
+    UnsupportedSignatureAlgorithm,
+    InvalidSignatureValue,
+    InvalidPublicKey,
+    InvalidPrivateKey,
+    KeyMismatch,
+    NodeIdMismatch,
+    MissingNodeSignature,
+    InvalidKeyFormat,
+    InvalidMnemonic,
+    InvalidDerivationPath,
+    InvalidPassphrase,
+    InvalidEncryptedKey,
+    SigningBackend,
+    InvalidJson,
+    JcsSerializationError,
+    JsonDoesNotConformToSchema,
+    UntrustedRoot,
+    ChainTooDeep,
+    InvalidTrustAnchor,
+    UnsupportedSchema,
+);
+
+/// Position in the certificate chain where validation failed, see `golem_certificate::ChainPosition`.
+#[pyclass(frozen, get_all, module = "golem_certificate")]
+#[derive(Clone)]
+struct ChainPosition {
+    /// 0 is the validated document, its signing certificate is at depth 1
+    depth: usize,
+    fingerprint: Option<String>,
+    display_name: Option<String>,
+}
+
+#[pymethods]
+impl ChainPosition {
+    fn __repr__(&self) -> String {
+        format!(
+            "ChainPosition(depth={}, fingerprint={:?}, display_name={:?})",
+            self.depth, self.fingerprint, self.display_name
+        )
+    }
+}
+
+fn to_py_err(py: Python<'_>, error: gcert::Error) -> PyErr {
+    let error_value = new_error(&error, error.to_string());
+    let position = error.chain_position().map(|position| ChainPosition {
+        depth: position.depth,
+        fingerprint: position.fingerprint.clone(),
+        display_name: position.display_name.clone(),
+    });
+    match error_value.value(py).setattr("position", position) {
+        Ok(()) => error_value,
+        Err(error) => error,
+    }
+}
+
+#[pyclass(frozen, get_all, module = "golem_certificate")]
+struct ValidityPeriod {
+    not_before: DateTime<Utc>,
+    not_after: DateTime<Utc>,
+}
+
+#[pymethods]
+impl ValidityPeriod {
+    fn __repr__(&self) -> String {
+        format!(
+            "ValidityPeriod(not_before={}, not_after={})",
+            self.not_before, self.not_after
+        )
+    }
+}
+
+impl From<validity_period::ValidityPeriod> for ValidityPeriod {
+    fn from(validity_period: validity_period::ValidityPeriod) -> Self {
+        Self {
+            not_before: validity_period.not_before,
+            not_after: validity_period.not_after,
+        }
+    }
+}
+
+/// Validated certificate, see `golem_certificate::ValidatedCertificate`.
+/// Permissions, key usage and subject are the objects `json.loads` returns for them.
+#[pyclass(frozen, get_all, module = "golem_certificate")]
+struct ValidatedCertificate {
+    /// Fingerprints of the chain, from the certificate to the root
+    certificate_chain_fingerprints: Vec<String>,
+    permissions: Py<PyAny>,
+    key_usage: Py<PyAny>,
+    validity_period: Py<ValidityPeriod>,
+    subject: Py<PyAny>,
+}
+
+#[pymethods]
+impl ValidatedCertificate {
+    fn __repr__(&self, py: Python<'_>) -> String {
+        format!(
+            "ValidatedCertificate(certificate_chain_fingerprints={:?}, permissions={}, key_usage={}, validity_period={}, subject={})",
+            self.certificate_chain_fingerprints,
+            self.permissions.bind(py),
+            self.key_usage.bind(py),
+            self.validity_period.get().__repr__(),
+            self.subject.bind(py),
+        )
+    }
+}
+
+impl ValidatedCertificate {
+    fn new(
+        py: Python<'_>,
+        validated: gcert::validator::validated_data::ValidatedCertificate,
+    ) -> PyResult<Self> {
+        Ok(Self {
+            certificate_chain_fingerprints: validated.certificate_chain_fingerprints,
+            permissions: to_object(py, &validated.permissions)?,
+            key_usage: to_object(py, &validated.key_usage)?,
+            validity_period: Py::new(py, ValidityPeriod::from(validated.validity_period))?,
+            subject: to_object(py, &validated.subject)?,
+        })
+    }
+}
+
+/// Validated node descriptor, see `golem_certificate::ValidatedNodeDescriptor`.
+/// Permissions are the object `json.loads` returns for them.
+#[pyclass(frozen, get_all, module = "golem_certificate")]
+struct ValidatedNodeDescriptor {
+    /// Fingerprints of the chain, from the signing certificate to the root
+    certificate_chain_fingerprints: Vec<String>,
+    permissions: Py<PyAny>,
+    node_id: String,
+    /// Node descriptor was counter-signed with the key of the node identity
+    possession_proven: bool,
+}
+
+#[pymethods]
+impl ValidatedNodeDescriptor {
+    fn __repr__(&self, py: Python<'_>) -> String {
+        format!(
+            "ValidatedNodeDescriptor(certificate_chain_fingerprints={:?}, permissions={}, node_id={:?}, possession_proven={})",
+            self.certificate_chain_fingerprints,
+            self.permissions.bind(py),
+            self.node_id,
+            if self.possession_proven { "True" } else { "False" },
+        )
+    }
+}
+
+impl ValidatedNodeDescriptor {
+    fn new(
+        py: Python<'_>,
+        validated: gcert::validator::validated_data::ValidatedNodeDescriptor,
+    ) -> PyResult<Self> {
+        Ok(Self {
+            certificate_chain_fingerprints: validated.certificate_chain_fingerprints,
+            permissions: to_object(py, &validated.permissions)?,
+            node_id: validated.node_id.to_string(),
+            possession_proven: validated.possession_proven,
+        })
+    }
+}
+
+/// Ed25519 key pair, keys are the objects `json.loads` returns for them.
+#[pyclass(frozen, get_all, module = "golem_certificate")]
+struct KeyPair {
+    public_key: Py<PyAny>,
+    private_key: Py<PyAny>,
+}
+
+/// Creates an Ed25519 key pair.
+#[pyfunction]
+fn create_key_pair(py: Python<'_>) -> PyResult<KeyPair> {
+    let key_pair = gcert::create_key_pair();
+    Ok(KeyPair {
+        public_key: to_object(py, &key_pair.public_key)?,
+        private_key: to_object(py, &key_pair.private_key)?,
+    })
+}
+
+/// Signs the certificate, self-signing it when there is no signer, see `golem_certificate::CertificateBuilder`.
+#[pyfunction]
+#[pyo3(signature = (certificate, private_key, signer = None))]
+fn sign_certificate(
+    py: Python<'_>,
+    certificate: &Bound<'_, PyAny>,
+    private_key: &Bound<'_, PyAny>,
+    signer: Option<&Bound<'_, PyAny>>,
+) -> PyResult<Py<PyAny>> {
+    let builder = gcert::CertificateBuilder::new(from_object::<Certificate>(certificate)?);
+    let private_key: gcert::Key = from_object(private_key)?;
+    let signed_certificate = match signer {
+        Some(signer) => builder.sign(from_object(signer)?, &private_key),
+        None => builder.self_sign(&private_key),
+    }
+    .map_err(|e| to_py_err(py, e))?;
+    to_object(py, &signed_certificate)
+}
+
+/// Signs the node descriptor with a certificate permitted to sign node descriptors, see `golem_certificate::NodeDescriptorBuilder`.
+#[pyfunction]
+fn sign_node_descriptor(
+    py: Python<'_>,
+    node_descriptor: &Bound<'_, PyAny>,
+    signer: &Bound<'_, PyAny>,
+    private_key: &Bound<'_, PyAny>,
+) -> PyResult<Py<PyAny>> {
+    let builder =
+        gcert::NodeDescriptorBuilder::new(from_object::<NodeDescriptor>(node_descriptor)?);
+    let signed_node_descriptor = builder
+        .sign(from_object(signer)?, &from_object(private_key)?)
+        .map_err(|e| to_py_err(py, e))?;
+    to_object(py, &signed_node_descriptor)
+}
+
+/// Fingerprint of the signed certificate, as listed in the certificate chain fingerprints of validated documents.
+#[pyfunction]
+fn fingerprint(py: Python<'_>, signed_certificate: &Bound<'_, PyAny>) -> PyResult<String> {
+    let signed_certificate: gcert::SignedCertificate = from_object(signed_certificate)?;
+    gcert::create_hash(
+        &signed_certificate.certificate,
+        &signed_certificate.signature.algorithm.hash,
+    )
+    .map(|hash| hash.iter().map(|byte| format!("{byte:02x}")).collect())
+    .map_err(|e| to_py_err(py, e))
+}
+
+/// Validates certificate, see `golem_certificate::validate_certificate_str`.
+/// The timestamp has to be a timezone aware datetime, validity is not checked when it is None.
+#[pyfunction]
+#[pyo3(signature = (data, timestamp = None))]
+fn validate_certificate(
+    py: Python<'_>,
+    data: &Bound<'_, PyAny>,
+    timestamp: Option<DateTime<Utc>>,
+) -> PyResult<ValidatedCertificate> {
+    let data = to_json(data)?;
+    let validated = py
+        .detach(|| gcert::validate_certificate_str(&data, timestamp))
+        .map_err(|e| to_py_err(py, e))?;
+    ValidatedCertificate::new(py, validated)
+}
+
+/// Validates node descriptor, see `golem_certificate::validate_node_descriptor_str`.
+/// The timestamp has to be a timezone aware datetime, validity is not checked when it is None.
+#[pyfunction]
+#[pyo3(signature = (data, timestamp = None))]
+fn validate_node_descriptor(
+    py: Python<'_>,
+    data: &Bound<'_, PyAny>,
+    timestamp: Option<DateTime<Utc>>,
+) -> PyResult<ValidatedNodeDescriptor> {
+    let data = to_json(data)?;
+    let validated = py
+        .detach(|| gcert::validate_node_descriptor_str(&data, timestamp))
+        .map_err(|e| to_py_err(py, e))?;
+    ValidatedNodeDescriptor::new(py, validated)
+}
+
+/// Json strings are passed as they are, other objects are serialized with `json.dumps`.
+fn to_json(object: &Bound<'_, PyAny>) -> PyResult<String> {
+    if let Ok(string) = object.cast::<PyString>() {
+        return string.extract();
+    }
+    let json = object.py().import("json")?;
+    json.call_method1("dumps", (object,))?.extract()
+}
+
+fn from_object<T: DeserializeOwned>(object: &Bound<'_, PyAny>) -> PyResult<T> {
+    let py = object.py();
+    let json = to_json(object)?;
+    serde_json::from_str(&json).map_err(|e| {
+        let error = if e.is_syntax() || e.is_eof() {
+            gcert::Error::InvalidJson(e.to_string())
+        } else {
+            gcert::Error::JsonDoesNotConformToSchema(e.to_string())
+        };
+        to_py_err(py, error)
+    })
+}
+
+fn to_object<T: Serialize>(py: Python<'_>, value: &T) -> PyResult<Py<PyAny>> {
+    let json = serde_json::to_string(value)
+        .map_err(|e| to_py_err(py, gcert::Error::JcsSerializationError(e.to_string())))?;
+    Ok(py.import("json")?.call_method1("loads", (json,))?.unbind())
+}
+
+#[pymodule]
+fn _golem_certificate(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    add_errors(module)?;
+    module.add_class::<ChainPosition>()?;
+    module.add_class::<KeyPair>()?;
+    module.add_class::<ValidatedCertificate>()?;
+    module.add_class::<ValidatedNodeDescriptor>()?;
+    module.add_class::<ValidityPeriod>()?;
+    module.add_function(wrap_pyfunction!(create_key_pair, module)?)?;
+    module.add_function(wrap_pyfunction!(sign_certificate, module)?)?;
+    module.add_function(wrap_pyfunction!(sign_node_descriptor, module)?)?;
+    module.add_function(wrap_pyfunction!(fingerprint, module)?)?;
+    module.add_function(wrap_pyfunction!(validate_certificate, module)?)?;
+    module.add_function(wrap_pyfunction!(validate_node_descriptor, module)?)?;
+    Ok(())
+}
@@ -0,0 +1,128 @@
+import datetime
+import json
+import pathlib
+import unittest
+
+import golem_certificate as gcert
+
+RESOURCES = pathlib.Path(__file__).resolve().parents[2] / "tests" / "resources"
+VALID = datetime.datetime(2024, 1, 1, tzinfo=datetime.timezone.utc)
+
+
+def read(relative_path):
+    return (RESOURCES / relative_path).read_text()
+
+
+class ValidateCertificate(unittest.TestCase):
+    def test_accept_valid_certificate(self):
+        validated = gcert.validate_certificate(read("certificate/happy_path.signed.json"), VALID)
+
+        self.assertEqual(len(validated.certificate_chain_fingerprints), 3)
+        self.assertEqual(validated.key_usage, ["signNode"])
+        self.assertEqual(validated.permissions, {"outbound": "unrestricted"})
+        self.assertEqual(validated.subject["displayName"], "Example leaf cert")
+        self.assertEqual(
+            validated.validity_period.not_after,
+            datetime.datetime(2025, 1, 1, tzinfo=datetime.timezone.utc),
+        )
+
+    def test_accept_parsed_json(self):
+        data = json.loads(read("certificate/happy_path.signed.json"))
+
+        validated = gcert.validate_certificate(data)
+
+        self.assertEqual(validated.certificate_chain_fingerprints[0], gcert.fingerprint(data))
+
+    def test_raise_expired(self):
+        with self.assertRaises(gcert.Expired) as context:
+            gcert.validate_certificate(read("certificate/expired.signed.json"), VALID)
+
+        self.assertIsInstance(context.exception, gcert.GolemCertificateError)
+
+    def test_raise_invalid_signature_with_position(self):
+        with self.assertRaises(gcert.InvalidSignature) as context:
+            gcert.validate_certificate(read("certificate/invalid_signature.signed.json"))
+
+        self.assertEqual(context.exception.position.depth, 0)
+
+    def test_raise_permissions_extended(self):
+        with self.assertRaises(gcert.PermissionsExtended):
+            gcert.validate_certificate(read("certificate/invalid_permissions.signed.json"))
+
+    def test_raise_invalid_json(self):
+        with self.assertRaises(gcert.InvalidJson) as context:
+            gcert.validate_certificate("{")
+
+        self.assertIsNone(context.exception.position)
+
+
+class ValidateNodeDescriptor(unittest.TestCase):
+    def test_accept_valid_node_descriptor(self):
+        validated = gcert.validate_node_descriptor(read("node_descriptor/happy_path.signed.json"), VALID)
+
+        self.assertEqual(validated.node_id, "0x338e02f29b63155beec8253af7ad367dd44b40c6")
+        self.assertFalse(validated.possession_proven)
+
+    def test_accept_node_signed_node_descriptor(self):
+        validated = gcert.validate_node_descriptor(read("node_descriptor/node_signed.signed.json"), VALID)
+
+        self.assertTrue(validated.possession_proven)
+
+    def test_raise_expired(self):
+        with self.assertRaises(gcert.Expired):
+            gcert.validate_node_descriptor(read("node_descriptor/expired.signed.json"), VALID)
+
+    def test_raise_invalid_chain_signature_with_position(self):
+        with self.assertRaises(gcert.InvalidSignatureValue) as context:
+            gcert.validate_node_descriptor(read("node_descriptor/invalid_cert_chain_signature.signed.json"))
+
+        self.assertEqual(context.exception.position.depth, 1)
+        self.assertEqual(context.exception.position.display_name, "Example partner cert")
+
+    def test_raise_node_sign_not_permitted(self):
+        with self.assertRaises(gcert.NodeSignNotPermitted):
+            gcert.validate_node_descriptor(read("node_descriptor/cert_cannot_sign_node.signed.json"))
+
+
+class Sign(unittest.TestCase):
+    def certificate(self, key_pair, key_usage):
+        return {
+            "validityPeriod": {"notBefore": "2023-01-01T00:00:00Z", "notAfter": "2025-01-01T00:00:00Z"},
+            "keyUsage": key_usage,
+            "permissions": "all",
+            "subject": {"displayName": "Python", "contact": {"email": "python@example.tld"}},
+            "publicKey": key_pair.public_key,
+        }
+
+    def test_sign_certificate_and_node_descriptor(self):
+        root_key_pair = gcert.create_key_pair()
+        leaf_key_pair = gcert.create_key_pair()
+        root = gcert.sign_certificate(self.certificate(root_key_pair, "all"), root_key_pair.private_key)
+        leaf = gcert.sign_certificate(
+            self.certificate(leaf_key_pair, ["signNode"]), root_key_pair.private_key, signer=root
+        )
+        node_descriptor = {
+            "nodeId": "0x338e02f29b63155beec8253af7ad367dd44b40c6",
+            "permissions": "all",
+            "validityPeriod": {"notBefore": "2023-01-01T00:00:00Z", "notAfter": "2025-01-01T00:00:00Z"},
+        }
+
+        signed_node_descriptor = gcert.sign_node_descriptor(node_descriptor, leaf, leaf_key_pair.private_key)
+        validated = gcert.validate_node_descriptor(signed_node_descriptor, VALID)
+
+        self.assertEqual(
+            validated.certificate_chain_fingerprints, [gcert.fingerprint(leaf), gcert.fingerprint(root)]
+        )
+
+    def test_raise_key_mismatch(self):
+        key_pair = gcert.create_key_pair()
+        root = gcert.sign_certificate(self.certificate(key_pair, "all"), key_pair.private_key)
+
+        with self.assertRaises(gcert.KeyMismatch):
+            gcert.sign_certificate(
+                self.certificate(key_pair, "all"), gcert.create_key_pair().private_key, signer=root
+            )
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,708 @@
+use serde_json::Value;
+
+use crate::{
+    cryptography::{verify_signature_json, verify_signature_json_with_node_id, Key},
+    schemas::{
+        certificate::{
+            key_usage::validator::{
+                validate_certificates_key_usage, validate_sign_manifest, validate_sign_node,
+                validate_sign_revocation_list,
+            },
+            Certificate,
+        },
+        manifest::{decode_manifest, Manifest},
+        node_descriptor::NodeDescriptor,
+        permissions::validator::{validate_permissions, validate_requested_outbound_permissions},
+        revocation_list::RevocationList,
+        signature::{
+            NodeSignature, Signature, SignatureAlgorithm, SignedCertificate, SignedManifest,
+            SignedNodeDescriptor, SignedRevocationList, Signer,
+        },
+        validity_period::validator::validate_validity_period,
+        SIGNED_CERTIFICATE_SCHEMA_ID, SIGNED_MANIFEST_SCHEMA_ID, SIGNED_NODE_DESCRIPTOR_SCHEMA_ID,
+        SIGNED_NODE_DESCRIPTOR_V2_SCHEMA_ID, SIGNED_REVOCATION_LIST_SCHEMA_ID,
+    },
+    signing::{sign_json_with_backend, SigningBackend, SoftwareSigningBackend},
+    validator::{
+        chain::ParsedChain, validate_signed_certificate, validated_data::ValidatedCertificate,
+    },
+    Error, Result,
+};
+
+/// Creates signed certificates from a typed `Certificate`.
+/// Constraints of the signing certificate are checked before signing, so the result passes validation.
+#[derive(Debug, Clone)]
+pub struct CertificateBuilder {
+    certificate: Certificate,
+}
+
+impl CertificateBuilder {
+    pub fn new(certificate: Certificate) -> Self {
+        Self { certificate }
+    }
+
+    /// Self-signs the certificate.
+    /// # Arguments
+    /// * `private_key` private key associated with the public key of the certificate
+    pub fn self_sign(self, private_key: &Key) -> Result<SignedCertificate> {
+        self.self_sign_with_backend(&mut SoftwareSigningBackend::new(private_key.clone()))
+    }
+
+    /// Self-signs the certificate using the signing backend.
+    /// # Arguments
+    /// * `signing_backend` backend holding the private key associated with the public key of the certificate
+    pub fn self_sign_with_backend(
+        self,
+        signing_backend: &mut dyn SigningBackend,
+    ) -> Result<SignedCertificate> {
+        let certificate = to_value(&self.certificate)?;
+        let (algorithm, signature_value) =
+            sign(&certificate, signing_backend, &self.certificate.public_key)?;
+        Ok(SignedCertificate {
+            schema: SIGNED_CERTIFICATE_SCHEMA_ID.into(),
+            certificate,
+            signature: Box::new(Signature::create_self_signed(algorithm, signature_value)),
+        })
+    }
+
+    /// Signs the certificate with the signing certificate.
+    /// # Arguments
+    /// * `signer` signing certificate, its chain has to be valid.
+    ///   Permissions, key usage and validity period of the certificate have to be included in those of the signer.
+    /// * `private_key` private key associated with the public key of the `signer`
+    pub fn sign(self, signer: SignedCertificate, private_key: &Key) -> Result<SignedCertificate> {
+        self.sign_with_backend(
+            signer,
+            &mut SoftwareSigningBackend::new(private_key.clone()),
+        )
+    }
+
+    /// Signs the certificate with the signing certificate using the signing backend, see [`CertificateBuilder::sign`].
+    pub fn sign_with_backend(
+        self,
+        signer: SignedCertificate,
+        signing_backend: &mut dyn SigningBackend,
+    ) -> Result<SignedCertificate> {
+        let (parent, parent_public_key) = validate_signer(&signer)?;
+        validate_permissions(&parent.permissions, &self.certificate.permissions)?;
+        validate_certificates_key_usage(&parent.key_usage, &self.certificate.key_usage)?;
+        validate_validity_period(&parent.validity_period, &self.certificate.validity_period)?;
+
+        let certificate = to_value(&self.certificate)?;
+        let (algorithm, signature_value) = sign(&certificate, signing_backend, &parent_public_key)?;
+        Ok(SignedCertificate {
+            schema: SIGNED_CERTIFICATE_SCHEMA_ID.into(),
+            certificate,
+            signature: Box::new(Signature {
+                algorithm,
+                value: signature_value,
+                signer: Signer::Certificate(signer),
+            }),
+        })
+    }
+}
+
+/// Creates signed node descriptors from a typed `NodeDescriptor`.
+/// Constraints of the signing certificate are checked before signing, so the result passes validation.
+#[derive(Debug, Clone)]
+pub struct NodeDescriptorBuilder {
+    node_descriptor: NodeDescriptor,
+}
+
+impl NodeDescriptorBuilder {
+    pub fn new(node_descriptor: NodeDescriptor) -> Self {
+        Self { node_descriptor }
+    }
+
+    /// Signs the node descriptor with the signing certificate.
+    /// # Arguments
+    /// * `signer` signing certificate permitted to sign node descriptors, its chain has to be valid.
+    ///   Permissions and validity period of the node descriptor have to be included in those of the signer.
+    /// * `private_key` private key associated with the public key of the `signer`
+    pub fn sign(
+        self,
+        signer: SignedCertificate,
+        private_key: &Key,
+    ) -> Result<SignedNodeDescriptor> {
+        self.sign_with_backend(
+            signer,
+            &mut SoftwareSigningBackend::new(private_key.clone()),
+        )
+    }
+
+    /// Signs the node descriptor with the signing certificate using the signing backend, see [`NodeDescriptorBuilder::sign`].
+    pub fn sign_with_backend(
+        self,
+        signer: SignedCertificate,
+        signing_backend: &mut dyn SigningBackend,
+    ) -> Result<SignedNodeDescriptor> {
+        let (parent, parent_public_key) = validate_signer(&signer)?;
+        validate_permissions(&parent.permissions, &self.node_descriptor.permissions)?;
+        validate_sign_node(&parent.key_usage)?;
+        validate_validity_period(
+            &parent.validity_period,
+            &self.node_descriptor.validity_period,
+        )?;
+
+        let node_descriptor = to_value(&self.node_descriptor)?;
+        let (algorithm, signature_value) =
+            sign(&node_descriptor, signing_backend, &parent_public_key)?;
+        Ok(SignedNodeDescriptor {
+            schema: SIGNED_NODE_DESCRIPTOR_SCHEMA_ID.into(),
+            node_descriptor,
+            signature: Signature::create(algorithm, signature_value, signer),
+            node_signature: None,
+        })
+    }
+}
+
+/// Creates signed revocation lists from a typed `RevocationList`.
+/// Constraints of the signing certificate are checked before signing, so the result passes validation.
+#[derive(Debug, Clone)]
+pub struct RevocationListBuilder {
+    revocation_list: RevocationList,
+}
+
+impl RevocationListBuilder {
+    pub fn new(revocation_list: RevocationList) -> Self {
+        Self { revocation_list }
+    }
+
+    /// Signs the revocation list with the signing certificate.
+    /// # Arguments
+    /// * `signer` signing certificate permitted to sign revocation lists, its chain has to be valid.
+    ///   Validity period of the revocation list has to be included in the one of the signer.
+    /// * `private_key` private key associated with the public key of the `signer`
+    pub fn sign(
+        self,
+        signer: SignedCertificate,
+        private_key: &Key,
+    ) -> Result<SignedRevocationList> {
+        self.sign_with_backend(
+            signer,
+            &mut SoftwareSigningBackend::new(private_key.clone()),
+        )
+    }
+
+    /// Signs the revocation list with the signing certificate using the signing backend, see [`RevocationListBuilder::sign`].
+    pub fn sign_with_backend(
+        self,
+        signer: SignedCertificate,
+        signing_backend: &mut dyn SigningBackend,
+    ) -> Result<SignedRevocationList> {
+        let (parent, parent_public_key) = validate_signer(&signer)?;
+        validate_sign_revocation_list(&parent.key_usage)?;
+        validate_validity_period(
+            &parent.validity_period,
+            &self.revocation_list.validity_period,
+        )?;
+
+        let revocation_list = to_value(&self.revocation_list)?;
+        let (algorithm, signature_value) =
+            sign(&revocation_list, signing_backend, &parent_public_key)?;
+        Ok(SignedRevocationList {
+            schema: SIGNED_REVOCATION_LIST_SCHEMA_ID.into(),
+            revocation_list,
+            signature: Signature::create(algorithm, signature_value, signer),
+        })
+    }
+}
+
+/// Counter-signs the node descriptor with the key of its node identity, proving possession of the node key.
+/// # Arguments
+/// * `signed_node_descriptor` node descriptor signed by a certificate
+/// * `node_private_key` secp256k1 private key of the node identity
+pub fn sign_node_identity(
+    signed_node_descriptor: SignedNodeDescriptor,
+    node_private_key: &Key,
+) -> Result<SignedNodeDescriptor> {
+    sign_node_identity_with_backend(
+        signed_node_descriptor,
+        &mut SoftwareSigningBackend::new(node_private_key.clone()),
+    )
+}
+
+/// Counter-signs the node descriptor using the signing backend, see [`sign_node_identity`].
+pub fn sign_node_identity_with_backend(
+    mut signed_node_descriptor: SignedNodeDescriptor,
+    signing_backend: &mut dyn SigningBackend,
+) -> Result<SignedNodeDescriptor> {
+    let node_descriptor: NodeDescriptor =
+        serde_json::from_value(signed_node_descriptor.node_descriptor.clone())
+            .map_err(|e| Error::JsonDoesNotConformToSchema(e.to_string()))?;
+    let node_id = signing_backend.public_key()?.node_id()?;
+    if node_id != node_descriptor.node_id {
+        return Err(Error::NodeIdMismatch {
+            expected: node_descriptor.node_id,
+            recovered: node_id,
+        });
+    }
+    let (algorithm, value) =
+        sign_json_with_backend(&signed_node_descriptor.node_descriptor, signing_backend)?;
+    verify_signature_json_with_node_id(
+        &signed_node_descriptor.node_descriptor,
+        &algorithm,
+        &value,
+        &node_descriptor.node_id,
+    )?;
+    signed_node_descriptor.schema = SIGNED_NODE_DESCRIPTOR_V2_SCHEMA_ID.into();
+    signed_node_descriptor.node_signature = Some(NodeSignature { algorithm, value });
+    Ok(signed_node_descriptor)
+}
+
+/// Signs base64 encoded computation manifest with the signing certificate.
+/// Constraints of the signing certificate are checked before signing, so the result passes validation.
+/// # Arguments
+/// * `manifest` base64 encoded manifest
+/// * `certificate` signing certificate permitted to sign manifests, its chain has to be valid.
+///   Outbound access requested by the manifest has to be permitted by the certificate.
+/// * `private_key` private key associated with the public key of the `certificate`
+pub fn sign_manifest(
+    manifest: &str,
+    certificate: SignedCertificate,
+    private_key: &Key,
+) -> Result<SignedManifest> {
+    sign_manifest_with_backend(
+        manifest,
+        certificate,
+        &mut SoftwareSigningBackend::new(private_key.clone()),
+    )
+}
+
+/// Signs base64 encoded computation manifest using the signing backend, see [`sign_manifest`].
+pub fn sign_manifest_with_backend(
+    manifest: &str,
+    certificate: SignedCertificate,
+    signing_backend: &mut dyn SigningBackend,
+) -> Result<SignedManifest> {
+    let parsed_manifest: Manifest = serde_json::from_value(decode_manifest(manifest)?)
+        .map_err(|e| Error::InvalidManifest(e.to_string()))?;
+    let (parent, parent_public_key) = validate_signer(&certificate)?;
+    validate_sign_manifest(&parent.key_usage)?;
+    validate_requested_outbound_permissions(
+        &parent.permissions,
+        &parsed_manifest.outbound_permissions(),
+    )?;
+
+    let (algorithm, signature_value) = sign(
+        &Value::String(manifest.into()),
+        signing_backend,
+        &parent_public_key,
+    )?;
+    Ok(SignedManifest {
+        schema: SIGNED_MANIFEST_SCHEMA_ID.into(),
+        manifest: manifest.into(),
+        signature: Signature::create(algorithm, signature_value, certificate),
+    })
+}
+
+fn validate_signer(signer: &SignedCertificate) -> Result<(ValidatedCertificate, Key)> {
+    let chain = ParsedChain::parse(signer);
+    let validated_certificate =
+        validate_signed_certificate(&chain, Default::default(), &Default::default())?;
+    Ok((validated_certificate, chain.leaf_public_key()?.clone()))
+}
+
+fn to_value<T: serde::Serialize>(value: &T) -> Result<Value> {
+    serde_json::to_value(value).map_err(|e| Error::InvalidJson(e.to_string()))
+}
+
+/// Signs the value after checking that the backend holds the key of the signer,
+/// the created signature is verified, so an invalid document is never returned.
+fn sign(
+    value: &Value,
+    signing_backend: &mut dyn SigningBackend,
+    signer_public_key: &Key,
+) -> Result<(SignatureAlgorithm, Vec<u8>)> {
+    if signing_backend.public_key()? != *signer_public_key {
+        return Err(Error::KeyMismatch);
+    }
+    let (algorithm, signature_value) = sign_json_with_backend(value, signing_backend)?;
+    verify_signature_json(value, &algorithm, &signature_value, signer_public_key)?;
+    Ok((algorithm, signature_value))
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+
+    use serde_json::json;
+    use test_case::test_case;
+
+    use crate::{
+        create_key_pair, create_secp256k1_key_pair,
+        schemas::{
+            certificate::key_usage::{KeyUsage, Usage},
+            permissions::{
+                OutboundPermissions, PermissionCategory, PermissionDetails, Permissions,
+            },
+            validity_period::ValidityPeriod,
+        },
+        validate_certificate, validate_manifest, validate_node_descriptor,
+        validate_revocation_list, EncryptionAlgorithm,
+    };
+    use utils::*;
+
+    #[test]
+    fn create_valid_certificate_chain() {
+        let (signer, signer_key_pair) = signer(
+            KeyUsage::Limited([Usage::SignCertificate].into()),
+            Permissions::All,
+        );
+        let key_pair = create_key_pair();
+
+        let certificate = CertificateBuilder::new(certificate(
+            &key_pair,
+            KeyUsage::Limited([Usage::SignCertificate].into()),
+            outbound_unrestricted(),
+        ))
+        .sign(signer, &signer_key_pair.private_key)
+        .unwrap();
+
+        let result = validate_certificate(serde_json::to_value(&certificate).unwrap(), Some(now()));
+
+        assert_eq!(result.unwrap().certificate_chain_fingerprints.len(), 3);
+    }
+
+    #[test]
+    fn create_valid_node_descriptor() {
+        let (signer, signer_key_pair) = signer(
+            KeyUsage::Limited([Usage::SignNode].into()),
+            Permissions::All,
+        );
+
+        let node_descriptor = NodeDescriptorBuilder::new(node_descriptor(outbound_unrestricted()))
+            .sign(signer, &signer_key_pair.private_key)
+            .unwrap();
+
+        let result =
+            validate_node_descriptor(serde_json::to_value(&node_descriptor).unwrap(), Some(now()));
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn create_node_descriptor_signed_with_secp256k1_key() {
+        let (signer, signer_key_pair) = signer(KeyUsage::All, Permissions::All);
+        let key_pair = create_secp256k1_key_pair();
+        let certificate = CertificateBuilder::new(certificate(
+            &key_pair,
+            KeyUsage::Limited([Usage::SignNode].into()),
+            Permissions::All,
+        ))
+        .sign(signer, &signer_key_pair.private_key)
+        .unwrap();
+
+        let node_descriptor = NodeDescriptorBuilder::new(node_descriptor(Permissions::All))
+            .sign(certificate, &key_pair.private_key)
+            .unwrap();
+
+        assert_eq!(
+            node_descriptor.signature.algorithm.encryption,
+            EncryptionAlgorithm::ECDSASecp256k1
+        );
+        let result =
+            validate_node_descriptor(serde_json::to_value(&node_descriptor).unwrap(), Some(now()));
+        assert_eq!(result.unwrap().certificate_chain_fingerprints.len(), 3);
+    }
+
+    #[test_case(
+        KeyUsage::Limited([Usage::SignNode, Usage::SignManifest].into()),
+        KeyUsage::Limited([Usage::SignNode].into()),
+        Error::CertSignNotPermitted
+    )]
+    #[test_case(
+        KeyUsage::Limited([Usage::SignCertificate].into()),
+        KeyUsage::All,
+        Error::KeyUsageExtended {
+            parent: KeyUsage::Limited([Usage::SignCertificate].into()),
+            child: KeyUsage::All,
+        }
+    )]
+    fn reject_certificate_with_key_usage_not_permitted_by_signer(
+        signer_key_usage: KeyUsage,
+        key_usage: KeyUsage,
+        expected_err: Error,
+    ) {
+        let (signer, signer_key_pair) = signer(signer_key_usage, Permissions::All);
+        let key_pair = create_key_pair();
+
+        let result = CertificateBuilder::new(certificate(&key_pair, key_usage, Permissions::All))
+            .sign(signer, &signer_key_pair.private_key);
+
+        assert_eq!(result.unwrap_err(), expected_err);
+    }
+
+    #[test]
+    fn reject_certificate_with_extended_permissions() {
+        let (signer, signer_key_pair) = signer(KeyUsage::All, outbound_unrestricted());
+        let key_pair = create_key_pair();
+
+        let result =
+            CertificateBuilder::new(certificate(&key_pair, KeyUsage::All, Permissions::All))
+                .sign(signer, &signer_key_pair.private_key);
+
+        assert_eq!(
+            result.unwrap_err(),
+            Error::PermissionsExtended {
+                category: PermissionCategory::All
+            }
+        );
+    }
+
+    #[test]
+    fn reject_certificate_with_extended_validity_period() {
+        let (signer, signer_key_pair) = signer(KeyUsage::All, Permissions::All);
+        let key_pair = create_key_pair();
+        let mut certificate = certificate(&key_pair, KeyUsage::All, Permissions::All);
+        certificate.validity_period.not_after = "2200-01-01T00:00:00Z".parse().unwrap();
+
+        let result =
+            CertificateBuilder::new(certificate).sign(signer, &signer_key_pair.private_key);
+
+        assert!(matches!(
+            result.unwrap_err(),
+            Error::ValidityPeriodExtended { .. }
+        ));
+    }
+
+    #[test]
+    fn reject_node_descriptor_signed_by_certificate_without_node_usage() {
+        let (signer, signer_key_pair) = signer(
+            KeyUsage::Limited([Usage::SignCertificate].into()),
+            Permissions::All,
+        );
+
+        let result = NodeDescriptorBuilder::new(node_descriptor(Permissions::All))
+            .sign(signer, &signer_key_pair.private_key);
+
+        assert_eq!(result.unwrap_err(), Error::NodeSignNotPermitted);
+    }
+
+    #[test]
+    fn reject_node_descriptor_with_extended_permissions() {
+        let (signer, signer_key_pair) = signer(KeyUsage::All, outbound_unrestricted());
+
+        let result = NodeDescriptorBuilder::new(node_descriptor(Permissions::All))
+            .sign(signer, &signer_key_pair.private_key);
+
+        assert!(matches!(
+            result.unwrap_err(),
+            Error::PermissionsExtended { .. }
+        ));
+    }
+
+    #[test]
+    fn counter_sign_node_descriptor_with_node_key() {
+        let (signer, signer_key_pair) = signer(KeyUsage::All, Permissions::All);
+        let node_key_pair = create_secp256k1_key_pair();
+        let mut node_descriptor = node_descriptor(Permissions::All);
+        node_descriptor.node_id = node_key_pair.public_key.node_id().unwrap();
+        let signed_node_descriptor = NodeDescriptorBuilder::new(node_descriptor)
+            .sign(signer, &signer_key_pair.private_key)
+            .unwrap();
+
+        let signed_node_descriptor =
+            sign_node_identity(signed_node_descriptor, &node_key_pair.private_key).unwrap();
+
+        let result = validate_node_descriptor(
+            serde_json::to_value(&signed_node_descriptor).unwrap(),
+            Some(now()),
+        );
+        assert!(result.unwrap().possession_proven);
+        assert_eq!(
+            signed_node_descriptor.schema,
+            SIGNED_NODE_DESCRIPTOR_V2_SCHEMA_ID
+        );
+    }
+
+    #[test]
+    fn reject_counter_signing_with_key_of_other_node() {
+        let (signer, signer_key_pair) = signer(KeyUsage::All, Permissions::All);
+        let node_key_pair = create_secp256k1_key_pair();
+        let signed_node_descriptor = NodeDescriptorBuilder::new(node_descriptor(Permissions::All))
+            .sign(signer, &signer_key_pair.private_key)
+            .unwrap();
+
+        let result = sign_node_identity(signed_node_descriptor, &node_key_pair.private_key);
+
+        assert!(matches!(result.unwrap_err(), Error::NodeIdMismatch { .. }));
+    }
+
+    #[test]
+    fn reject_private_key_not_matching_signer() {
+        let (signer, _) = signer(KeyUsage::All, Permissions::All);
+        let other_key_pair = create_key_pair();
+
+        let result = NodeDescriptorBuilder::new(node_descriptor(Permissions::All))
+            .sign(signer, &other_key_pair.private_key);
+
+        assert_eq!(result.unwrap_err(), Error::KeyMismatch);
+    }
+
+    #[test]
+    fn reject_self_signing_with_other_key() {
+        let key_pair = create_key_pair();
+        let other_key_pair = create_key_pair();
+
+        let result =
+            CertificateBuilder::new(certificate(&key_pair, KeyUsage::All, Permissions::All))
+                .self_sign(&other_key_pair.private_key);
+
+        assert_eq!(result.unwrap_err(), Error::KeyMismatch);
+    }
+
+    #[test]
+    fn create_valid_manifest() {
+        let (signer, signer_key_pair) = signer(
+            KeyUsage::Limited([Usage::SignManifest].into()),
+            outbound_unrestricted(),
+        );
+
+        let signed_manifest = sign_manifest(
+            &manifest(json!({ "urls": ["https://example.com/"] })),
+            signer,
+            &signer_key_pair.private_key,
+        )
+        .unwrap();
+
+        let result =
+            validate_manifest(serde_json::to_value(&signed_manifest).unwrap(), Some(now()));
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn reject_manifest_signed_by_certificate_without_manifest_usage() {
+        let (signer, signer_key_pair) = signer(
+            KeyUsage::Limited([Usage::SignNode].into()),
+            Permissions::All,
+        );
+
+        let result = sign_manifest(
+            &manifest(json!({ "urls": ["https://example.com/"] })),
+            signer,
+            &signer_key_pair.private_key,
+        );
+
+        assert_eq!(result.unwrap_err(), Error::ManifestSignNotPermitted);
+    }
+
+    #[test]
+    fn reject_manifest_requesting_outbound_access_not_permitted_by_signer() {
+        let (signer, signer_key_pair) = signer(
+            KeyUsage::Limited([Usage::SignManifest].into()),
+            Permissions::Object(PermissionDetails {
+                outbound: Some(OutboundPermissions::Urls(
+                    ["https://example.com/".parse::<url::Url>().unwrap().into()].into(),
+                )),
+                ..Default::default()
+            }),
+        );
+
+        let result = sign_manifest(
+            &manifest(json!({ "unrestricted": { "urls": true } })),
+            signer,
+            &signer_key_pair.private_key,
+        );
+
+        assert!(matches!(
+            result.unwrap_err(),
+            Error::PermissionsExtended { .. }
+        ));
+    }
+
+    #[test]
+    fn create_valid_revocation_list() {
+        let (signer, signer_key_pair) = signer(
+            KeyUsage::Limited([Usage::SignRevocationList].into()),
+            Permissions::All,
+        );
+
+        let signed_revocation_list =
+            RevocationListBuilder::new(revocation_list("2025-01-01T00:00:00Z"))
+                .sign(signer, &signer_key_pair.private_key)
+                .unwrap();
+
+        let result = validate_revocation_list(
+            serde_json::to_value(&signed_revocation_list).unwrap(),
+            Some(now()),
+        );
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn reject_revocation_list_signed_by_certificate_without_revocation_list_usage() {
+        let (signer, signer_key_pair) = signer(
+            KeyUsage::Limited([Usage::SignNode].into()),
+            Permissions::All,
+        );
+
+        let result = RevocationListBuilder::new(revocation_list("2025-01-01T00:00:00Z"))
+            .sign(signer, &signer_key_pair.private_key);
+
+        assert_eq!(result.unwrap_err(), Error::RevocationListSignNotPermitted);
+    }
+
+    #[test]
+    fn reject_revocation_list_valid_longer_than_signer() {
+        let (signer, signer_key_pair) = signer(
+            KeyUsage::Limited([Usage::SignRevocationList].into()),
+            Permissions::All,
+        );
+
+        let result = RevocationListBuilder::new(revocation_list("2031-01-01T00:00:00Z"))
+            .sign(signer, &signer_key_pair.private_key);
+
+        assert!(matches!(
+            result.unwrap_err(),
+            Error::ValidityPeriodExtended { .. }
+        ));
+    }
+
+    mod utils {
+        use super::*;
+
+        use base64::{engine::general_purpose::STANDARD, Engine};
+
+        pub use crate::test_utils::{certificate, now, signer};
+
+        pub fn node_descriptor(permissions: Permissions) -> NodeDescriptor {
+            NodeDescriptor {
+                node_id: "0xbabe000000000000000000000000000000000000"
+                    .parse()
+                    .unwrap(),
+                permissions,
+                validity_period: ValidityPeriod {
+                    not_before: "2023-01-01T00:00:00Z".parse().unwrap(),
+                    not_after: "2025-01-01T00:00:00Z".parse().unwrap(),
+                },
+            }
+        }
+
+        pub fn revocation_list(not_after: &str) -> RevocationList {
+            RevocationList {
+                revoked_certificates: ["abcd".to_string()].into(),
+                validity_period: ValidityPeriod {
+                    not_before: "2023-01-01T00:00:00Z".parse().unwrap(),
+                    not_after: not_after.parse().unwrap(),
+                },
+            }
+        }
+
+        /// Base64 encoded manifest requesting the outbound access.
+        pub fn manifest(outbound: Value) -> String {
+            let manifest = json!({
+                "version": "0.1.0",
+                "compManifest": { "net": { "inet": { "out": outbound } } }
+            });
+            STANDARD.encode(manifest.to_string())
+        }
+
+        pub fn outbound_unrestricted() -> Permissions {
+            Permissions::Object(PermissionDetails {
+                outbound: Some(OutboundPermissions::Unrestricted),
+                ..Default::default()
+            })
+        }
+    }
+}
@@ -4,18 +4,26 @@ use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 
 use sha2::{Digest, Sha224, Sha256, Sha384, Sha512};
-use sha3::{Sha3_224, Sha3_256, Sha3_384, Sha3_512};
+use sha3::{Keccak256, Sha3_224, Sha3_256, Sha3_384, Sha3_512};
 
 use ed25519_dalek::{
-    ExpandedSecretKey, Keypair, PublicKey, SecretKey, Signature as EdDSASignature, Verifier,
+    Digest as PrehashDigest, ExpandedSecretKey, Keypair, PublicKey, SecretKey,
+    Signature as EdDSASignature, Verifier,
 };
-use rand::rngs::OsRng;
+use k256::ecdsa::{
+    RecoveryId, Signature as ECDSASignature, SigningKey as ECDSASigningKey,
+    VerifyingKey as ECDSAVerifyingKey,
+};
+use rand::{rngs::OsRng, RngCore};
+use sha2_09::Sha512 as Sha512Prehash;
+use ya_client_model::NodeId;
 
 use crate::schemas::signature::SignatureAlgorithm;
 use crate::serde_utils::{bytes_to_hex, hex_to_bytes};
+use crate::signing::{sign_json_with_backend, SoftwareSigningBackend};
 use crate::Error;
 
-#[derive(Clone, Debug, Deserialize, Serialize, Default)]
+#[derive(Clone, Debug, Deserialize, Serialize, Default, Eq, PartialEq)]
 #[serde(rename_all = "kebab-case")]
 pub enum HashAlgorithm {
     Sha224,
@@ -27,16 +35,24 @@ pub enum HashAlgorithm {
     Sha3_256,
     Sha3_384,
     Sha3_512,
+    /// Keccak-256 as used by Ethereum, differs from the standardized SHA3-256 in padding
+    Keccak256,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize, Default)]
+#[derive(Clone, Debug, Deserialize, Serialize, Default, Eq, PartialEq)]
 pub enum EncryptionAlgorithm {
     #[default]
     EdDSA,
+    /// Ed25519ph variant of EdDSA signing the SHA-512 hash of the message
+    /// https://datatracker.ietf.org/doc/html/rfc8032#section-5.1
+    EdDSAph,
     EdDSAOpenPGP,
+    /// Recoverable ECDSA over the secp256k1 curve used by Ethereum and Golem node identities.
+    /// The signature value is `r || s || v` where `v` is the recovery id.
+    ECDSASecp256k1,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, Eq, PartialEq)]
 pub struct Key {
     algorithm: EncryptionAlgorithm,
     #[serde(serialize_with = "bytes_to_hex", deserialize_with = "hex_to_bytes")]
@@ -55,6 +71,44 @@ impl From<[u8; 32]> for Key {
     }
 }
 
+impl Key {
+    /// Creates a secp256k1 private key, e.g. from the secret of a Golem node identity.
+    /// # Arguments
+    /// * `secret_key` 32 bytes of the secp256k1 secret scalar
+    pub fn from_secp256k1_secret_key(secret_key: &[u8]) -> Result<Self, Error> {
+        ECDSASigningKey::from_slice(secret_key).map_err(|_| Error::InvalidPrivateKey)?;
+        Ok(Self::secp256k1(secret_key.into()))
+    }
+
+    /// Node id (Ethereum address) of a secp256k1 public key.
+    pub fn node_id(&self) -> Result<NodeId, Error> {
+        if self.algorithm != EncryptionAlgorithm::ECDSASecp256k1 {
+            return Err(Error::InvalidPublicKey);
+        }
+        let verifying_key =
+            ECDSAVerifyingKey::from_sec1_bytes(&self.key).map_err(|_| Error::InvalidPublicKey)?;
+        Ok(node_id_from_verifying_key(&verifying_key))
+    }
+
+    fn secp256k1(key: Vec<u8>) -> Self {
+        Self {
+            algorithm: EncryptionAlgorithm::ECDSASecp256k1,
+            parameters: Some(json!({ "curve": "secp256k1" })),
+            key,
+        }
+    }
+
+    fn is_secp256k1(&self) -> bool {
+        self.algorithm == EncryptionAlgorithm::ECDSASecp256k1
+    }
+}
+
+impl From<&ECDSAVerifyingKey> for Key {
+    fn from(value: &ECDSAVerifyingKey) -> Self {
+        Self::secp256k1(value.to_encoded_point(true).as_bytes().into())
+    }
+}
+
 pub struct KeyPair {
     pub public_key: Key,
     pub private_key: Key,
@@ -69,6 +123,47 @@ pub fn create_key_pair() -> KeyPair {
     }
 }
 
+/// Creates a secp256k1 key pair usable as a Golem node identity.
+pub fn create_secp256k1_key_pair() -> KeyPair {
+    let mut csprng = OsRng {};
+    let mut secret_key = [0u8; 32];
+    let signing_key = loop {
+        csprng.fill_bytes(&mut secret_key);
+        // almost all 32 byte values are valid secret scalars
+        if let Ok(signing_key) = ECDSASigningKey::from_slice(&secret_key) {
+            break signing_key;
+        }
+    };
+    KeyPair {
+        public_key: signing_key.verifying_key().into(),
+        private_key: Key::secp256k1(secret_key.into()),
+    }
+}
+
+pub(crate) fn public_key_from_private(private_key: &Key) -> Result<Key, Error> {
+    if private_key.is_secp256k1() {
+        let signing_key =
+            ECDSASigningKey::from_slice(&private_key.key).map_err(|_| Error::InvalidPrivateKey)?;
+        return Ok(signing_key.verifying_key().into());
+    }
+    let secret_key =
+        SecretKey::from_bytes(&private_key.key).map_err(|_| Error::InvalidPrivateKey)?;
+    Ok(PublicKey::from(&secret_key).to_bytes().into())
+}
+
+/// Raw bytes of an Ed25519 public or private key.
+pub(crate) fn ed25519_key_bytes(key: &Key) -> Result<[u8; 32], Error> {
+    if key.is_secp256k1() {
+        return Err(Error::InvalidKeyFormat(
+            "only Ed25519 keys can be converted".into(),
+        ));
+    }
+    key.key
+        .as_slice()
+        .try_into()
+        .map_err(|_| Error::InvalidKeyFormat("Ed25519 key has to be 32 bytes long".into()))
+}
+
 pub fn create_default_hash(value: &Value) -> Result<Vec<u8>, Error> {
     create_hash(value, &HashAlgorithm::default())
 }
@@ -79,7 +174,7 @@ pub fn create_hash(value: &Value, hash_algorithm: &HashAlgorithm) -> Result<Vec<
         .map_err(|e| Error::JcsSerializationError(e.to_string()))
 }
 
-fn create_digest(input: impl AsRef<[u8]>, hash_algorithm: &HashAlgorithm) -> Vec<u8> {
+pub(crate) fn create_digest(input: impl AsRef<[u8]>, hash_algorithm: &HashAlgorithm) -> Vec<u8> {
     // Digest trait and the output hash contains the size so we cannot create a common variable prior to converting it into a Vec<u8>
     match hash_algorithm {
         HashAlgorithm::Sha224 => Sha224::digest(input).into_iter().collect(),
@@ -90,15 +185,90 @@ fn create_digest(input: impl AsRef<[u8]>, hash_algorithm: &HashAlgorithm) -> Vec
         HashAlgorithm::Sha3_256 => Sha3_256::digest(input).into_iter().collect(),
         HashAlgorithm::Sha3_384 => Sha3_384::digest(input).into_iter().collect(),
         HashAlgorithm::Sha3_512 => Sha3_512::digest(input).into_iter().collect(),
+        HashAlgorithm::Keccak256 => Keccak256::digest(input).into_iter().collect(),
     }
 }
 
 pub fn sign_json(value: &Value, private_key: &Key) -> Result<(SignatureAlgorithm, Vec<u8>)> {
-    let canonical_json = serde_json_canonicalizer::to_vec(value)?;
-    let secret_key = SecretKey::from_bytes(&private_key.key)?;
-    let signature_value = sign_bytes(canonical_json, &secret_key);
-    let algorithm = SignatureAlgorithm::default();
-    Ok((algorithm, signature_value))
+    sign_json_with_algorithm(value, private_key, default_signature_algorithm(private_key))
+}
+
+/// Signature algorithm used for the key when none is requested:
+/// EdDSA with SHA-512 for Ed25519 keys and recoverable ECDSA with Keccak-256 for secp256k1 keys.
+pub(crate) fn default_signature_algorithm(key: &Key) -> SignatureAlgorithm {
+    if key.is_secp256k1() {
+        SignatureAlgorithm {
+            hash: HashAlgorithm::Keccak256,
+            encryption: EncryptionAlgorithm::ECDSASecp256k1,
+        }
+    } else {
+        SignatureAlgorithm::default()
+    }
+}
+
+/// Signs json using the requested signature algorithm.
+/// # Arguments
+/// * `value` json to be signed
+/// * `private_key` signing key
+/// * `algorithm` signature algorithm, the hash algorithm has to be supported by the encryption algorithm
+pub fn sign_json_with_algorithm(
+    value: &Value,
+    private_key: &Key,
+    algorithm: SignatureAlgorithm,
+) -> Result<(SignatureAlgorithm, Vec<u8>)> {
+    let mut signing_backend =
+        SoftwareSigningBackend::new(private_key.clone()).with_signature_algorithm(algorithm);
+    Ok(sign_json_with_backend(value, &mut signing_backend)?)
+}
+
+pub(crate) fn sign_canonical_json(
+    canonical_json: &[u8],
+    private_key: &Key,
+    algorithm: &SignatureAlgorithm,
+) -> Result<Vec<u8>, Error> {
+    verify_supported_algorithm(algorithm)?;
+    if algorithm.encryption == EncryptionAlgorithm::ECDSASecp256k1 {
+        if !private_key.is_secp256k1() {
+            return Err(Error::InvalidPrivateKey);
+        }
+        let signing_key =
+            ECDSASigningKey::from_slice(&private_key.key).map_err(|_| Error::InvalidPrivateKey)?;
+        return sign_bytes_recoverable(canonical_json, &algorithm.hash, &signing_key);
+    }
+    if private_key.is_secp256k1() {
+        return Err(Error::InvalidPrivateKey);
+    }
+    let secret_key =
+        SecretKey::from_bytes(&private_key.key).map_err(|_| Error::InvalidPrivateKey)?;
+    let signature_value = match algorithm.encryption {
+        EncryptionAlgorithm::EdDSA => sign_bytes(canonical_json, &secret_key),
+        EncryptionAlgorithm::EdDSAph => sign_bytes_prehashed(canonical_json, &secret_key)?,
+        EncryptionAlgorithm::EdDSAOpenPGP => {
+            sign_bytes(create_digest(canonical_json, &algorithm.hash), &secret_key)
+        }
+        EncryptionAlgorithm::ECDSASecp256k1 => unreachable!("secp256k1 keys are handled above"),
+    };
+    Ok(signature_value)
+}
+
+/// Checks if the hash algorithm can be used with the encryption algorithm.
+/// EdDSA and Ed25519ph are defined with SHA-512 only, OpenPGP signs the hash of the message created by any supported algorithm.
+/// ECDSA over secp256k1 signs 256 bit hashes.
+fn verify_supported_algorithm(algorithm: &SignatureAlgorithm) -> Result<(), Error> {
+    match (&algorithm.encryption, &algorithm.hash) {
+        (EncryptionAlgorithm::EdDSA | EncryptionAlgorithm::EdDSAph, HashAlgorithm::Sha512) => {
+            Ok(())
+        }
+        (EncryptionAlgorithm::EdDSAOpenPGP, _) => Ok(()),
+        (
+            EncryptionAlgorithm::ECDSASecp256k1,
+            HashAlgorithm::Keccak256 | HashAlgorithm::Sha256 | HashAlgorithm::Sha3_256,
+        ) => Ok(()),
+        (encryption, hash) => Err(Error::UnsupportedSignatureAlgorithm {
+            encryption: encryption.clone(),
+            hash: hash.clone(),
+        }),
+    }
 }
 
 fn sign_bytes(bytes: impl AsRef<[u8]>, secret_key: &SecretKey) -> Vec<u8> {
@@ -108,37 +278,235 @@ fn sign_bytes(bytes: impl AsRef<[u8]>, secret_key: &SecretKey) -> Vec<u8> {
     signature_value.to_bytes().into()
 }
 
+fn sign_bytes_recoverable(
+    bytes: impl AsRef<[u8]>,
+    hash_algorithm: &HashAlgorithm,
+    signing_key: &ECDSASigningKey,
+) -> Result<Vec<u8>, Error> {
+    let bytes_hash = create_digest(bytes, hash_algorithm);
+    let (signature, recovery_id) = signing_key
+        .sign_prehash_recoverable(&bytes_hash)
+        .map_err(|_| Error::InvalidPrivateKey)?;
+    let mut signature_value: Vec<u8> = signature.to_bytes().to_vec();
+    signature_value.push(recovery_id.to_byte());
+    Ok(signature_value)
+}
+
+fn sign_bytes_prehashed(bytes: impl AsRef<[u8]>, secret_key: &SecretKey) -> Result<Vec<u8>, Error> {
+    let expanded_secret_key = ExpandedSecretKey::from(secret_key);
+    let public_key = PublicKey::from(secret_key);
+    let prehashed_message = Sha512Prehash::new().chain(bytes);
+    let signature_value = expanded_secret_key
+        .sign_prehashed(prehashed_message, &public_key, None)
+        .map_err(|_| Error::InvalidPrivateKey)?;
+    Ok(signature_value.to_bytes().into())
+}
+
 pub fn verify_signature_json(
     value: &Value,
-    signature_algorithm: &EncryptionAlgorithm,
+    signature_algorithm: &SignatureAlgorithm,
+    signature_value: impl AsRef<[u8]>,
+    public_key: &Key,
+) -> Result<(), Error> {
+    let canonical_json = canonicalize(value)?;
+    verify_signature_canonical_json(
+        &canonical_json,
+        signature_algorithm,
+        signature_value,
+        public_key,
+    )
+}
+
+pub(crate) fn canonicalize(value: &Value) -> Result<Vec<u8>, Error> {
+    serde_json_canonicalizer::to_vec(value).map_err(|e| Error::JcsSerializationError(e.to_string()))
+}
+
+/// Verifies signature of an already canonicalized json, see [`verify_signature_json`].
+pub(crate) fn verify_signature_canonical_json(
+    canonical_json: &[u8],
+    signature_algorithm: &SignatureAlgorithm,
     signature_value: impl AsRef<[u8]>,
     public_key: &Key,
 ) -> Result<(), Error> {
-    let canonical_json = serde_json_canonicalizer::to_vec(value)
-        .map_err(|e| Error::JcsSerializationError(e.to_string()))?;
+    verify_supported_algorithm(signature_algorithm)?;
+    if signature_algorithm.encryption == EncryptionAlgorithm::ECDSASecp256k1 {
+        if !public_key.is_secp256k1() {
+            return Err(Error::InvalidPublicKey);
+        }
+        let recovered_key =
+            recover_verifying_key(canonical_json, &signature_algorithm.hash, signature_value)?;
+        return if Key::from(&recovered_key) == *public_key {
+            Ok(())
+        } else {
+            Err(Error::InvalidSignature)
+        };
+    }
+    if public_key.is_secp256k1() {
+        return Err(Error::InvalidPublicKey);
+    }
     let eddsa_signature = EdDSASignature::from_bytes(signature_value.as_ref())
         .map_err(|_| Error::InvalidSignatureValue)?;
     let public_key = PublicKey::from_bytes(&public_key.key).map_err(|_| Error::InvalidPublicKey)?;
-    match signature_algorithm {
+    match signature_algorithm.encryption {
         EncryptionAlgorithm::EdDSA => verify_bytes(canonical_json, &eddsa_signature, &public_key),
-        EncryptionAlgorithm::EdDSAOpenPGP => {
-            verify_bytes_openpgp(canonical_json, &eddsa_signature, &public_key)
+        EncryptionAlgorithm::EdDSAph => {
+            verify_bytes_prehashed(canonical_json, &eddsa_signature, &public_key)
         }
+        EncryptionAlgorithm::EdDSAOpenPGP => verify_bytes_openpgp(
+            canonical_json,
+            &signature_algorithm.hash,
+            &eddsa_signature,
+            &public_key,
+        ),
+        EncryptionAlgorithm::ECDSASecp256k1 => unreachable!("secp256k1 keys are handled above"),
     }
 }
 
+/// Verifies a recoverable secp256k1 signature made by the given node identity.
+/// # Arguments
+/// * `value` signed json
+/// * `signature_algorithm` has to use `EncryptionAlgorithm::ECDSASecp256k1`
+/// * `signature_value` signature value `r || s || v`
+/// * `node_id` expected node id (Ethereum address) of the signer
+pub fn verify_signature_json_with_node_id(
+    value: &Value,
+    signature_algorithm: &SignatureAlgorithm,
+    signature_value: impl AsRef<[u8]>,
+    node_id: &NodeId,
+) -> Result<(), Error> {
+    let recovered = recover_node_id_json(value, signature_algorithm, signature_value)?;
+    if recovered == *node_id {
+        Ok(())
+    } else {
+        Err(Error::NodeIdMismatch {
+            expected: *node_id,
+            recovered,
+        })
+    }
+}
+
+/// Recovers the node id (Ethereum address) of the signer from a recoverable secp256k1 signature.
+pub fn recover_node_id_json(
+    value: &Value,
+    signature_algorithm: &SignatureAlgorithm,
+    signature_value: impl AsRef<[u8]>,
+) -> Result<NodeId, Error> {
+    if signature_algorithm.encryption != EncryptionAlgorithm::ECDSASecp256k1 {
+        return Err(Error::UnsupportedSignatureAlgorithm {
+            encryption: signature_algorithm.encryption.clone(),
+            hash: signature_algorithm.hash.clone(),
+        });
+    }
+    verify_supported_algorithm(signature_algorithm)?;
+    let canonical_json = canonicalize(value)?;
+    let recovered_key =
+        recover_verifying_key(canonical_json, &signature_algorithm.hash, signature_value)?;
+    Ok(node_id_from_verifying_key(&recovered_key))
+}
+
+// The recovery id is accepted both as 0/1 and in the Ethereum legacy 27/28 form
+fn recover_verifying_key(
+    bytes: impl AsRef<[u8]>,
+    hash_algorithm: &HashAlgorithm,
+    signature_value: impl AsRef<[u8]>,
+) -> Result<ECDSAVerifyingKey, Error> {
+    let signature_value = signature_value.as_ref();
+    if signature_value.len() != 65 {
+        return Err(Error::InvalidSignatureValue);
+    }
+    let signature = ECDSASignature::from_slice(&signature_value[..64])
+        .map_err(|_| Error::InvalidSignatureValue)?;
+    let v = signature_value[64];
+    let recovery_id = RecoveryId::from_byte(if v >= 27 { v - 27 } else { v })
+        .ok_or(Error::InvalidSignatureValue)?;
+    let bytes_hash = create_digest(bytes, hash_algorithm);
+    ECDSAVerifyingKey::recover_from_prehash(&bytes_hash, &signature, recovery_id)
+        .map_err(|_| Error::InvalidSignature)
+}
+
+// Ethereum address: last 20 bytes of the Keccak-256 hash of the uncompressed public key without the prefix byte
+fn node_id_from_verifying_key(verifying_key: &ECDSAVerifyingKey) -> NodeId {
+    let encoded_point = verifying_key.to_encoded_point(false);
+    let hash = Keccak256::digest(&encoded_point.as_bytes()[1..]);
+    NodeId::from(&hash[12..])
+}
+
 // OpenPGP uses the hash of the message as input to the signature algorithm
 // https://datatracker.ietf.org/doc/html/rfc4880#section-5.2.4
 // This is used when signing with OpenPGP application on smartcards
 fn verify_bytes_openpgp(
     bytes: impl AsRef<[u8]>,
+    hash_algorithm: &HashAlgorithm,
     signature: &EdDSASignature,
     public_key: &PublicKey,
 ) -> Result<(), Error> {
-    let bytes_hash = create_digest(bytes, &HashAlgorithm::Sha512);
+    let bytes_hash = create_digest(bytes, hash_algorithm);
     verify_bytes(bytes_hash, signature, public_key)
 }
 
+fn verify_bytes_prehashed(
+    bytes: impl AsRef<[u8]>,
+    signature: &EdDSASignature,
+    public_key: &PublicKey,
+) -> Result<(), Error> {
+    let prehashed_message = Sha512Prehash::new().chain(bytes);
+    public_key
+        .verify_prehashed(prehashed_message, None, signature)
+        .map_err(|_| Error::InvalidSignature)
+}
+
+/// Ed25519 signature of a canonical json, collected to be verified in a batch.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub(crate) struct BatchSignature {
+    message: Vec<u8>,
+    signature: [u8; 64],
+    public_key: [u8; 32],
+}
+
+/// Prepares signature to be verified in a batch, only plain EdDSA signatures can be batched.
+/// Returns `None` for other algorithms, these have to be verified with [`verify_signature_canonical_json`].
+/// Malformed signatures and keys are rejected with the same errors as by [`verify_signature_canonical_json`].
+pub(crate) fn prepare_batch_signature(
+    canonical_json: &[u8],
+    signature_algorithm: &SignatureAlgorithm,
+    signature_value: impl AsRef<[u8]>,
+    public_key: &Key,
+) -> Result<Option<BatchSignature>, Error> {
+    if signature_algorithm.encryption != EncryptionAlgorithm::EdDSA || public_key.is_secp256k1() {
+        return Ok(None);
+    }
+    verify_supported_algorithm(signature_algorithm)?;
+    let message = canonical_json.to_vec();
+    let signature = EdDSASignature::from_bytes(signature_value.as_ref())
+        .map_err(|_| Error::InvalidSignatureValue)?;
+    let public_key = PublicKey::from_bytes(&public_key.key).map_err(|_| Error::InvalidPublicKey)?;
+    Ok(Some(BatchSignature {
+        message,
+        signature: signature.to_bytes(),
+        public_key: public_key.to_bytes(),
+    }))
+}
+
+/// Verifies Ed25519 signatures at once, fails when any of them is invalid without telling which one.
+pub(crate) fn verify_batch(signatures: &[&BatchSignature]) -> Result<(), Error> {
+    if signatures.is_empty() {
+        return Ok(());
+    }
+    let messages: Vec<&[u8]> = signatures.iter().map(|s| s.message.as_slice()).collect();
+    let (eddsa_signatures, public_keys) = signatures
+        .iter()
+        .map(|s| {
+            let signature = EdDSASignature::from_bytes(&s.signature)
+                .map_err(|_| Error::InvalidSignatureValue)?;
+            let public_key =
+                PublicKey::from_bytes(&s.public_key).map_err(|_| Error::InvalidPublicKey)?;
+            Ok((signature, public_key))
+        })
+        .collect::<Result<(Vec<_>, Vec<_>), Error>>()?;
+    ed25519_dalek::verify_batch(&messages, &eddsa_signatures, &public_keys)
+        .map_err(|_| Error::InvalidSignature)
+}
+
 fn verify_bytes(
     bytes: impl AsRef<[u8]>,
     signature: &EdDSASignature,
@@ -148,3 +516,220 @@ fn verify_bytes(
         .verify(bytes.as_ref(), signature)
         .map_err(|_| Error::InvalidSignature)
 }
+
+#[cfg(test)]
+mod should {
+    use super::*;
+
+    use test_case::test_case;
+
+    fn signature_algorithm(
+        encryption: EncryptionAlgorithm,
+        hash: HashAlgorithm,
+    ) -> SignatureAlgorithm {
+        SignatureAlgorithm { hash, encryption }
+    }
+
+    #[test_case(EncryptionAlgorithm::EdDSA, HashAlgorithm::Sha512)]
+    #[test_case(EncryptionAlgorithm::EdDSAph, HashAlgorithm::Sha512)]
+    #[test_case(EncryptionAlgorithm::EdDSAOpenPGP, HashAlgorithm::Sha512)]
+    #[test_case(EncryptionAlgorithm::EdDSAOpenPGP, HashAlgorithm::Sha256)]
+    #[test_case(EncryptionAlgorithm::EdDSAOpenPGP, HashAlgorithm::Sha3_256)]
+    fn verify_signature_created_with_chosen_algorithm(
+        encryption: EncryptionAlgorithm,
+        hash: HashAlgorithm,
+    ) {
+        let key_pair = create_key_pair();
+        let value = json!({ "test": "value" });
+        let algorithm = signature_algorithm(encryption, hash);
+
+        let (algorithm, signature_value) =
+            sign_json_with_algorithm(&value, &key_pair.private_key, algorithm).unwrap();
+
+        assert!(
+            verify_signature_json(&value, &algorithm, signature_value, &key_pair.public_key)
+                .is_ok()
+        );
+    }
+
+    #[test_case(EncryptionAlgorithm::EdDSA, HashAlgorithm::Sha256)]
+    #[test_case(EncryptionAlgorithm::EdDSA, HashAlgorithm::Sha3_512)]
+    #[test_case(EncryptionAlgorithm::EdDSAph, HashAlgorithm::Sha384)]
+    #[test_case(EncryptionAlgorithm::ECDSASecp256k1, HashAlgorithm::Sha512)]
+    fn reject_unsupported_algorithm(encryption: EncryptionAlgorithm, hash: HashAlgorithm) {
+        let key_pair = create_key_pair();
+        let value = json!({ "test": "value" });
+        let (_, signature_value) = sign_json(&value, &key_pair.private_key).unwrap();
+        let algorithm = signature_algorithm(encryption.clone(), hash.clone());
+
+        assert!(
+            sign_json_with_algorithm(&value, &key_pair.private_key, algorithm.clone()).is_err()
+        );
+        assert_eq!(
+            verify_signature_json(&value, &algorithm, signature_value, &key_pair.public_key),
+            Err(Error::UnsupportedSignatureAlgorithm { encryption, hash })
+        );
+    }
+
+    #[test_case(HashAlgorithm::Sha256, HashAlgorithm::Sha512)]
+    #[test_case(HashAlgorithm::Sha512, HashAlgorithm::Sha3_512)]
+    fn reject_openpgp_signature_with_different_declared_hash(
+        signed_with: HashAlgorithm,
+        declared: HashAlgorithm,
+    ) {
+        let key_pair = create_key_pair();
+        let value = json!({ "test": "value" });
+        let algorithm = signature_algorithm(EncryptionAlgorithm::EdDSAOpenPGP, signed_with);
+        let (_, signature_value) =
+            sign_json_with_algorithm(&value, &key_pair.private_key, algorithm).unwrap();
+        let declared_algorithm = signature_algorithm(EncryptionAlgorithm::EdDSAOpenPGP, declared);
+
+        assert_eq!(
+            verify_signature_json(
+                &value,
+                &declared_algorithm,
+                signature_value,
+                &key_pair.public_key
+            ),
+            Err(Error::InvalidSignature)
+        );
+    }
+
+    #[test_case(HashAlgorithm::Keccak256)]
+    #[test_case(HashAlgorithm::Sha256)]
+    #[test_case(HashAlgorithm::Sha3_256)]
+    fn verify_secp256k1_signature_and_recover_node_id(hash: HashAlgorithm) {
+        let key_pair = create_secp256k1_key_pair();
+        let value = json!({ "test": "value" });
+        let algorithm = signature_algorithm(EncryptionAlgorithm::ECDSASecp256k1, hash);
+
+        let (algorithm, signature_value) =
+            sign_json_with_algorithm(&value, &key_pair.private_key, algorithm).unwrap();
+
+        assert!(
+            verify_signature_json(&value, &algorithm, &signature_value, &key_pair.public_key)
+                .is_ok()
+        );
+        assert_eq!(
+            recover_node_id_json(&value, &algorithm, &signature_value).unwrap(),
+            key_pair.public_key.node_id().unwrap()
+        );
+    }
+
+    #[test]
+    fn sign_with_secp256k1_by_default_for_secp256k1_key() {
+        let key_pair = create_secp256k1_key_pair();
+
+        let (algorithm, signature_value) =
+            sign_json(&json!({ "test": "value" }), &key_pair.private_key).unwrap();
+
+        assert_eq!(algorithm.encryption, EncryptionAlgorithm::ECDSASecp256k1);
+        assert_eq!(algorithm.hash, HashAlgorithm::Keccak256);
+        assert_eq!(signature_value.len(), 65);
+    }
+
+    #[test]
+    fn derive_ethereum_address_of_secp256k1_key() {
+        let private_key = Key::from_secp256k1_secret_key(
+            &hex::decode("4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318")
+                .unwrap(),
+        )
+        .unwrap();
+        let public_key = public_key_from_private(&private_key).unwrap();
+
+        assert_eq!(
+            public_key.node_id().unwrap(),
+            "0x2c7536e3605d9c16a7a3d7b1898e529396a65c23"
+                .parse()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn reject_secp256k1_signature_of_other_node() {
+        let key_pair = create_secp256k1_key_pair();
+        let other_node_id = create_secp256k1_key_pair().public_key.node_id().unwrap();
+        let value = json!({ "test": "value" });
+        let (algorithm, signature_value) = sign_json(&value, &key_pair.private_key).unwrap();
+
+        assert_eq!(
+            verify_signature_json_with_node_id(&value, &algorithm, signature_value, &other_node_id),
+            Err(Error::NodeIdMismatch {
+                expected: other_node_id,
+                recovered: key_pair.public_key.node_id().unwrap(),
+            })
+        );
+    }
+
+    #[test]
+    fn reject_secp256k1_signature_verified_with_other_key() {
+        let key_pair = create_secp256k1_key_pair();
+        let other_key_pair = create_secp256k1_key_pair();
+        let value = json!({ "test": "value" });
+        let (algorithm, signature_value) = sign_json(&value, &key_pair.private_key).unwrap();
+
+        assert_eq!(
+            verify_signature_json(
+                &value,
+                &algorithm,
+                signature_value,
+                &other_key_pair.public_key
+            ),
+            Err(Error::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn reject_mixing_key_and_signature_types() {
+        let ed25519_key_pair = create_key_pair();
+        let secp256k1_key_pair = create_secp256k1_key_pair();
+        let value = json!({ "test": "value" });
+        let ecdsa = signature_algorithm(
+            EncryptionAlgorithm::ECDSASecp256k1,
+            HashAlgorithm::Keccak256,
+        );
+        let (eddsa, eddsa_signature) = sign_json(&value, &ed25519_key_pair.private_key).unwrap();
+
+        assert!(sign_json_with_algorithm(&value, &ed25519_key_pair.private_key, ecdsa).is_err());
+        assert!(sign_json_with_algorithm(
+            &value,
+            &secp256k1_key_pair.private_key,
+            SignatureAlgorithm::default()
+        )
+        .is_err());
+        assert_eq!(
+            verify_signature_json(
+                &value,
+                &eddsa,
+                eddsa_signature,
+                &secp256k1_key_pair.public_key
+            ),
+            Err(Error::InvalidPublicKey)
+        );
+    }
+
+    #[test_case(EncryptionAlgorithm::EdDSA, EncryptionAlgorithm::EdDSAph)]
+    #[test_case(EncryptionAlgorithm::EdDSAph, EncryptionAlgorithm::EdDSA)]
+    #[test_case(EncryptionAlgorithm::EdDSAph, EncryptionAlgorithm::EdDSAOpenPGP)]
+    fn reject_signature_with_different_declared_encryption(
+        signed_with: EncryptionAlgorithm,
+        declared: EncryptionAlgorithm,
+    ) {
+        let key_pair = create_key_pair();
+        let value = json!({ "test": "value" });
+        let algorithm = signature_algorithm(signed_with, HashAlgorithm::Sha512);
+        let (_, signature_value) =
+            sign_json_with_algorithm(&value, &key_pair.private_key, algorithm).unwrap();
+        let declared_algorithm = signature_algorithm(declared, HashAlgorithm::Sha512);
+
+        assert_eq!(
+            verify_signature_json(
+                &value,
+                &declared_algorithm,
+                signature_value,
+                &key_pair.public_key
+            ),
+            Err(Error::InvalidSignature)
+        );
+    }
+}
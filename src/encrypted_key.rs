@@ -0,0 +1,282 @@
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+use chacha20poly1305::{
+    aead::{Aead, KeyInit},
+    ChaCha20Poly1305, Nonce,
+};
+use rand::{rngs::OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    cryptography::Key,
+    serde_utils::{bytes_to_hex, hex_to_bytes},
+    Error, Result,
+};
+
+const SALT_LENGTH: usize = 16;
+const NONCE_LENGTH: usize = 12;
+const ENCRYPTION_KEY_LENGTH: usize = 32;
+
+/// Private key encrypted with a key derived from a passphrase.
+/// Every parameter needed to derive the key and decrypt the private key is recorded in the structure,
+/// so it can be saved as is next to the other key files.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EncryptedKey {
+    pub kdf: KeyDerivation,
+    pub cipher: Cipher,
+    #[serde(serialize_with = "bytes_to_hex", deserialize_with = "hex_to_bytes")]
+    pub ciphertext: Vec<u8>,
+}
+
+/// Password based key derivation function and its parameters.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "algorithm", rename_all = "lowercase")]
+pub enum KeyDerivation {
+    #[serde(rename_all = "camelCase")]
+    Scrypt {
+        log_n: u8,
+        r: u32,
+        p: u32,
+        #[serde(serialize_with = "bytes_to_hex", deserialize_with = "hex_to_bytes")]
+        salt: Vec<u8>,
+    },
+}
+
+/// Authenticated encryption algorithm and its parameters.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "algorithm")]
+pub enum Cipher {
+    #[serde(rename = "chacha20-poly1305")]
+    ChaCha20Poly1305 {
+        #[serde(serialize_with = "bytes_to_hex", deserialize_with = "hex_to_bytes")]
+        nonce: Vec<u8>,
+    },
+}
+
+/// Cost parameters of scrypt, the salt is generated for every encryption.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ScryptParameters {
+    pub log_n: u8,
+    pub r: u32,
+    pub p: u32,
+}
+
+impl Default for ScryptParameters {
+    fn default() -> Self {
+        Self {
+            log_n: scrypt::Params::RECOMMENDED_LOG_N,
+            r: scrypt::Params::RECOMMENDED_R,
+            p: scrypt::Params::RECOMMENDED_P,
+        }
+    }
+}
+
+impl KeyDerivation {
+    fn derive_key(&self, passphrase: &str) -> Result<[u8; ENCRYPTION_KEY_LENGTH]> {
+        match self {
+            KeyDerivation::Scrypt { log_n, r, p, salt } => {
+                let params = scrypt::Params::new(*log_n, *r, *p, ENCRYPTION_KEY_LENGTH)
+                    .map_err(|e| Error::InvalidEncryptedKey(e.to_string()))?;
+                let mut key = [0u8; ENCRYPTION_KEY_LENGTH];
+                scrypt::scrypt(passphrase.as_bytes(), salt, &params, &mut key)
+                    .map_err(|e| Error::InvalidEncryptedKey(e.to_string()))?;
+                Ok(key)
+            }
+        }
+    }
+}
+
+impl EncryptedKey {
+    /// Encrypts the private key with the recommended scrypt parameters.
+    /// # Arguments
+    /// * `key` private key to encrypt
+    /// * `passphrase` passphrase the encryption key is derived from
+    pub fn encrypt(key: &Key, passphrase: &str) -> Result<Self> {
+        Self::encrypt_with_parameters(key, passphrase, Default::default())
+    }
+
+    /// Encrypts the private key with custom scrypt cost parameters.
+    /// # Arguments
+    /// * `key` private key to encrypt
+    /// * `passphrase` passphrase the encryption key is derived from
+    /// * `parameters` scrypt cost parameters
+    pub fn encrypt_with_parameters(
+        key: &Key,
+        passphrase: &str,
+        parameters: ScryptParameters,
+    ) -> Result<Self> {
+        let mut salt = vec![0u8; SALT_LENGTH];
+        OsRng.fill_bytes(&mut salt);
+        let mut nonce = vec![0u8; NONCE_LENGTH];
+        OsRng.fill_bytes(&mut nonce);
+        let kdf = KeyDerivation::Scrypt {
+            log_n: parameters.log_n,
+            r: parameters.r,
+            p: parameters.p,
+            salt,
+        };
+        let encryption_key = kdf.derive_key(passphrase)?;
+        let plaintext =
+            serde_json::to_vec(key).map_err(|e| Error::InvalidEncryptedKey(e.to_string()))?;
+        let ciphertext = ChaCha20Poly1305::new(&encryption_key.into())
+            .encrypt(Nonce::from_slice(&nonce), plaintext.as_slice())
+            .map_err(|_| Error::InvalidEncryptedKey("encryption failed".into()))?;
+        Ok(Self {
+            kdf,
+            cipher: Cipher::ChaCha20Poly1305 { nonce },
+            ciphertext,
+        })
+    }
+
+    /// Decrypts the private key.
+    /// # Arguments
+    /// * `passphrase` passphrase the key was encrypted with
+    pub fn decrypt(&self, passphrase: &str) -> Result<Key> {
+        let encryption_key = self.kdf.derive_key(passphrase)?;
+        let plaintext = match &self.cipher {
+            Cipher::ChaCha20Poly1305 { nonce } => {
+                if nonce.len() != NONCE_LENGTH {
+                    return Err(Error::InvalidEncryptedKey(format!(
+                        "nonce has to be {NONCE_LENGTH} bytes long"
+                    )));
+                }
+                ChaCha20Poly1305::new(&encryption_key.into())
+                    .decrypt(Nonce::from_slice(nonce), self.ciphertext.as_slice())
+                    .map_err(|_| Error::InvalidPassphrase)?
+            }
+        };
+        serde_json::from_slice(&plaintext).map_err(|e| Error::InvalidEncryptedKey(e.to_string()))
+    }
+
+    /// Checks whether the json has the structure of an encrypted key, see [`EncryptedKey`].
+    pub fn is_encrypted_key(value: &Value) -> bool {
+        value.get("kdf").is_some() && value.get("ciphertext").is_some()
+    }
+}
+
+/// Encrypts the private key with the passphrase and saves it as json, readable only by the owner on unix.
+/// # Arguments
+/// * `path` file to save the encrypted key to
+/// * `key` private key to encrypt
+/// * `passphrase` passphrase the encryption key is derived from
+pub fn save_encrypted_key(path: impl AsRef<Path>, key: &Key, passphrase: &str) -> Result<()> {
+    let encrypted_key = EncryptedKey::encrypt(key, passphrase)?;
+    let json = serde_json::to_string_pretty(&encrypted_key)
+        .map_err(|e| Error::InvalidEncryptedKey(e.to_string()))?;
+    write_private_file(path.as_ref(), json + "\n")
+        .map_err(|e| Error::InvalidEncryptedKey(e.to_string()))
+}
+
+/// Writes the file readable and writable only by its owner on unix, also when it already exists.
+fn write_private_file(path: &Path, contents: String) -> std::io::Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file = options.open(path)?;
+    #[cfg(unix)]
+    file.set_permissions(std::os::unix::fs::PermissionsExt::from_mode(0o600))?;
+    file.write_all(contents.as_bytes())
+}
+
+/// Loads an encrypted private key file and decrypts it with the passphrase.
+/// # Arguments
+/// * `path` json file created by [`save_encrypted_key`]
+/// * `passphrase` passphrase the key was encrypted with
+pub fn load_encrypted_key(path: impl AsRef<Path>, passphrase: &str) -> Result<Key> {
+    let json = fs::read_to_string(path).map_err(|e| Error::InvalidEncryptedKey(e.to_string()))?;
+    let encrypted_key: EncryptedKey =
+        serde_json::from_str(&json).map_err(|e| Error::InvalidEncryptedKey(e.to_string()))?;
+    encrypted_key.decrypt(passphrase)
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+
+    use test_case::test_case;
+
+    use crate::{create_key_pair, create_secp256k1_key_pair};
+
+    const PASSPHRASE: &str = "correct horse battery staple";
+
+    // Cheap parameters to keep the tests fast
+    const TEST_PARAMETERS: ScryptParameters = ScryptParameters {
+        log_n: 4,
+        r: 8,
+        p: 1,
+    };
+
+    #[test_case(create_key_pair().private_key)]
+    #[test_case(create_secp256k1_key_pair().private_key)]
+    fn decrypt_encrypted_key(key: Key) {
+        let encrypted_key =
+            EncryptedKey::encrypt_with_parameters(&key, PASSPHRASE, TEST_PARAMETERS).unwrap();
+
+        assert_eq!(encrypted_key.decrypt(PASSPHRASE).unwrap(), key);
+    }
+
+    #[test]
+    fn reject_wrong_passphrase() {
+        let key = create_key_pair().private_key;
+        let encrypted_key =
+            EncryptedKey::encrypt_with_parameters(&key, PASSPHRASE, TEST_PARAMETERS).unwrap();
+
+        assert_eq!(
+            encrypted_key.decrypt("wrong passphrase").unwrap_err(),
+            Error::InvalidPassphrase
+        );
+    }
+
+    #[test]
+    fn reject_tampered_ciphertext() {
+        let key = create_key_pair().private_key;
+        let mut encrypted_key =
+            EncryptedKey::encrypt_with_parameters(&key, PASSPHRASE, TEST_PARAMETERS).unwrap();
+        encrypted_key.ciphertext[0] ^= 1;
+
+        assert_eq!(
+            encrypted_key.decrypt(PASSPHRASE).unwrap_err(),
+            Error::InvalidPassphrase
+        );
+    }
+
+    #[test]
+    fn record_parameters_in_json() {
+        let key = create_key_pair().private_key;
+        let encrypted_key =
+            EncryptedKey::encrypt_with_parameters(&key, PASSPHRASE, TEST_PARAMETERS).unwrap();
+
+        let json = serde_json::to_value(&encrypted_key).unwrap();
+
+        assert!(EncryptedKey::is_encrypted_key(&json));
+        assert!(!EncryptedKey::is_encrypted_key(
+            &serde_json::to_value(&key).unwrap()
+        ));
+        assert_eq!(json["kdf"]["algorithm"], "scrypt");
+        assert_eq!(json["kdf"]["logN"], 4);
+        assert_eq!(json["cipher"]["algorithm"], "chacha20-poly1305");
+        let deserialized: EncryptedKey = serde_json::from_value(json).unwrap();
+        assert_eq!(deserialized.decrypt(PASSPHRASE).unwrap(), key);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn create_key_file_readable_only_by_owner() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = std::env::temp_dir().join(format!("gcert-key-{}.json", std::process::id()));
+        fs::write(&path, "").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+
+        write_private_file(&path, "{}".to_string()).unwrap();
+
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+}
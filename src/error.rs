@@ -1,11 +1,23 @@
+use std::fmt;
+
 use chrono::{DateTime, Utc};
+use ya_client_model::NodeId;
 
+use crate::cryptography::{EncryptionAlgorithm, HashAlgorithm};
 use crate::schemas::{
-    certificate::key_usage::KeyUsage, permissions::Permissions, validity_period::ValidityPeriod,
+    certificate::{key_usage::KeyUsage, Fingerprint},
+    permissions::PermissionCategory,
+    validity_period::ValidityPeriod,
 };
 
-#[derive(thiserror::Error, Debug, PartialEq)]
+#[derive(thiserror::Error, Debug, Clone, PartialEq)]
 pub enum Error {
+    /// Error raised by a link of the certificate chain, compare `without_chain_position()` to match the wrapped error
+    #[error("{error} ({position})")]
+    InChain {
+        position: ChainPosition,
+        error: Box<Error>,
+    },
     #[error("Expired: was valid to {0}")]
     Expired(DateTime<Utc>),
     #[error("Not valid yet: will be valid from {0}")]
@@ -15,29 +27,69 @@ pub enum Error {
         parent: ValidityPeriod,
         child: ValidityPeriod,
     },
-    #[error("Permissions extended: {parent:?}, {child:?}")]
-    PermissionsExtended {
-        parent: Permissions,
-        child: Permissions,
-    },
+    #[error("Permissions extended: {category} permissions are not granted by the parent")]
+    PermissionsExtended { category: PermissionCategory },
+    #[error("Unknown permission `{property}` is not granted with the same value by the parent")]
+    UnknownPermissionExtended { property: String },
     #[error("Key usage extended: {parent:?}, {child:?}")]
     KeyUsageExtended { parent: KeyUsage, child: KeyUsage },
     #[error("Certificate signing not permitted")]
     CertSignNotPermitted,
     #[error("Certificate cannot sign Node Descriptor")]
     NodeSignNotPermitted,
+    #[error("Certificate cannot sign Manifest")]
+    ManifestSignNotPermitted,
+    #[error("Invalid URL pattern {0}")]
+    InvalidUrlPattern(String),
+    #[error("Invalid manifest: {0}")]
+    InvalidManifest(String),
+    #[error("Certificate cannot sign Revocation List")]
+    RevocationListSignNotPermitted,
+    #[error("Certificate revoked: {0}")]
+    Revoked(Fingerprint),
     #[error("Invalid signature")]
     InvalidSignature,
+    #[error("Unsupported signature algorithm: {encryption:?} with {hash:?}")]
+    UnsupportedSignatureAlgorithm {
+        encryption: EncryptionAlgorithm,
+        hash: HashAlgorithm,
+    },
     #[error("Invalid signature value (cannot deserialize)")]
     InvalidSignatureValue,
     #[error("Invalid public key")]
     InvalidPublicKey,
+    #[error("Invalid private key")]
+    InvalidPrivateKey,
+    #[error("Private key does not match the public key of the signer")]
+    KeyMismatch,
+    #[error("Signature was created by node {recovered} instead of {expected}")]
+    NodeIdMismatch { expected: NodeId, recovered: NodeId },
+    #[error("Node descriptor is not signed by the node identity")]
+    MissingNodeSignature,
+    #[error("Invalid key format: {0}")]
+    InvalidKeyFormat(String),
+    #[error("Invalid mnemonic: {0}")]
+    InvalidMnemonic(String),
+    #[error("Invalid derivation path {0}")]
+    InvalidDerivationPath(String),
+    #[error("Invalid passphrase or corrupted encrypted key")]
+    InvalidPassphrase,
+    #[error("Invalid encrypted key: {0}")]
+    InvalidEncryptedKey(String),
+    #[error("Signing backend error: {0}")]
+    SigningBackend(String),
     #[error("Invalid json: {0}")]
     InvalidJson(String),
     #[error("JCS serialization error: {0}")]
     JcsSerializationError(String),
     #[error("Json does not conform to schema: {0}")]
     JsonDoesNotConformToSchema(String),
+    #[error("Certificate chain is not anchored in a trusted root: {0}")]
+    UntrustedRoot(Fingerprint),
+    #[error("Certificate chain is too deep: root at depth {depth}, at most {max_depth} allowed")]
+    ChainTooDeep { depth: usize, max_depth: usize },
+    #[error("Cannot load trust anchor from {path}: {reason}")]
+    InvalidTrustAnchor { path: String, reason: String },
     #[error("Unsupported schema for structure {structure_name}: {schema}")]
     UnsupportedSchema {
         schema: String,
@@ -45,4 +97,88 @@ pub enum Error {
     },
 }
 
+impl Error {
+    /// Position in the chain where the error was raised, if known.
+    pub fn chain_position(&self) -> Option<&ChainPosition> {
+        match self {
+            Error::InChain { position, .. } => Some(position),
+            _ => None,
+        }
+    }
+
+    /// Strips the chain position context from the error.
+    pub fn without_chain_position(self) -> Error {
+        match self {
+            Error::InChain { error, .. } => error.without_chain_position(),
+            error => error,
+        }
+    }
+
+    /// Name of the error variant, of the wrapped error for errors raised in the chain.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Error::InChain { error, .. } => error.kind(),
+            Error::Expired(..) => "Expired",
+            Error::NotValidYet(..) => "NotValidYet",
+            Error::ValidityPeriodExtended { .. } => "ValidityPeriodExtended",
+            Error::PermissionsExtended { .. } => "PermissionsExtended",
+            Error::UnknownPermissionExtended { .. } => "UnknownPermissionExtended",
+            Error::KeyUsageExtended { .. } => "KeyUsageExtended",
+            Error::CertSignNotPermitted => "CertSignNotPermitted",
+            Error::NodeSignNotPermitted => "NodeSignNotPermitted",
+            Error::ManifestSignNotPermitted => "ManifestSignNotPermitted",
+            Error::InvalidUrlPattern(..) => "InvalidUrlPattern",
+            Error::InvalidManifest(..) => "InvalidManifest",
+            Error::RevocationListSignNotPermitted => "RevocationListSignNotPermitted",
+            Error::Revoked(..) => "Revoked",
+            Error::InvalidSignature => "InvalidSignature",
+            Error::UnsupportedSignatureAlgorithm { .. } => "UnsupportedSignatureAlgorithm",
+            Error::InvalidSignatureValue => "InvalidSignatureValue",
+            Error::InvalidPublicKey => "InvalidPublicKey",
+            Error::InvalidPrivateKey => "InvalidPrivateKey",
+            Error::KeyMismatch => "KeyMismatch",
+            Error::NodeIdMismatch { .. } => "NodeIdMismatch",
+            Error::MissingNodeSignature => "MissingNodeSignature",
+            Error::InvalidKeyFormat(..) => "InvalidKeyFormat",
+            Error::InvalidMnemonic(..) => "InvalidMnemonic",
+            Error::InvalidDerivationPath(..) => "InvalidDerivationPath",
+            Error::InvalidPassphrase => "InvalidPassphrase",
+            Error::InvalidEncryptedKey(..) => "InvalidEncryptedKey",
+            Error::SigningBackend(..) => "SigningBackend",
+            Error::InvalidJson(..) => "InvalidJson",
+            Error::JcsSerializationError(..) => "JcsSerializationError",
+            Error::JsonDoesNotConformToSchema(..) => "JsonDoesNotConformToSchema",
+            Error::UntrustedRoot(..) => "UntrustedRoot",
+            Error::ChainTooDeep { .. } => "ChainTooDeep",
+            Error::InvalidTrustAnchor { .. } => "InvalidTrustAnchor",
+            Error::UnsupportedSchema { .. } => "UnsupportedSchema",
+        }
+    }
+}
+
+/// Link of the chain where validation failed.
+/// Depth 0 is the validated document, the signing certificate is at depth 1, its signer at depth 2 and so on.
+/// When a certificate is validated it is the document at depth 0.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct ChainPosition {
+    pub depth: usize,
+    /// Fingerprint of the certificate, missing for other documents or when it cannot be created
+    pub fingerprint: Option<Fingerprint>,
+    /// Display name of the certificate subject, missing for other documents or when the certificate cannot be parsed
+    pub display_name: Option<String>,
+}
+
+impl fmt::Display for ChainPosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "chain depth {}", self.depth)?;
+        if let Some(display_name) = &self.display_name {
+            write!(f, ", certificate \"{display_name}\"")?;
+        }
+        if let Some(fingerprint) = &self.fingerprint {
+            write!(f, ", fingerprint {fingerprint}")?;
+        }
+        Ok(())
+    }
+}
+
 pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,476 @@
+use base64::{
+    engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD},
+    Engine,
+};
+use rand::{rngs::OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cryptography::{ed25519_key_bytes, public_key_from_private, Key},
+    Error, Result,
+};
+
+// DER encodings of the Ed25519 key structures are fixed apart from the key bytes
+// https://datatracker.ietf.org/doc/html/rfc8410#section-7
+const PKCS8_V1_PREFIX: [u8; 16] = [
+    0x30, 0x2e, 0x02, 0x01, 0x00, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x04, 0x22, 0x04, 0x20,
+];
+// OneAsymmetricKey (version 2) carrying the public key after the private key
+const PKCS8_V2_PREFIX: [u8; 16] = [
+    0x30, 0x51, 0x02, 0x01, 0x01, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x04, 0x22, 0x04, 0x20,
+];
+const PKCS8_V2_PUBLIC_KEY_PREFIX: [u8; 3] = [0x81, 0x21, 0x00];
+const SPKI_PREFIX: [u8; 12] = [
+    0x30, 0x2a, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x03, 0x21, 0x00,
+];
+
+const PEM_PRIVATE_KEY_LABEL: &str = "PRIVATE KEY";
+const PEM_PUBLIC_KEY_LABEL: &str = "PUBLIC KEY";
+const PEM_OPENSSH_PRIVATE_KEY_LABEL: &str = "OPENSSH PRIVATE KEY";
+
+// https://github.com/openssh/openssh-portable/blob/master/PROTOCOL.key
+const OPENSSH_MAGIC: &[u8] = b"openssh-key-v1\0";
+const OPENSSH_ED25519: &str = "ssh-ed25519";
+const OPENSSH_NONE: &str = "none";
+const OPENSSH_BLOCK_SIZE: usize = 8;
+const OPENSSH_LINE_LENGTH: usize = 70;
+const PEM_LINE_LENGTH: usize = 64;
+
+/// External key formats of Ed25519 keys.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum KeyFormat {
+    /// PKCS#8 private keys and SubjectPublicKeyInfo public keys in PEM encoding
+    Pem,
+    /// OpenSSH `ssh-ed25519` public keys and `openssh-key-v1` private keys
+    OpenSsh,
+    /// RFC 8037 OKP JSON Web Keys
+    Jwk,
+}
+
+impl KeyFormat {
+    /// Recognizes the format of the input, `None` when it is none of the supported formats.
+    pub fn detect(input: &str) -> Option<Self> {
+        let input = input.trim_start();
+        if input.starts_with(&pem_begin(PEM_OPENSSH_PRIVATE_KEY_LABEL))
+            || input.starts_with(OPENSSH_ED25519)
+        {
+            Some(KeyFormat::OpenSsh)
+        } else if input.starts_with("-----BEGIN ") {
+            Some(KeyFormat::Pem)
+        } else if serde_json::from_str::<Jwk>(input).is_ok() {
+            Some(KeyFormat::Jwk)
+        } else {
+            None
+        }
+    }
+}
+
+/// Key read from an external format.
+/// The public key is always present, it is derived from the private key when the format does not contain it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ImportedKey {
+    pub public_key: Key,
+    pub private_key: Option<Key>,
+}
+
+impl ImportedKey {
+    /// Completes a private key with its public key.
+    pub fn from_private_key(private_key: Key) -> Result<Self> {
+        Ok(Self {
+            public_key: public_key_from_private(&private_key)?,
+            private_key: Some(private_key),
+        })
+    }
+}
+
+#[derive(Deserialize, Serialize)]
+struct Jwk {
+    kty: String,
+    crv: String,
+    x: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    d: Option<String>,
+}
+
+/// Imports an Ed25519 key from PEM, OpenSSH or JWK, the format is detected from the input.
+/// Encrypted OpenSSH private keys are not supported.
+/// # Arguments
+/// * `input` contents of the key file
+pub fn import_key(input: &str) -> Result<ImportedKey> {
+    match KeyFormat::detect(input) {
+        Some(KeyFormat::Pem) => import_pem(input),
+        Some(KeyFormat::OpenSsh) => import_openssh(input),
+        Some(KeyFormat::Jwk) => import_jwk(input),
+        None => Err(invalid_key_format("unknown key format")),
+    }
+}
+
+/// Exports an Ed25519 public key.
+/// # Arguments
+/// * `public_key` the public key
+/// * `format` format of the output
+pub fn export_public_key(public_key: &Key, format: KeyFormat) -> Result<String> {
+    let public_key = ed25519_key_bytes(public_key)?;
+    let output = match format {
+        KeyFormat::Pem => encode_pem(
+            PEM_PUBLIC_KEY_LABEL,
+            &[SPKI_PREFIX.as_slice(), &public_key].concat(),
+            PEM_LINE_LENGTH,
+        ),
+        KeyFormat::OpenSsh => format!(
+            "{OPENSSH_ED25519} {}\n",
+            STANDARD.encode(openssh_public_key_blob(&public_key))
+        ),
+        KeyFormat::Jwk => encode_jwk(&public_key, None)?,
+    };
+    Ok(output)
+}
+
+/// Exports an Ed25519 private key, formats that carry the public key as well get it derived from the private key.
+/// # Arguments
+/// * `private_key` the private key
+/// * `format` format of the output
+pub fn export_private_key(private_key: &Key, format: KeyFormat) -> Result<String> {
+    let public_key = ed25519_key_bytes(&public_key_from_private(private_key)?)?;
+    let private_key = ed25519_key_bytes(private_key)?;
+    let output = match format {
+        KeyFormat::Pem => encode_pem(
+            PEM_PRIVATE_KEY_LABEL,
+            &[PKCS8_V1_PREFIX.as_slice(), &private_key].concat(),
+            PEM_LINE_LENGTH,
+        ),
+        KeyFormat::OpenSsh => encode_pem(
+            PEM_OPENSSH_PRIVATE_KEY_LABEL,
+            &openssh_private_key(&private_key, &public_key),
+            OPENSSH_LINE_LENGTH,
+        ),
+        KeyFormat::Jwk => encode_jwk(&public_key, Some(&private_key))?,
+    };
+    Ok(output)
+}
+
+fn import_pem(input: &str) -> Result<ImportedKey> {
+    let (label, der) = decode_pem(input)?;
+    match label.as_str() {
+        PEM_PRIVATE_KEY_LABEL => {
+            let (private_key, public_key) = if der.len() == 48 && der[..16] == PKCS8_V1_PREFIX {
+                (&der[16..48], None)
+            } else if der.len() == 83
+                && der[..16] == PKCS8_V2_PREFIX
+                && der[48..51] == PKCS8_V2_PUBLIC_KEY_PREFIX
+            {
+                (&der[16..48], Some(&der[51..83]))
+            } else {
+                return Err(invalid_key_format("not an Ed25519 PKCS#8 private key"));
+            };
+            imported_private_key(private_key, public_key)
+        }
+        PEM_PUBLIC_KEY_LABEL => {
+            if der.len() != 44 || der[..12] != SPKI_PREFIX {
+                return Err(invalid_key_format("not an Ed25519 public key"));
+            }
+            imported_public_key(&der[12..])
+        }
+        PEM_OPENSSH_PRIVATE_KEY_LABEL => import_openssh_private_key(&der),
+        label => Err(invalid_key_format(format!("unsupported PEM label {label}"))),
+    }
+}
+
+fn import_openssh(input: &str) -> Result<ImportedKey> {
+    let input = input.trim();
+    if input.starts_with(OPENSSH_ED25519) {
+        let encoded = input
+            .split_whitespace()
+            .nth(1)
+            .ok_or_else(|| invalid_key_format("missing OpenSSH public key data"))?;
+        let blob = STANDARD
+            .decode(encoded)
+            .map_err(|e| invalid_key_format(e.to_string()))?;
+        let mut reader = SshReader::new(&blob);
+        let public_key = read_openssh_public_key(&mut reader)?;
+        imported_public_key(public_key)
+    } else {
+        import_pem(input)
+    }
+}
+
+fn import_openssh_private_key(data: &[u8]) -> Result<ImportedKey> {
+    let mut reader = SshReader::new(
+        data.strip_prefix(OPENSSH_MAGIC)
+            .ok_or_else(|| invalid_key_format("not an OpenSSH private key"))?,
+    );
+    let cipher_name = reader.read_string()?;
+    let kdf_name = reader.read_string()?;
+    if cipher_name != OPENSSH_NONE.as_bytes() || kdf_name != OPENSSH_NONE.as_bytes() {
+        return Err(invalid_key_format(
+            "encrypted OpenSSH private keys are not supported",
+        ));
+    }
+    let _kdf_options = reader.read_string()?;
+    if reader.read_u32()? != 1 {
+        return Err(invalid_key_format(
+            "only OpenSSH files with a single key are supported",
+        ));
+    }
+    let _public_key_blob = reader.read_string()?;
+    let mut section = SshReader::new(reader.read_string()?);
+    if section.read_u32()? != section.read_u32()? {
+        return Err(invalid_key_format("corrupted OpenSSH private key"));
+    }
+    let public_key = read_openssh_public_key(&mut section)?;
+    // the private key is stored as the seed followed by the public key
+    let private_key = section.read_string()?;
+    if private_key.len() != 64 || &private_key[32..] != public_key {
+        return Err(invalid_key_format("corrupted OpenSSH private key"));
+    }
+    imported_private_key(&private_key[..32], Some(public_key))
+}
+
+fn import_jwk(input: &str) -> Result<ImportedKey> {
+    let jwk: Jwk = serde_json::from_str(input).map_err(|e| invalid_key_format(e.to_string()))?;
+    if jwk.kty != "OKP" || jwk.crv != "Ed25519" {
+        return Err(invalid_key_format(format!(
+            "unsupported JWK key type {} {}",
+            jwk.kty, jwk.crv
+        )));
+    }
+    let decode = |value: &str| {
+        URL_SAFE_NO_PAD
+            .decode(value)
+            .map_err(|e| invalid_key_format(e.to_string()))
+    };
+    let public_key = decode(&jwk.x)?;
+    match jwk.d {
+        Some(private_key) => imported_private_key(&decode(&private_key)?, Some(&public_key)),
+        None => imported_public_key(&public_key),
+    }
+}
+
+fn imported_public_key(public_key: &[u8]) -> Result<ImportedKey> {
+    let public_key: [u8; 32] = public_key
+        .try_into()
+        .map_err(|_| invalid_key_format("Ed25519 public key has to be 32 bytes long"))?;
+    Ok(ImportedKey {
+        public_key: public_key.into(),
+        private_key: None,
+    })
+}
+
+/// Derives the public key, checking that it matches the one stored next to the private key.
+fn imported_private_key(private_key: &[u8], public_key: Option<&[u8]>) -> Result<ImportedKey> {
+    let private_key: [u8; 32] = private_key
+        .try_into()
+        .map_err(|_| invalid_key_format("Ed25519 private key has to be 32 bytes long"))?;
+    let imported_key = ImportedKey::from_private_key(private_key.into())?;
+    if let Some(public_key) = public_key {
+        if ed25519_key_bytes(&imported_key.public_key)?.as_slice() != public_key {
+            return Err(Error::KeyMismatch);
+        }
+    }
+    Ok(imported_key)
+}
+
+fn encode_jwk(public_key: &[u8], private_key: Option<&[u8]>) -> Result<String> {
+    let jwk = Jwk {
+        kty: "OKP".into(),
+        crv: "Ed25519".into(),
+        x: URL_SAFE_NO_PAD.encode(public_key),
+        d: private_key.map(|private_key| URL_SAFE_NO_PAD.encode(private_key)),
+    };
+    serde_json::to_string_pretty(&jwk)
+        .map(|jwk| jwk + "\n")
+        .map_err(|e| Error::InvalidJson(e.to_string()))
+}
+
+fn pem_begin(label: &str) -> String {
+    format!("-----BEGIN {label}-----")
+}
+
+fn pem_end(label: &str) -> String {
+    format!("-----END {label}-----")
+}
+
+fn encode_pem(label: &str, data: &[u8], line_length: usize) -> String {
+    let encoded = STANDARD.encode(data);
+    let mut pem = pem_begin(label) + "\n";
+    for line in encoded.as_bytes().chunks(line_length) {
+        pem.push_str(std::str::from_utf8(line).unwrap());
+        pem.push('\n');
+    }
+    pem + &pem_end(label) + "\n"
+}
+
+fn decode_pem(input: &str) -> Result<(String, Vec<u8>)> {
+    let input = input.trim();
+    let label = input
+        .strip_prefix("-----BEGIN ")
+        .and_then(|rest| rest.split_once("-----"))
+        .map(|(label, _)| label.to_string())
+        .ok_or_else(|| invalid_key_format("missing PEM header"))?;
+    let body = input[pem_begin(&label).len()..]
+        .strip_suffix(&pem_end(&label))
+        .ok_or_else(|| invalid_key_format("missing PEM footer"))?;
+    let body: String = body.chars().filter(|c| !c.is_whitespace()).collect();
+    let der = STANDARD
+        .decode(body)
+        .map_err(|e| invalid_key_format(e.to_string()))?;
+    Ok((label, der))
+}
+
+fn openssh_public_key_blob(public_key: &[u8]) -> Vec<u8> {
+    let mut blob = vec![];
+    write_ssh_string(&mut blob, OPENSSH_ED25519.as_bytes());
+    write_ssh_string(&mut blob, public_key);
+    blob
+}
+
+fn openssh_private_key(private_key: &[u8], public_key: &[u8]) -> Vec<u8> {
+    let check = OsRng.next_u32().to_be_bytes();
+    let mut section = [check, check].concat();
+    section.extend(openssh_public_key_blob(public_key));
+    write_ssh_string(&mut section, &[private_key, public_key].concat());
+    // empty comment
+    write_ssh_string(&mut section, &[]);
+    let padding = (OPENSSH_BLOCK_SIZE - section.len() % OPENSSH_BLOCK_SIZE) % OPENSSH_BLOCK_SIZE;
+    section.extend((1..=padding as u8).collect::<Vec<_>>());
+
+    let mut data = OPENSSH_MAGIC.to_vec();
+    write_ssh_string(&mut data, OPENSSH_NONE.as_bytes());
+    write_ssh_string(&mut data, OPENSSH_NONE.as_bytes());
+    write_ssh_string(&mut data, &[]);
+    data.extend(1u32.to_be_bytes());
+    write_ssh_string(&mut data, &openssh_public_key_blob(public_key));
+    write_ssh_string(&mut data, &section);
+    data
+}
+
+fn write_ssh_string(buffer: &mut Vec<u8>, value: &[u8]) {
+    buffer.extend((value.len() as u32).to_be_bytes());
+    buffer.extend(value);
+}
+
+fn read_openssh_public_key<'a>(reader: &mut SshReader<'a>) -> Result<&'a [u8]> {
+    let key_type = reader.read_string()?;
+    if key_type != OPENSSH_ED25519.as_bytes() {
+        return Err(invalid_key_format(format!(
+            "unsupported OpenSSH key type {}",
+            String::from_utf8_lossy(key_type)
+        )));
+    }
+    reader.read_string()
+}
+
+/// Reads the length prefixed fields of the OpenSSH wire format.
+struct SshReader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> SshReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    fn read_bytes(&mut self, length: usize) -> Result<&'a [u8]> {
+        if self.data.len() < length {
+            return Err(invalid_key_format("truncated OpenSSH key"));
+        }
+        let (bytes, rest) = self.data.split_at(length);
+        self.data = rest;
+        Ok(bytes)
+    }
+
+    fn read_u32(&mut self) -> Result<u32> {
+        let bytes = self.read_bytes(4)?;
+        Ok(u32::from_be_bytes(bytes.try_into().unwrap()))
+    }
+
+    fn read_string(&mut self) -> Result<&'a [u8]> {
+        let length = self.read_u32()? as usize;
+        self.read_bytes(length)
+    }
+}
+
+fn invalid_key_format(reason: impl Into<String>) -> Error {
+    Error::InvalidKeyFormat(reason.into())
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+
+    use test_case::test_case;
+
+    use crate::{create_key_pair, create_secp256k1_key_pair};
+
+    #[test_case(KeyFormat::Pem)]
+    #[test_case(KeyFormat::OpenSsh)]
+    #[test_case(KeyFormat::Jwk)]
+    fn round_trip_private_key(format: KeyFormat) {
+        let key_pair = create_key_pair();
+
+        let exported = export_private_key(&key_pair.private_key, format).unwrap();
+        let imported = import_key(&exported).unwrap();
+
+        assert_eq!(KeyFormat::detect(&exported), Some(format));
+        assert_eq!(imported.private_key, Some(key_pair.private_key));
+        assert_eq!(imported.public_key, key_pair.public_key);
+    }
+
+    #[test_case(KeyFormat::Pem)]
+    #[test_case(KeyFormat::OpenSsh)]
+    #[test_case(KeyFormat::Jwk)]
+    fn round_trip_public_key(format: KeyFormat) {
+        let key_pair = create_key_pair();
+
+        let exported = export_public_key(&key_pair.public_key, format).unwrap();
+        let imported = import_key(&exported).unwrap();
+
+        assert_eq!(KeyFormat::detect(&exported), Some(format));
+        assert_eq!(imported.private_key, None);
+        assert_eq!(imported.public_key, key_pair.public_key);
+    }
+
+    #[test]
+    fn import_rfc8037_jwk() {
+        // https://datatracker.ietf.org/doc/html/rfc8037#appendix-A.1
+        let jwk = r#"{"kty":"OKP","crv":"Ed25519",
+            "d":"nWGxne_9WmC6hEr0kuwsxERJxWl7MmkZcDusAxyuf2A",
+            "x":"11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo"}"#;
+
+        let imported = import_key(jwk).unwrap();
+
+        assert_eq!(
+            serde_json::to_value(imported.public_key).unwrap()["key"],
+            "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a"
+        );
+    }
+
+    #[test]
+    fn reject_jwk_with_mismatching_public_key() {
+        let jwk = r#"{"kty":"OKP","crv":"Ed25519",
+            "d":"nWGxne_9WmC6hEr0kuwsxERJxWl7MmkZcDusAxyuf2A",
+            "x":"Gb9ECWmEzf6FQbrBZ9w7lYHvaOHhOeOUYuuqkzFXj-I"}"#;
+
+        assert_eq!(import_key(jwk).unwrap_err(), Error::KeyMismatch);
+    }
+
+    #[test_case("not a key")]
+    #[test_case(r#"{"kty":"EC","crv":"P-256","x":"AAAA"}"#)]
+    #[test_case("-----BEGIN CERTIFICATE-----\nAAAA\n-----END CERTIFICATE-----\n")]
+    #[test_case("ssh-rsa AAAAB3NzaC1yc2EAAAADAQABAAABAQC")]
+    fn reject_unsupported_input(input: &str) {
+        assert!(matches!(
+            import_key(input).unwrap_err(),
+            Error::InvalidKeyFormat(_)
+        ));
+    }
+
+    #[test]
+    fn reject_secp256k1_key() {
+        let key_pair = create_secp256k1_key_pair();
+
+        let result = export_private_key(&key_pair.private_key, KeyFormat::Pem);
+
+        assert!(matches!(result.unwrap_err(), Error::InvalidKeyFormat(_)));
+    }
+}
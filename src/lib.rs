@@ -1,29 +1,108 @@
 mod cryptography;
 mod serde_utils;
+#[cfg(test)]
+mod test_utils;
 
+pub mod builder;
+pub mod encrypted_key;
 pub mod error;
+pub mod key_format;
+pub mod mnemonic;
 pub mod schemas;
+pub mod signing;
 pub mod validator;
 
+pub use builder::sign_manifest;
+pub use builder::sign_manifest_with_backend;
+pub use builder::sign_node_identity;
+pub use builder::sign_node_identity_with_backend;
+pub use builder::CertificateBuilder;
+pub use builder::NodeDescriptorBuilder;
+pub use builder::RevocationListBuilder;
+
+pub use encrypted_key::load_encrypted_key;
+pub use encrypted_key::save_encrypted_key;
+pub use encrypted_key::EncryptedKey;
+
+pub use key_format::export_private_key;
+pub use key_format::export_public_key;
+pub use key_format::import_key;
+pub use key_format::ImportedKey;
+pub use key_format::KeyFormat;
+
+pub use mnemonic::create_key_pair_with_mnemonic;
+pub use mnemonic::recover_key_pair;
+pub use mnemonic::MnemonicKeyPair;
+
 pub use cryptography::create_default_hash;
+pub use cryptography::create_hash;
 pub use cryptography::create_key_pair;
+pub use cryptography::create_secp256k1_key_pair;
+pub use cryptography::recover_node_id_json;
 pub use cryptography::sign_json;
+pub use cryptography::sign_json_with_algorithm;
 pub use cryptography::verify_signature_json;
+pub use cryptography::verify_signature_json_with_node_id;
 
 pub use cryptography::EncryptionAlgorithm;
+pub use cryptography::HashAlgorithm;
 pub use cryptography::Key;
 pub use cryptography::KeyPair;
 
+pub use schemas::signature::NodeSignature;
 pub use schemas::signature::Signature;
 pub use schemas::signature::SignatureAlgorithm;
 pub use schemas::signature::SignedCertificate;
+pub use schemas::signature::SignedManifest;
 pub use schemas::signature::SignedNodeDescriptor;
+pub use schemas::signature::SignedRevocationList;
 pub use schemas::signature::Signer;
 
+pub use validator::batch::validate_certificates_batch;
+pub use validator::batch::validate_node_descriptors_batch;
 pub use validator::validate_certificate;
+pub use validator::validate_certificate_report;
+pub use validator::validate_certificate_report_with_options;
 pub use validator::validate_certificate_str;
+pub use validator::validate_certificate_str_report;
+pub use validator::validate_certificate_str_with_options;
+pub use validator::validate_certificate_str_with_trust_store;
+pub use validator::validate_certificate_with_options;
+pub use validator::validate_certificate_with_trust_store;
+pub use validator::validate_manifest;
+pub use validator::validate_manifest_str;
+pub use validator::validate_manifest_str_with_options;
+pub use validator::validate_manifest_with_options;
 pub use validator::validate_node_descriptor;
 pub use validator::validate_node_descriptor_str;
+pub use validator::validate_node_descriptor_str_strict;
+pub use validator::validate_node_descriptor_str_with_options;
+pub use validator::validate_node_descriptor_str_with_trust_store;
+pub use validator::validate_node_descriptor_strict;
+pub use validator::validate_node_descriptor_with_options;
+pub use validator::validate_node_descriptor_with_trust_store;
+pub use validator::validate_revocation_list;
+pub use validator::validate_revocation_list_str;
+pub use validator::validate_revocation_list_str_with_options;
+pub use validator::validate_revocation_list_with_options;
+
+pub use signing::sign_json_with_backend;
+pub use signing::SigningBackend;
+pub use signing::SoftwareSigningBackend;
+
+pub use validator::cache::CertificateCache;
+pub use validator::lint::lint_document;
+pub use validator::lint::LintWarning;
+pub use validator::options::Clock;
+pub use validator::options::FixedClock;
+pub use validator::options::SystemClock;
+pub use validator::options::ValidationOptions;
+pub use validator::report::Finding;
+pub use validator::report::ValidationCheck;
+pub use validator::report::ValidationReport;
+pub use validator::revocation::RevocationLists;
+pub use validator::trust_store::TrustStore;
 
+pub use error::ChainPosition;
 pub use error::Error;
 pub use error::Result;
@@ -0,0 +1,246 @@
+use bip39::Mnemonic;
+use hmac::{Hmac, Mac};
+use rand::{rngs::OsRng, RngCore};
+use sha2::Sha512;
+
+use crate::{
+    cryptography::{public_key_from_private, Key, KeyPair},
+    Error, Result,
+};
+
+/// Number of words of a new mnemonic, 24 words encode 256 bits of entropy.
+pub const DEFAULT_WORD_COUNT: usize = 24;
+/// Derivation path of the master key, used when no other path is given.
+pub const DEFAULT_DERIVATION_PATH: &str = "m";
+
+const HARDENED_OFFSET: u32 = 0x8000_0000;
+// https://github.com/satoshilabs/slips/blob/master/slip-0010.md#master-key-generation
+const ED25519_CURVE_SEED: &[u8] = b"ed25519 seed";
+
+/// Key pair with the mnemonic it was derived from.
+pub struct MnemonicKeyPair {
+    pub mnemonic: String,
+    pub key_pair: KeyPair,
+}
+
+/// Creates an Ed25519 key pair derived from a new BIP39 mnemonic.
+/// The same key pair can be recovered from the mnemonic with [`recover_key_pair`].
+/// # Arguments
+/// * `word_count` number of words of the mnemonic: 12, 15, 18, 21 or 24
+/// * `passphrase` optional BIP39 passphrase, use an empty string for none
+/// * `derivation_path` SLIP-0010 derivation path with hardened indexes only, e.g. `m/0'/1'`
+pub fn create_key_pair_with_mnemonic(
+    word_count: usize,
+    passphrase: &str,
+    derivation_path: &str,
+) -> Result<MnemonicKeyPair> {
+    if ![12, 15, 18, 21, 24].contains(&word_count) {
+        return Err(Error::InvalidMnemonic(format!(
+            "word count has to be 12, 15, 18, 21 or 24, got {word_count}"
+        )));
+    }
+    // every 3 words encode 32 bits of entropy and 1 bit of checksum
+    let mut entropy = vec![0u8; word_count / 3 * 4];
+    OsRng.fill_bytes(&mut entropy);
+    let mnemonic =
+        Mnemonic::from_entropy(&entropy).map_err(|e| Error::InvalidMnemonic(e.to_string()))?;
+    let key_pair = derive_key_pair(&mnemonic, passphrase, derivation_path)?;
+    Ok(MnemonicKeyPair {
+        mnemonic: mnemonic.to_string(),
+        key_pair,
+    })
+}
+
+/// Recovers the Ed25519 key pair derived from a BIP39 mnemonic.
+/// # Arguments
+/// * `mnemonic` the english words of the mnemonic separated by whitespace
+/// * `passphrase` BIP39 passphrase used when the key pair was created
+/// * `derivation_path` SLIP-0010 derivation path used when the key pair was created
+pub fn recover_key_pair(
+    mnemonic: &str,
+    passphrase: &str,
+    derivation_path: &str,
+) -> Result<KeyPair> {
+    let mnemonic = Mnemonic::parse_normalized(&normalize_whitespace(mnemonic))
+        .map_err(|e| Error::InvalidMnemonic(e.to_string()))?;
+    derive_key_pair(&mnemonic, passphrase, derivation_path)
+}
+
+fn derive_key_pair(
+    mnemonic: &Mnemonic,
+    passphrase: &str,
+    derivation_path: &str,
+) -> Result<KeyPair> {
+    let indexes = parse_derivation_path(derivation_path)?;
+    let private_key: Key = derive_ed25519_key(&mnemonic.to_seed(passphrase), &indexes).into();
+    Ok(KeyPair {
+        public_key: public_key_from_private(&private_key)?,
+        private_key,
+    })
+}
+
+/// SLIP-0010 derivation of an Ed25519 private key, only hardened derivation is defined for Ed25519.
+fn derive_ed25519_key(seed: &[u8], indexes: &[u32]) -> [u8; 32] {
+    let (mut key, mut chain_code) = hmac_sha512(ED25519_CURVE_SEED, &[seed]);
+    for index in indexes {
+        (key, chain_code) = hmac_sha512(&chain_code, &[&[0], &key, &index.to_be_bytes()]);
+    }
+    key
+}
+
+fn hmac_sha512(key: &[u8], data: &[&[u8]]) -> ([u8; 32], [u8; 32]) {
+    let mut mac = Hmac::<Sha512>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    for chunk in data {
+        mac.update(chunk);
+    }
+    let result = mac.finalize().into_bytes();
+    let mut left = [0u8; 32];
+    let mut right = [0u8; 32];
+    left.copy_from_slice(&result[..32]);
+    right.copy_from_slice(&result[32..]);
+    (left, right)
+}
+
+fn parse_derivation_path(derivation_path: &str) -> Result<Vec<u32>> {
+    let invalid_path =
+        |reason: &str| Error::InvalidDerivationPath(format!("{derivation_path}: {reason}"));
+    let mut segments = derivation_path.trim().split('/');
+    if segments.next() != Some("m") {
+        return Err(invalid_path("has to start with m"));
+    }
+    segments
+        .map(|segment| {
+            let index = segment
+                .strip_suffix(['\'', 'h', 'H'])
+                .ok_or_else(|| invalid_path("Ed25519 keys support hardened derivation only"))?;
+            index
+                .parse::<u32>()
+                .ok()
+                .filter(|index| *index < HARDENED_OFFSET)
+                .map(|index| index + HARDENED_OFFSET)
+                .ok_or_else(|| invalid_path("invalid index"))
+        })
+        .collect()
+}
+
+fn normalize_whitespace(mnemonic: &str) -> String {
+    mnemonic
+        .split_whitespace()
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+
+    use test_case::test_case;
+
+    const MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    fn key_hex(key: &Key) -> String {
+        serde_json::to_value(key).unwrap()["key"]
+            .as_str()
+            .unwrap()
+            .to_string()
+    }
+
+    // https://github.com/satoshilabs/slips/blob/master/slip-0010.md#test-vector-1-for-ed25519
+    #[test_case(&[], "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7")]
+    #[test_case(&[HARDENED_OFFSET], "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3")]
+    #[test_case(&[HARDENED_OFFSET, HARDENED_OFFSET + 1], "b1d0bad404bf35da785a64ca1ac54b2617211d2777696fbffaf208f746ae84f2")]
+    fn derive_slip10_test_vector(indexes: &[u32], expected_key: &str) {
+        let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+
+        let key = derive_ed25519_key(&seed, indexes);
+
+        assert_eq!(hex::encode(key), expected_key);
+    }
+
+    #[test_case("m", &[])]
+    #[test_case("m/0'", &[HARDENED_OFFSET])]
+    #[test_case("m/44'/1h/2H", &[HARDENED_OFFSET + 44, HARDENED_OFFSET + 1, HARDENED_OFFSET + 2])]
+    fn parse_hardened_derivation_path(derivation_path: &str, expected: &[u32]) {
+        assert_eq!(parse_derivation_path(derivation_path).unwrap(), expected);
+    }
+
+    #[test_case("")]
+    #[test_case("0'/1'")]
+    #[test_case("m/0")]
+    #[test_case("m/x'")]
+    #[test_case("m/2147483648'")]
+    fn reject_invalid_derivation_path(derivation_path: &str) {
+        assert!(matches!(
+            parse_derivation_path(derivation_path).unwrap_err(),
+            Error::InvalidDerivationPath(_)
+        ));
+    }
+
+    #[test_case(12)]
+    #[test_case(24)]
+    fn recover_created_key_pair(word_count: usize) {
+        let created = create_key_pair_with_mnemonic(word_count, "passphrase", "m/0'").unwrap();
+
+        let recovered = recover_key_pair(&created.mnemonic, "passphrase", "m/0'").unwrap();
+
+        assert_eq!(created.mnemonic.split(' ').count(), word_count);
+        assert_eq!(recovered.private_key, created.key_pair.private_key);
+        assert_eq!(recovered.public_key, created.key_pair.public_key);
+    }
+
+    #[test]
+    fn derive_different_keys_for_different_passphrases_and_paths() {
+        let key_pair = recover_key_pair(MNEMONIC, "", DEFAULT_DERIVATION_PATH).unwrap();
+        let with_passphrase =
+            recover_key_pair(MNEMONIC, "TREZOR", DEFAULT_DERIVATION_PATH).unwrap();
+        let with_path = recover_key_pair(MNEMONIC, "", "m/0'").unwrap();
+
+        assert_ne!(key_pair.private_key, with_passphrase.private_key);
+        assert_ne!(key_pair.private_key, with_path.private_key);
+    }
+
+    #[test]
+    fn recover_from_words_with_extra_whitespace() {
+        let key_pair = recover_key_pair(MNEMONIC, "", DEFAULT_DERIVATION_PATH).unwrap();
+        let words = format!("  {}\n", MNEMONIC.to_uppercase().replace(' ', "\t "));
+
+        let recovered = recover_key_pair(&words, "", DEFAULT_DERIVATION_PATH).unwrap();
+
+        assert_eq!(recovered.private_key, key_pair.private_key);
+    }
+
+    #[test]
+    fn derive_master_key_from_bip39_seed() {
+        // BIP39 seed of the mnemonic with passphrase "TREZOR" is
+        // c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04
+        let seed = hex::decode("c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04").unwrap();
+
+        let key_pair = recover_key_pair(MNEMONIC, "TREZOR", DEFAULT_DERIVATION_PATH).unwrap();
+
+        assert_eq!(
+            key_hex(&key_pair.private_key),
+            hex::encode(derive_ed25519_key(&seed, &[]))
+        );
+    }
+
+    #[test_case("abandon abandon abandon")]
+    #[test_case("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon")]
+    #[test_case("golem golem golem golem golem golem golem golem golem golem golem golem")]
+    fn reject_invalid_mnemonic(mnemonic: &str) {
+        assert!(matches!(
+            recover_key_pair(mnemonic, "", DEFAULT_DERIVATION_PATH),
+            Err(Error::InvalidMnemonic(_))
+        ));
+    }
+
+    #[test_case(11)]
+    #[test_case(13)]
+    #[test_case(27)]
+    fn reject_invalid_word_count(word_count: usize) {
+        assert!(matches!(
+            create_key_pair_with_mnemonic(word_count, "", DEFAULT_DERIVATION_PATH),
+            Err(Error::InvalidMnemonic(_))
+        ));
+    }
+}
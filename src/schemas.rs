@@ -1,9 +1,14 @@
 pub mod certificate;
+pub mod manifest;
 pub mod node_descriptor;
 pub mod permissions;
+pub mod revocation_list;
 pub mod signature;
 pub mod subject;
 pub mod validity_period;
 
 pub use signature::SIGNED_CERTIFICATE_SCHEMA_ID;
+pub use signature::SIGNED_MANIFEST_SCHEMA_ID;
 pub use signature::SIGNED_NODE_DESCRIPTOR_SCHEMA_ID;
+pub use signature::SIGNED_NODE_DESCRIPTOR_V2_SCHEMA_ID;
+pub use signature::SIGNED_REVOCATION_LIST_SCHEMA_ID;
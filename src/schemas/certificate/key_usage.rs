@@ -12,15 +12,16 @@ pub mod validator;
 pub enum KeyUsage {
     #[serde(with = "serde_utils::all")]
     All,
-    Limited(HashSet<Usage>),
+    Limited(#[serde(serialize_with = "serde_utils::sorted_set")] HashSet<Usage>),
 }
 
-#[derive(Serialize, Deserialize, Eq, PartialEq, Hash, Clone, Debug)]
+#[derive(Serialize, Deserialize, Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
 pub enum Usage {
     SignCertificate,
     SignManifest,
     SignNode,
+    SignRevocationList,
 }
 
 #[cfg(test)]
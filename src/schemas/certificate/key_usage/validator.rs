@@ -38,6 +38,32 @@ pub fn validate_sign_node(key_usage: &KeyUsage) -> Result<(), Error> {
     }
 }
 
+pub fn validate_sign_manifest(key_usage: &KeyUsage) -> Result<(), Error> {
+    match key_usage {
+        KeyUsage::All => Ok(()),
+        KeyUsage::Limited(usages) => {
+            if usages.contains(&Usage::SignManifest) {
+                Ok(())
+            } else {
+                Err(Error::ManifestSignNotPermitted)
+            }
+        }
+    }
+}
+
+pub fn validate_sign_revocation_list(key_usage: &KeyUsage) -> Result<(), Error> {
+    match key_usage {
+        KeyUsage::All => Ok(()),
+        KeyUsage::Limited(usages) => {
+            if usages.contains(&Usage::SignRevocationList) {
+                Ok(())
+            } else {
+                Err(Error::RevocationListSignNotPermitted)
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -132,6 +158,66 @@ mod tests {
         }
     }
 
+    mod validate_sign_manifest_should {
+        use super::*;
+
+        use test_case::test_case;
+
+        #[test_case(&[Usage::SignManifest])]
+        #[test_case(&[Usage::SignManifest, Usage::SignNode])]
+        fn accept_because_cert_has_proper_usage(key_usage: &[Usage]) {
+            let key_usage = slice_to_usages(key_usage);
+
+            assert!(validate_sign_manifest(&key_usage).is_ok());
+        }
+
+        #[test]
+        fn accept_because_cert_has_all_usage() {
+            let key_usage = KeyUsage::All;
+
+            assert!(validate_sign_manifest(&key_usage).is_ok());
+        }
+
+        #[test_case(&[])]
+        #[test_case(&[Usage::SignNode])]
+        #[test_case(&[Usage::SignCertificate, Usage::SignRevocationList])]
+        fn reject_because_cert_has_no_proper_usage(key_usage: &[Usage]) {
+            let key_usage = slice_to_usages(key_usage);
+
+            assert!(validate_sign_manifest(&key_usage).is_err());
+        }
+    }
+
+    mod validate_sign_revocation_list_should {
+        use super::*;
+
+        use test_case::test_case;
+
+        #[test_case(&[Usage::SignRevocationList])]
+        #[test_case(&[Usage::SignRevocationList, Usage::SignCertificate])]
+        fn accept_because_cert_has_proper_usage(key_usage: &[Usage]) {
+            let key_usage = slice_to_usages(key_usage);
+
+            assert!(validate_sign_revocation_list(&key_usage).is_ok());
+        }
+
+        #[test]
+        fn accept_because_cert_has_all_usage() {
+            let key_usage = KeyUsage::All;
+
+            assert!(validate_sign_revocation_list(&key_usage).is_ok());
+        }
+
+        #[test_case(&[])]
+        #[test_case(&[Usage::SignNode])]
+        #[test_case(&[Usage::SignCertificate, Usage::SignManifest])]
+        fn reject_because_cert_has_no_proper_usage(key_usage: &[Usage]) {
+            let key_usage = slice_to_usages(key_usage);
+
+            assert!(validate_sign_revocation_list(&key_usage).is_err());
+        }
+    }
+
     mod utils {
         use super::*;
 
@@ -0,0 +1,148 @@
+use std::collections::HashSet;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use url::Url;
+
+use super::permissions::OutboundPermissions;
+use crate::Error;
+
+/// Subset of the Golem computation manifest relevant for certificate based permissions.
+/// https://github.com/golemfactory/golem-architecture/blob/master/gaps/gap-5_computation_manifest/gap-5_computation_manifest.md
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Manifest {
+    pub version: String,
+    pub created_at: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub comp_manifest: Option<CompManifest>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CompManifest {
+    pub net: Option<Net>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Net {
+    pub inet: Option<Inet>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Inet {
+    pub out: Option<OutboundAccess>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OutboundAccess {
+    #[serde(default)]
+    pub protocols: HashSet<String>,
+    pub urls: Option<HashSet<Url>>,
+    pub unrestricted: Option<UnrestrictedAccess>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct UnrestrictedAccess {
+    pub urls: bool,
+}
+
+impl Manifest {
+    /// Outbound access requested by the manifest expressed as certificate permissions.
+    pub fn outbound_permissions(&self) -> Option<OutboundPermissions> {
+        let out = self
+            .comp_manifest
+            .as_ref()?
+            .net
+            .as_ref()?
+            .inet
+            .as_ref()?
+            .out
+            .as_ref()?;
+        match (&out.unrestricted, &out.urls) {
+            (Some(UnrestrictedAccess { urls: true }), _) => Some(OutboundPermissions::Unrestricted),
+            (_, Some(urls)) => Some(OutboundPermissions::Urls(
+                urls.iter().cloned().map(Into::into).collect(),
+            )),
+            (_, None) => Some(OutboundPermissions::Urls(Default::default())),
+        }
+    }
+}
+
+/// Decodes base64 encoded manifest json.
+pub fn decode_manifest(manifest: &str) -> Result<Value, Error> {
+    let bytes = STANDARD
+        .decode(manifest)
+        .map_err(|e| Error::InvalidManifest(e.to_string()))?;
+    serde_json::from_slice(&bytes).map_err(|e| Error::InvalidManifest(e.to_string()))
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use test_case::test_case;
+
+    #[test]
+    fn return_no_outbound_permissions_when_manifest_has_no_net() {
+        let manifest: Manifest = serde_json::from_value(json!({
+            "version": "0.1.0",
+            "compManifest": {}
+        }))
+        .unwrap();
+
+        assert_eq!(manifest.outbound_permissions(), None);
+    }
+
+    #[test]
+    fn return_unrestricted_outbound_permissions() {
+        let manifest: Manifest = serde_json::from_value(json!({
+            "version": "0.1.0",
+            "compManifest": {
+                "net": { "inet": { "out": {
+                    "protocols": ["http", "https"],
+                    "unrestricted": { "urls": true }
+                }}}
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(
+            manifest.outbound_permissions(),
+            Some(OutboundPermissions::Unrestricted)
+        );
+    }
+
+    #[test_case(json!(["https://example.net/"]), &["https://example.net/"])]
+    #[test_case(json!([]), &[])]
+    fn return_outbound_url_permissions(urls: serde_json::Value, expected: &[&str]) {
+        let manifest: Manifest = serde_json::from_value(json!({
+            "version": "0.1.0",
+            "compManifest": {
+                "net": { "inet": { "out": {
+                    "protocols": ["https"],
+                    "urls": urls
+                }}}
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(
+            manifest.outbound_permissions(),
+            Some(OutboundPermissions::Urls(
+                expected
+                    .iter()
+                    .map(|url| Url::parse(url).unwrap().into())
+                    .collect()
+            ))
+        );
+    }
+}
@@ -1,12 +1,19 @@
-use std::collections::HashSet;
-
-use serde::{Deserialize, Serialize};
-use url::Url;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt;
+use std::marker::PhantomData;
 
 use crate::serde_utils;
+use serde::de::{self, DeserializeOwned, MapAccess, Visitor};
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use url::Url;
 
+pub mod url_pattern;
 pub mod validator;
 
+pub use url_pattern::UrlPattern;
+
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
 #[serde(rename_all = "camelCase")]
 #[serde(untagged)]
@@ -16,17 +23,187 @@ pub enum Permissions {
     Object(PermissionDetails),
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+/// Permissions by category, a missing category is not permitted.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, Eq, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct PermissionDetails {
     pub outbound: Option<OutboundPermissions>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inbound: Option<InboundPermissions>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub runtimes: Option<RuntimePermissions>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payment_platforms: Option<PaymentPlatformPermissions>,
+    /// permissions unknown to this version of the library, e.g. added by a newer schema version.
+    /// A child can only have them when the parent has the same json value.
+    #[serde(flatten)]
+    pub additional_properties: HashMap<String, Value>,
 }
 
+/// Outgoing network connections.
 #[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub enum OutboundPermissions {
     Unrestricted,
-    Urls(HashSet<Url>),
+    Urls(#[serde(serialize_with = "serde_utils::sorted_set")] HashSet<UrlPattern>),
+}
+
+/// Category granted without restriction or limited to the listed entries,
+/// serialized as `"unrestricted"` or as an object with the entries in the [`PermissionEntry::PROPERTY`] array.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum EntryPermissions<T> {
+    Unrestricted,
+    Entries(BTreeSet<T>),
+}
+
+/// Entry of an [`EntryPermissions`] list, a child entry is covered only by the same entry of the parent.
+pub trait PermissionEntry: Ord + Serialize + DeserializeOwned {
+    /// Name of the json property listing the entries
+    const PROPERTY: &'static str;
+}
+
+impl PermissionEntry for u16 {
+    const PROPERTY: &'static str = "ports";
+}
+
+impl PermissionEntry for String {
+    const PROPERTY: &'static str = "names";
+}
+
+/// Incoming network connections, limited to the listed ports.
+pub type InboundPermissions = EntryPermissions<u16>;
+
+/// Runtimes (payload types) that can be used, e.g. `vm` or `wasmtime`.
+pub type RuntimePermissions = EntryPermissions<String>;
+
+/// Payment platforms that can be used, e.g. `erc20-polygon-glm`.
+pub type PaymentPlatformPermissions = EntryPermissions<String>;
+
+const UNRESTRICTED: &str = "unrestricted";
+
+impl<T: PermissionEntry> Serialize for EntryPermissions<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            EntryPermissions::Unrestricted => serializer.serialize_str(UNRESTRICTED),
+            EntryPermissions::Entries(entries) => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry(T::PROPERTY, entries)?;
+                map.end()
+            }
+        }
+    }
+}
+
+impl<'de, T: PermissionEntry> Deserialize<'de> for EntryPermissions<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct EntryPermissionsVisitor<T>(PhantomData<T>);
+
+        impl<'de, T: PermissionEntry> Visitor<'de> for EntryPermissionsVisitor<T> {
+            type Value = EntryPermissions<T>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "\"{UNRESTRICTED}\" or an object with `{}`", T::PROPERTY)
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                if value == UNRESTRICTED {
+                    Ok(EntryPermissions::Unrestricted)
+                } else {
+                    Err(E::invalid_value(de::Unexpected::Str(value), &self))
+                }
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut entries = None;
+                while let Some(property) = map.next_key::<String>()? {
+                    if property != T::PROPERTY || entries.is_some() {
+                        return Err(de::Error::custom(format!(
+                            "unexpected property `{property}`, expected a single `{}`",
+                            T::PROPERTY
+                        )));
+                    }
+                    entries = Some(map.next_value()?);
+                }
+                entries
+                    .map(EntryPermissions::Entries)
+                    .ok_or_else(|| de::Error::missing_field(T::PROPERTY))
+            }
+        }
+
+        deserializer.deserialize_any(EntryPermissionsVisitor(PhantomData))
+    }
+}
+
+/// Category of permissions a child can extend beyond those of its parent.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum PermissionCategory {
+    /// Child requests all permissions while the parent does not have them
+    All,
+    Outbound,
+    Inbound,
+    Runtimes,
+    PaymentPlatforms,
+}
+
+impl fmt::Display for PermissionCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            PermissionCategory::All => "all",
+            PermissionCategory::Outbound => "outbound",
+            PermissionCategory::Inbound => "inbound",
+            PermissionCategory::Runtimes => "runtimes",
+            PermissionCategory::PaymentPlatforms => "paymentPlatforms",
+        })
+    }
+}
+
+/// Permission entry granting outbound access to a URL.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum OutboundGrant {
+    /// Every permission is granted
+    All,
+    /// Outbound access to any URL is granted
+    Unrestricted,
+    /// Entry of the outbound URLs matching the URL
+    Url(UrlPattern),
+}
+
+impl fmt::Display for OutboundGrant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OutboundGrant::All => f.write_str("all permissions are granted"),
+            OutboundGrant::Unrestricted => f.write_str("outbound access is unrestricted"),
+            OutboundGrant::Url(pattern) => write!(f, "outbound URL entry {pattern} matches"),
+        }
+    }
+}
+
+impl Permissions {
+    /// Checks whether outbound access to the URL is permitted.
+    pub fn allows_outbound(&self, url: &Url) -> bool {
+        self.outbound_grant(url).is_some()
+    }
+
+    /// Returns the permission entry granting outbound access to the URL or `None` when the access is not permitted.
+    /// When several URL entries match, an exact entry is preferred over a pattern.
+    /// # Arguments
+    /// * `url` URL the access is requested to, it is normalized before matching
+    pub fn outbound_grant(&self, url: &Url) -> Option<OutboundGrant> {
+        match self {
+            Permissions::All => Some(OutboundGrant::All),
+            Permissions::Object(PermissionDetails { outbound, .. }) => match outbound {
+                None => None,
+                Some(OutboundPermissions::Unrestricted) => Some(OutboundGrant::Unrestricted),
+                Some(OutboundPermissions::Urls(urls)) => {
+                    let url = UrlPattern::from(url.clone());
+                    urls.iter()
+                        .filter(|pattern| pattern.covers(&url))
+                        .min_by_key(|pattern| (!pattern.is_exact(), *pattern))
+                        .map(|pattern| OutboundGrant::Url(pattern.clone()))
+                }
+            },
+        }
+    }
 }
 
 #[cfg(test)]
@@ -35,6 +212,20 @@ mod should {
 
     use pretty_assertions::assert_eq;
     use serde_json::json;
+    use test_case::test_case;
+
+    fn outbound(outbound: Option<OutboundPermissions>) -> Permissions {
+        Permissions::Object(PermissionDetails {
+            outbound,
+            ..Default::default()
+        })
+    }
+
+    fn outbound_urls(urls: &[&str]) -> Permissions {
+        outbound(Some(OutboundPermissions::Urls(
+            urls.iter().map(|url| url.parse().unwrap()).collect(),
+        )))
+    }
 
     #[test]
     fn serialize_and_deserialize_all() {
@@ -52,6 +243,7 @@ mod should {
     fn serialize_and_deserialize_outbound_unrestricted() {
         let permissions = Permissions::Object(PermissionDetails {
             outbound: Some(OutboundPermissions::Unrestricted),
+            ..Default::default()
         });
         let json = json!({
             "outbound": "unrestricted"
@@ -68,8 +260,9 @@ mod should {
     fn serialize_outbound_urls() {
         let permissions = Permissions::Object(PermissionDetails {
             outbound: Some(OutboundPermissions::Urls(
-                [Url::parse("https://example.net/").unwrap()].into(),
+                [Url::parse("https://example.net/").unwrap().into()].into(),
             )),
+            ..Default::default()
         });
         let json = json!({
             "outbound": {
@@ -83,4 +276,95 @@ mod should {
             permissions
         );
     }
+
+    #[test_case(Permissions::All, Some(OutboundGrant::All))]
+    #[test_case(outbound(None), None)]
+    #[test_case(
+        outbound(Some(OutboundPermissions::Unrestricted)),
+        Some(OutboundGrant::Unrestricted)
+    )]
+    #[test_case(outbound_urls(&["https://example.com/*", "https://*.example.net/*", "https://api.example.net/v1/nodes/"]), Some(OutboundGrant::Url("https://api.example.net/v1/nodes".parse().unwrap())))]
+    #[test_case(outbound_urls(&["https://*.example.net/*", "https://api.example.net/v2"]), Some(OutboundGrant::Url("https://*.example.net/*".parse().unwrap())))]
+    #[test_case(outbound_urls(&["https://example.net/v1/nodes", "http://api.example.net/v1/nodes"]), None)]
+    fn explain_outbound_grant(permissions: Permissions, expected: Option<OutboundGrant>) {
+        let url = Url::parse("https://API.example.net:443/v1/nodes").unwrap();
+
+        assert_eq!(permissions.outbound_grant(&url), expected);
+        assert_eq!(permissions.allows_outbound(&url), expected.is_some());
+    }
+
+    #[test]
+    fn serialize_and_deserialize_permission_categories() {
+        let permissions = Permissions::Object(PermissionDetails {
+            outbound: None,
+            inbound: Some(EntryPermissions::Entries([8080, 443].into())),
+            runtimes: Some(EntryPermissions::Entries(
+                ["wasmtime".to_string(), "vm".to_string()].into(),
+            )),
+            payment_platforms: Some(EntryPermissions::Unrestricted),
+            ..Default::default()
+        });
+        let json = json!({
+            "outbound": null,
+            "inbound": { "ports": [443, 8080] },
+            "runtimes": { "names": ["vm", "wasmtime"] },
+            "paymentPlatforms": "unrestricted"
+        });
+
+        assert_eq!(serde_json::to_value(&permissions).unwrap(), json);
+        assert_eq!(
+            serde_json::from_value::<Permissions>(json).unwrap(),
+            permissions
+        );
+    }
+
+    #[test_case(json!("all"))]
+    #[test_case(json!({}))]
+    #[test_case(json!({ "names": [443] }))]
+    #[test_case(json!({ "ports": [443], "names": [] }))]
+    #[test_case(json!({ "ports": [-1] }))]
+    fn reject_invalid_entry_permissions(json: Value) {
+        assert!(serde_json::from_value::<InboundPermissions>(json).is_err());
+    }
+
+    #[test]
+    fn preserve_unknown_permissions() {
+        let json = json!({
+            "outbound": "unrestricted",
+            "storage": { "maxGb": 10 }
+        });
+
+        let permissions = serde_json::from_value::<Permissions>(json.clone()).unwrap();
+
+        match &permissions {
+            Permissions::Object(details) => assert_eq!(
+                details.additional_properties,
+                [("storage".to_string(), json!({ "maxGb": 10 }))].into()
+            ),
+            Permissions::All => panic!("expected permission details"),
+        }
+        assert_eq!(serde_json::to_value(&permissions).unwrap(), json);
+    }
+
+    #[test]
+    fn serialize_outbound_urls_in_sorted_order() {
+        let permissions = Permissions::Object(PermissionDetails {
+            outbound: Some(OutboundPermissions::Urls(
+                [
+                    Url::parse("https://example.org/").unwrap().into(),
+                    Url::parse("https://example.com/").unwrap().into(),
+                    Url::parse("https://example.net/").unwrap().into(),
+                ]
+                .into(),
+            )),
+            ..Default::default()
+        });
+        let json = json!({
+            "outbound": {
+                "urls": ["https://example.com/", "https://example.net/", "https://example.org/"]
+            }
+        });
+
+        assert_eq!(serde_json::to_value(&permissions).unwrap(), json);
+    }
 }
@@ -0,0 +1,552 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use url::{Position, Url};
+
+use crate::Error;
+
+const WILDCARD: &str = "*";
+const SUBDOMAIN_WILDCARD: &str = "*.";
+// only used to normalize the parts of a pattern with a wildcard scheme or host
+const PLACEHOLDER_SCHEME: &str = "https";
+const PLACEHOLDER_HOST: &str = "placeholder.invalid";
+
+/// Entry of the outbound URL permissions.
+/// A plain URL permits exactly that URL. Wildcards turn the entry into a pattern:
+/// * `*://` any scheme
+/// * `*` any host, `*.example.com` any subdomain of example.com
+/// * `:*` any port, without a port only the default port of the scheme is permitted
+/// * a path ending with `*` permits every path with that prefix, e.g. `https://example.com/api/*`
+///
+/// Entries are compared in their normalized form: lowercase scheme and host, IDNA host names,
+/// no default port, uppercase percent-encoding without encoded unreserved characters,
+/// no trailing slash after a non-empty path and no fragment.
+/// The entry is serialized as written, so signed documents are not altered.
+#[derive(Debug, Clone)]
+pub struct UrlPattern {
+    source: String,
+    normalized: String,
+    scheme: Option<String>,
+    host: HostPattern,
+    port: PortPattern,
+    path: PathPattern,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum HostPattern {
+    Any,
+    Subdomains(String),
+    Exact(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PortPattern {
+    Any,
+    Default,
+    Exact(u16),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PathPattern {
+    Prefix(String),
+    Exact(String),
+}
+
+impl UrlPattern {
+    /// Checks whether every URL permitted by `other` is permitted by this entry as well.
+    pub fn covers(&self, other: &UrlPattern) -> bool {
+        if self.is_exact() {
+            return other.is_exact() && self.normalized == other.normalized;
+        }
+        self.covers_scheme(other)
+            && self.host.covers(&other.host)
+            && self.covers_port(other)
+            && self.path.covers(&other.path)
+    }
+
+    /// Checks whether the URL is permitted by this entry, the URL is normalized before the check.
+    pub fn matches(&self, url: &Url) -> bool {
+        self.covers(&url.clone().into())
+    }
+
+    /// Entry without wildcards, permitting a single URL.
+    pub fn is_exact(&self) -> bool {
+        self.scheme.is_some()
+            && matches!(self.host, HostPattern::Exact(_))
+            && self.port != PortPattern::Any
+            && matches!(self.path, PathPattern::Exact(_))
+    }
+
+    /// Checks whether the entry is written in its normalized form, see [`UrlPattern`].
+    pub fn is_normalized(&self) -> bool {
+        self.source == self.normalized
+    }
+
+    /// Entry as written in the document.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    fn covers_scheme(&self, other: &UrlPattern) -> bool {
+        match (&self.scheme, &other.scheme) {
+            (None, _) => true,
+            (Some(_), None) => false,
+            (Some(scheme), Some(other_scheme)) => scheme == other_scheme,
+        }
+    }
+
+    fn covers_port(&self, other: &UrlPattern) -> bool {
+        match (&self.port, &other.port) {
+            (PortPattern::Any, _) => true,
+            (_, PortPattern::Any) => false,
+            (PortPattern::Default, PortPattern::Default) => true,
+            (PortPattern::Default, PortPattern::Exact(port)) => other.default_port() == Some(*port),
+            (PortPattern::Exact(port), PortPattern::Exact(other_port)) => port == other_port,
+            (PortPattern::Exact(port), PortPattern::Default) => other.default_port() == Some(*port),
+        }
+    }
+
+    fn default_port(&self) -> Option<u16> {
+        self.scheme.as_deref().and_then(default_port)
+    }
+}
+
+impl HostPattern {
+    fn covers(&self, other: &HostPattern) -> bool {
+        match (self, other) {
+            (HostPattern::Any, _) => true,
+            (_, HostPattern::Any) => false,
+            (HostPattern::Subdomains(domain), HostPattern::Subdomains(other_domain)) => {
+                is_same_or_subdomain(other_domain, domain)
+            }
+            (HostPattern::Subdomains(domain), HostPattern::Exact(host)) => {
+                is_subdomain(host, domain)
+            }
+            (HostPattern::Exact(_), HostPattern::Subdomains(_)) => false,
+            (HostPattern::Exact(host), HostPattern::Exact(other_host)) => host == other_host,
+        }
+    }
+}
+
+impl PathPattern {
+    fn covers(&self, other: &PathPattern) -> bool {
+        match (self, other) {
+            (PathPattern::Prefix(prefix), PathPattern::Prefix(other)) => {
+                other.starts_with(prefix.as_str())
+            }
+            // trailing slash is removed from exact paths, `/api/*` covers `/api/` normalized to `/api`
+            (PathPattern::Prefix(prefix), PathPattern::Exact(other)) => {
+                other.starts_with(prefix.as_str()) || prefix.strip_suffix('/') == Some(other)
+            }
+            (PathPattern::Exact(_), PathPattern::Prefix(_)) => false,
+            (PathPattern::Exact(path), PathPattern::Exact(other)) => path == other,
+        }
+    }
+}
+
+/// Default ports of the special schemes, the url crate omits these from URLs.
+fn default_port(scheme: &str) -> Option<u16> {
+    match scheme {
+        "http" | "ws" => Some(80),
+        "https" | "wss" => Some(443),
+        "ftp" => Some(21),
+        _ => None,
+    }
+}
+
+/// Normalizes percent-encoding and removes the trailing slash and an empty query.
+/// The trailing slash of a prefix is significant, so it is kept.
+fn normalize_path(path_and_query: &str, is_prefix: bool) -> String {
+    let (path, query) = match path_and_query.split_once('?') {
+        Some((path, query)) => (path, Some(query)),
+        None => (path_and_query, None),
+    };
+    let mut path = normalize_percent_encoding(path);
+    if !is_prefix && path.len() > 1 && path.ends_with('/') {
+        path.truncate(path.trim_end_matches('/').len().max(1));
+    }
+    match query {
+        Some(query) if is_prefix || !query.is_empty() => {
+            format!("{path}?{}", normalize_percent_encoding(query))
+        }
+        _ => path,
+    }
+}
+
+/// Decodes percent-encoded unreserved characters and uppercases the hex digits of the others (RFC 3986 6.2.2).
+fn normalize_percent_encoding(input: &str) -> String {
+    let mut normalized = String::with_capacity(input.len());
+    let mut rest = input;
+    while let Some(index) = rest.find('%') {
+        normalized.push_str(&rest[..index]);
+        let encoded = rest
+            .get(index + 1..index + 3)
+            .filter(|hex| hex.bytes().all(|byte| byte.is_ascii_hexdigit()))
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match encoded {
+            Some(byte) if is_unreserved(byte) => normalized.push(byte as char),
+            Some(byte) => normalized.push_str(&format!("%{byte:02X}")),
+            None => {
+                normalized.push('%');
+                rest = &rest[index + 1..];
+                continue;
+            }
+        }
+        rest = &rest[index + 3..];
+    }
+    normalized.push_str(rest);
+    normalized
+}
+
+fn is_unreserved(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~')
+}
+
+fn is_subdomain(host: &str, domain: &str) -> bool {
+    host.strip_suffix(domain)
+        .map(|subdomain| subdomain.len() > 1 && subdomain.ends_with('.'))
+        .unwrap_or(false)
+}
+
+fn is_same_or_subdomain(host: &str, domain: &str) -> bool {
+    host == domain || is_subdomain(host, domain)
+}
+
+/// The url crate already lowercases the scheme and host, converts host names with IDNA and removes the default port.
+impl From<Url> for UrlPattern {
+    fn from(url: Url) -> Self {
+        let path = normalize_path(&url[Position::BeforePath..Position::AfterQuery], false);
+        Self {
+            scheme: Some(url.scheme().to_string()),
+            host: HostPattern::Exact(url.host_str().unwrap_or_default().to_string()),
+            port: url.port().map_or(PortPattern::Default, PortPattern::Exact),
+            normalized: format!("{}{path}", &url[..Position::BeforePath]),
+            path: PathPattern::Exact(path),
+            source: url.into(),
+        }
+    }
+}
+
+impl FromStr for UrlPattern {
+    type Err = Error;
+
+    fn from_str(pattern: &str) -> Result<Self, Self::Err> {
+        let invalid_pattern =
+            |reason: &str| Error::InvalidUrlPattern(format!("{pattern}: {reason}"));
+        if !pattern.contains(WILDCARD) {
+            return Url::parse(pattern)
+                .map(|url| Self {
+                    source: pattern.to_string(),
+                    ..url.into()
+                })
+                .map_err(|e| invalid_pattern(&e.to_string()));
+        }
+
+        let (scheme, rest) = pattern
+            .split_once("://")
+            .ok_or_else(|| invalid_pattern("missing scheme"))?;
+        let (authority, path) = rest
+            .find('/')
+            .map(|index| rest.split_at(index))
+            .unwrap_or((rest, "/"));
+        let (host, port) = split_port(authority);
+
+        let port = match port {
+            None => PortPattern::Default,
+            Some(WILDCARD) => PortPattern::Any,
+            Some(port) => port
+                .parse()
+                .map(PortPattern::Exact)
+                .map_err(|_| invalid_pattern("invalid port"))?,
+        };
+        let (path, is_prefix) = match path.strip_suffix(WILDCARD) {
+            Some(prefix) => (prefix, true),
+            None => (path, false),
+        };
+        let host_without_wildcard = if host == WILDCARD {
+            PLACEHOLDER_HOST
+        } else {
+            host.strip_prefix(SUBDOMAIN_WILDCARD).unwrap_or(host)
+        };
+        if (scheme != WILDCARD && scheme.contains(WILDCARD))
+            || host_without_wildcard.contains(WILDCARD)
+            || path.contains(WILDCARD)
+        {
+            return Err(invalid_pattern(
+                "wildcards are only allowed as the scheme, the leftmost label of the host, the port or the end of the path",
+            ));
+        }
+
+        // the url crate normalizes the scheme, host and path like in plain URLs
+        let probe_scheme = if scheme == WILDCARD {
+            PLACEHOLDER_SCHEME
+        } else {
+            scheme
+        };
+        let probe = Url::parse(&format!("{probe_scheme}://{host_without_wildcard}{path}"))
+            .map_err(|e| invalid_pattern(&e.to_string()))?;
+        let normalized_host = probe
+            .host_str()
+            .ok_or_else(|| invalid_pattern("missing host"))?
+            .to_string();
+        let normalized_path = normalize_path(&probe[Position::BeforePath..], is_prefix);
+
+        let scheme = (scheme != WILDCARD).then(|| probe.scheme().to_string());
+        let port = match port {
+            PortPattern::Exact(port) if scheme.as_deref().and_then(default_port) == Some(port) => {
+                PortPattern::Default
+            }
+            port => port,
+        };
+        let host = if host == WILDCARD {
+            HostPattern::Any
+        } else if host.starts_with(SUBDOMAIN_WILDCARD) {
+            HostPattern::Subdomains(normalized_host)
+        } else {
+            HostPattern::Exact(normalized_host)
+        };
+        let path = if is_prefix {
+            PathPattern::Prefix(normalized_path)
+        } else {
+            PathPattern::Exact(normalized_path)
+        };
+        let normalized = format_pattern(&scheme, &host, &port, &path);
+        Ok(Self {
+            source: pattern.to_string(),
+            normalized,
+            scheme,
+            host,
+            port,
+            path,
+        })
+    }
+}
+
+/// Splits the port from the authority, IPv6 hosts are enclosed in brackets.
+fn split_port(authority: &str) -> (&str, Option<&str>) {
+    let host_end = if authority.starts_with('[') {
+        authority.find(']').map(|index| index + 1).unwrap_or(0)
+    } else {
+        0
+    };
+    match authority[host_end..].rfind(':') {
+        Some(index) => (
+            &authority[..host_end + index],
+            Some(&authority[host_end + index + 1..]),
+        ),
+        None => (authority, None),
+    }
+}
+
+fn format_pattern(
+    scheme: &Option<String>,
+    host: &HostPattern,
+    port: &PortPattern,
+    path: &PathPattern,
+) -> String {
+    let scheme = scheme.as_deref().unwrap_or(WILDCARD);
+    let host = match host {
+        HostPattern::Any => WILDCARD.to_string(),
+        HostPattern::Subdomains(domain) => format!("{SUBDOMAIN_WILDCARD}{domain}"),
+        HostPattern::Exact(host) => host.clone(),
+    };
+    let port = match port {
+        PortPattern::Any => format!(":{WILDCARD}"),
+        PortPattern::Default => String::new(),
+        PortPattern::Exact(port) => format!(":{port}"),
+    };
+    let path = match path {
+        PathPattern::Prefix(prefix) => format!("{prefix}{WILDCARD}"),
+        PathPattern::Exact(path) => path.clone(),
+    };
+    format!("{scheme}://{host}{port}{path}")
+}
+
+/// Displays the normalized form of the entry.
+impl fmt::Display for UrlPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.normalized)
+    }
+}
+
+impl PartialEq for UrlPattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.normalized == other.normalized
+    }
+}
+
+impl Eq for UrlPattern {}
+
+impl Hash for UrlPattern {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.normalized.hash(state)
+    }
+}
+
+impl PartialOrd for UrlPattern {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for UrlPattern {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.normalized.cmp(&other.normalized)
+    }
+}
+
+impl Serialize for UrlPattern {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.source)
+    }
+}
+
+impl<'de> Deserialize<'de> for UrlPattern {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let pattern = String::deserialize(deserializer)?;
+        pattern.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+
+    use test_case::test_case;
+
+    fn pattern(pattern: &str) -> UrlPattern {
+        pattern.parse().unwrap()
+    }
+
+    #[test_case("https://example.com", "https://example.com/")]
+    #[test_case("https://*.Example.COM/api/*", "https://*.example.com/api/*")]
+    #[test_case("*://example.com:*/*", "*://example.com:*/*")]
+    #[test_case("https://*:8443", "https://*:8443/")]
+    #[test_case("https://*.bücher.example/*", "https://*.xn--bcher-kva.example/*")]
+    #[test_case("HTTPS://Example.NET:443/api/", "https://example.net/api")]
+    #[test_case("https://bücher.example/", "https://xn--bcher-kva.example/")]
+    #[test_case(
+        "https://example.net/%7euser/a%2fb?q=%7E%3d#fragment",
+        "https://example.net/~user/a%2Fb?q=~%3D"
+    )]
+    #[test_case("https://example.net/api?", "https://example.net/api")]
+    #[test_case("https://example.net/%zz%", "https://example.net/%zz%")]
+    #[test_case("https://*.example.net/%7eapi/*", "https://*.example.net/~api/*")]
+    #[test_case("https://example.net:443/*", "https://example.net/*")]
+    #[test_case("*://example.net:443/*", "*://example.net:443/*")]
+    #[test_case("mailto:Admin%40example.net", "mailto:Admin%40example.net")]
+    fn parse_and_normalize(input: &str, expected: &str) {
+        assert_eq!(pattern(input).to_string(), expected);
+    }
+
+    #[test_case("https://api.*.com/")]
+    #[test_case("https://example.com/*/api")]
+    #[test_case("ht*ps://example.com/")]
+    #[test_case("example.com/*")]
+    #[test_case("https://example.com:port*/")]
+    #[test_case("not a url")]
+    fn reject_invalid_pattern(input: &str) {
+        assert!(matches!(
+            input.parse::<UrlPattern>(),
+            Err(Error::InvalidUrlPattern(_))
+        ));
+    }
+
+    #[test_case("https://example.com/", "https://example.com/")]
+    #[test_case("https://example.com/*", "https://example.com/")]
+    #[test_case("https://example.com/*", "https://example.com/any/path?query=1")]
+    #[test_case("https://example.com/api/*", "https://example.com/api/v1/*")]
+    #[test_case("https://*.example.com/*", "https://api.example.com/v1")]
+    #[test_case("https://*.example.com/*", "https://a.b.example.com/*")]
+    #[test_case("https://*.example.com/*", "https://*.api.example.com/*")]
+    #[test_case("https://*/*", "https://*.example.com/*")]
+    #[test_case("*://example.com/", "http://example.com/")]
+    #[test_case("https://example.com:*/", "https://example.com:8443/")]
+    #[test_case("https://example.com:443/*", "https://example.com/")]
+    #[test_case("*://example.com:443/", "https://example.com/")]
+    #[test_case("https://Example.net", "https://example.net:443/")]
+    #[test_case("https://example.net/api", "https://example.net/api/")]
+    #[test_case("https://example.net/~user", "https://example.net/%7Euser")]
+    #[test_case("https://example.net/api/*", "https://example.net/api")]
+    #[test_case("https://example.net/api/*", "https://EXAMPLE.net/api/v1/")]
+    fn cover_child_entry(parent: &str, child: &str) {
+        assert!(pattern(parent).covers(&pattern(child)));
+    }
+
+    #[test_case("https://example.com/", "https://example.com/*")]
+    #[test_case("https://example.com/", "https://example.com/other")]
+    #[test_case("https://example.com/api/*", "https://example.com/")]
+    #[test_case("https://example.com/api/*", "https://example.com/*")]
+    #[test_case("https://*.example.com/*", "https://example.com/")]
+    #[test_case("https://*.example.com/*", "https://badexample.com/")]
+    #[test_case("https://*.example.com/*", "https://*/*")]
+    #[test_case("https://example.com/*", "http://example.com/")]
+    #[test_case("https://example.com/*", "*://example.com/")]
+    #[test_case("https://example.com/*", "https://example.com:8443/")]
+    #[test_case("https://example.com:8443/*", "https://example.com:*/")]
+    #[test_case("*://example.com:443/", "http://example.com/")]
+    #[test_case("https://example.net/api/*", "https://example.net/ap")]
+    #[test_case("https://example.net/a%2Fb", "https://example.net/a/b")]
+    fn not_cover_broader_entry(parent: &str, child: &str) {
+        assert!(!pattern(parent).covers(&pattern(child)));
+    }
+
+    #[test]
+    fn match_url() {
+        let url = Url::parse("https://api.example.com/v1/nodes").unwrap();
+
+        assert!(pattern("https://*.example.com/v1/*").matches(&url));
+        assert!(!pattern("https://*.example.com/v2/*").matches(&url));
+    }
+
+    #[test]
+    fn match_url_after_normalization() {
+        let url = Url::parse("https://Example.net:443/api/%7Euser/").unwrap();
+
+        assert!(pattern("https://example.net/api/~user").matches(&url));
+        assert!(pattern("https://example.net/api/*").matches(&url));
+    }
+
+    #[test_case("https://example.net/", true)]
+    #[test_case("https://*.example.net/api/*", true)]
+    #[test_case("https://example.net", false)]
+    #[test_case("https://Example.net/", false)]
+    #[test_case("https://example.net/api/", false)]
+    #[test_case("https://example.net/%7e", false)]
+    fn detect_non_normalized_entry(input: &str, expected: bool) {
+        assert_eq!(pattern(input).is_normalized(), expected);
+    }
+
+    #[test]
+    fn serialize_entry_as_written() {
+        let entry = pattern("https://Example.net");
+
+        assert_eq!(
+            serde_json::to_value(&entry).unwrap(),
+            serde_json::json!("https://Example.net")
+        );
+        assert_eq!(entry, pattern("https://example.net/"));
+    }
+
+    #[test]
+    fn serialize_and_deserialize_as_string() {
+        let patterns = [
+            pattern("https://*.example.com/*"),
+            pattern("https://example.com/"),
+        ];
+
+        let json = serde_json::to_value(&patterns).unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!(["https://*.example.com/*", "https://example.com/"])
+        );
+        assert_eq!(
+            serde_json::from_value::<Vec<UrlPattern>>(json).unwrap(),
+            patterns
+        );
+    }
+}
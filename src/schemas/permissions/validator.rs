@@ -1,6 +1,9 @@
-use super::Permissions;
+use super::{OutboundPermissions, PermissionCategory, PermissionDetails, Permissions};
 use crate::Error;
 
+mod entries;
+use entries::validate_entry_permissions;
+
 mod outbound;
 use outbound::validate_outbound_permissions;
 
@@ -8,59 +11,210 @@ pub fn validate_permissions(parent: &Permissions, child: &Permissions) -> Result
     match (parent, child) {
         (Permissions::All, _) => Ok(()),
         (Permissions::Object { .. }, Permissions::All) => Err(Error::PermissionsExtended {
-            parent: parent.to_owned(),
-            child: child.to_owned(),
+            category: PermissionCategory::All,
         }),
         (Permissions::Object(parent_details), Permissions::Object(child_details)) => {
+            let extended = |category| Error::PermissionsExtended { category };
             validate_outbound_permissions(&parent_details.outbound, &child_details.outbound)
-                .map_err(|_| Error::PermissionsExtended {
-                    parent: parent.to_owned(),
-                    child: child.to_owned(),
-                })
+                .map_err(|_| extended(PermissionCategory::Outbound))?;
+            validate_entry_permissions(&parent_details.inbound, &child_details.inbound)
+                .map_err(|_| extended(PermissionCategory::Inbound))?;
+            validate_entry_permissions(&parent_details.runtimes, &child_details.runtimes)
+                .map_err(|_| extended(PermissionCategory::Runtimes))?;
+            validate_entry_permissions(
+                &parent_details.payment_platforms,
+                &child_details.payment_platforms,
+            )
+            .map_err(|_| extended(PermissionCategory::PaymentPlatforms))?;
+            validate_unknown_permissions(parent_details, child_details)
         }
     }
 }
 
+/// Unknown permissions cannot be compared by their meaning, so the child fails closed
+/// unless the parent has exactly the same json value.
+fn validate_unknown_permissions(
+    parent: &PermissionDetails,
+    child: &PermissionDetails,
+) -> Result<(), Error> {
+    let mut properties: Vec<_> = child.additional_properties.iter().collect();
+    properties.sort_by_key(|(property, _)| *property);
+    match properties
+        .into_iter()
+        .find(|(property, value)| parent.additional_properties.get(*property) != Some(value))
+    {
+        Some((property, _)) => Err(Error::UnknownPermissionExtended {
+            property: property.to_owned(),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Validates that the outbound access requested by a manifest is covered by the certificate permissions.
+pub fn validate_requested_outbound_permissions(
+    permissions: &Permissions,
+    requested: &Option<OutboundPermissions>,
+) -> Result<(), Error> {
+    let requested_permissions = Permissions::Object(PermissionDetails {
+        outbound: requested.to_owned(),
+        ..Default::default()
+    });
+    validate_permissions(permissions, &requested_permissions)
+}
+
 #[cfg(test)]
 mod should {
     use super::*;
 
-    use super::super::{OutboundPermissions, PermissionDetails};
-
+    use serde_json::{json, Value};
     use test_case::test_case;
     use url::Url;
 
+    fn with_unknown_permissions(properties: Value) -> Permissions {
+        let mut json = json!({ "outbound": "unrestricted" });
+        json.as_object_mut()
+            .unwrap()
+            .extend(properties.as_object().unwrap().clone());
+        serde_json::from_value(json).unwrap()
+    }
+
+    use crate::schemas::permissions::EntryPermissions;
+
     #[test_case(Permissions::All)]
-    #[test_case(Permissions::Object(PermissionDetails {outbound: Some(OutboundPermissions::Unrestricted)}))]
-    #[test_case(Permissions::Object(PermissionDetails { outbound: Some(OutboundPermissions::Urls([Url::parse("https://1.net").unwrap()].into()))}))]
+    #[test_case(Permissions::Object(PermissionDetails {outbound: Some(OutboundPermissions::Unrestricted), ..Default::default() }))]
+    #[test_case(Permissions::Object(PermissionDetails { outbound: Some(OutboundPermissions::Urls([Url::parse("https://1.net").unwrap().into()].into())), ..Default::default() }))]
     fn accept_because_parent_has_all_permissions(child: Permissions) {
         let parent = Permissions::All;
 
         assert!(validate_permissions(&parent, &child).is_ok());
     }
 
-    #[test_case(Permissions::Object(PermissionDetails {outbound: Some(OutboundPermissions::Unrestricted)}))]
-    #[test_case(Permissions::Object(PermissionDetails { outbound: Some(OutboundPermissions::Urls([Url::parse("https://1.net").unwrap()].into()))}))]
+    #[test_case(Permissions::Object(PermissionDetails {outbound: Some(OutboundPermissions::Unrestricted), ..Default::default() }))]
+    #[test_case(Permissions::Object(PermissionDetails { outbound: Some(OutboundPermissions::Urls([Url::parse("https://1.net").unwrap().into()].into())), ..Default::default() }))]
     fn reject_because_child_requests_all_permissions_and_parent_does_not_have_one(
         parent: Permissions,
     ) {
         let child = Permissions::All;
 
-        assert!(validate_permissions(&parent, &child).is_err());
+        assert_eq!(
+            validate_permissions(&parent, &child).unwrap_err(),
+            Error::PermissionsExtended {
+                category: PermissionCategory::All
+            }
+        );
     }
 
     #[test]
     fn accept_valid_outbound_permissions() {
         let parent = Permissions::Object(PermissionDetails {
             outbound: Some(OutboundPermissions::Unrestricted),
+            ..Default::default()
         });
 
         let child = Permissions::Object(PermissionDetails {
             outbound: Some(OutboundPermissions::Urls(
-                [Url::parse("https://1.net").unwrap()].into(),
+                [Url::parse("https://1.net").unwrap().into()].into(),
             )),
+            ..Default::default()
+        });
+
+        assert!(validate_permissions(&parent, &child).is_ok());
+    }
+
+    #[test]
+    fn reject_child_extending_any_permission_category() {
+        let parent = Permissions::Object(PermissionDetails {
+            outbound: Some(OutboundPermissions::Unrestricted),
+            inbound: Some(EntryPermissions::Entries([443].into())),
+            runtimes: Some(EntryPermissions::Entries(["vm".to_string()].into())),
+            payment_platforms: Some(EntryPermissions::Unrestricted),
+            ..Default::default()
+        });
+        let child = |details: PermissionDetails| {
+            Permissions::Object(PermissionDetails {
+                outbound: Some(OutboundPermissions::Unrestricted),
+                ..details
+            })
+        };
+
+        let within_parent = child(PermissionDetails {
+            inbound: Some(EntryPermissions::Entries([443].into())),
+            runtimes: Some(EntryPermissions::Entries(["vm".to_string()].into())),
+            ..Default::default()
+        });
+        let extended_inbound = child(PermissionDetails {
+            inbound: Some(EntryPermissions::Unrestricted),
+            ..Default::default()
+        });
+        let extended_runtimes = child(PermissionDetails {
+            runtimes: Some(EntryPermissions::Entries(["wasmtime".to_string()].into())),
+            ..Default::default()
         });
+        let extended_payment_platforms = Permissions::Object(PermissionDetails {
+            payment_platforms: Some(EntryPermissions::Unrestricted),
+            ..Default::default()
+        });
+
+        let extended = |category| Error::PermissionsExtended { category };
+        assert!(validate_permissions(&parent, &within_parent).is_ok());
+        assert_eq!(
+            validate_permissions(&parent, &extended_inbound).unwrap_err(),
+            extended(PermissionCategory::Inbound)
+        );
+        assert_eq!(
+            validate_permissions(&parent, &extended_runtimes).unwrap_err(),
+            extended(PermissionCategory::Runtimes)
+        );
+        assert!(validate_permissions(&parent, &extended_payment_platforms).is_ok());
+        assert_eq!(
+            validate_permissions(&extended_payment_platforms, &parent).unwrap_err(),
+            extended(PermissionCategory::Outbound)
+        );
+    }
+
+    #[test_case(json!({}), json!({}))]
+    #[test_case(json!({"storage": {"maxGb": 10}}), json!({}))]
+    #[test_case(json!({"storage": {"maxGb": 10}, "gpu": true}), json!({"storage": {"maxGb": 10}}))]
+    fn accept_unknown_permissions_equal_in_parent(parent: Value, child: Value) {
+        let parent = with_unknown_permissions(parent);
+        let child = with_unknown_permissions(child);
 
         assert!(validate_permissions(&parent, &child).is_ok());
+        assert!(validate_permissions(&Permissions::All, &child).is_ok());
+    }
+
+    #[test_case(json!({}), json!({"storage": {"maxGb": 10}}), "storage")]
+    #[test_case(json!({"storage": {"maxGb": 10}}), json!({"storage": {"maxGb": 20}}), "storage")]
+    #[test_case(json!({"storage": {"maxGb": 10}}), json!({"storage": {"maxGb": 10}, "gpu": true}), "gpu")]
+    fn reject_unknown_permissions_not_equal_in_parent(parent: Value, child: Value, property: &str) {
+        let parent = with_unknown_permissions(parent);
+        let child = with_unknown_permissions(child);
+
+        assert_eq!(
+            validate_permissions(&parent, &child).unwrap_err(),
+            Error::UnknownPermissionExtended {
+                property: property.into()
+            }
+        );
+    }
+
+    #[test_case(Permissions::All, Some(OutboundPermissions::Unrestricted))]
+    #[test_case(Permissions::Object(PermissionDetails { outbound: Some(OutboundPermissions::Urls([Url::parse("https://1.net").unwrap().into()].into())), ..Default::default() }), Some(OutboundPermissions::Urls([Url::parse("https://1.net").unwrap().into()].into())))]
+    #[test_case(Permissions::Object(PermissionDetails { outbound: None, ..Default::default() }), None)]
+    fn accept_outbound_access_requested_by_manifest(
+        permissions: Permissions,
+        requested: Option<OutboundPermissions>,
+    ) {
+        assert!(validate_requested_outbound_permissions(&permissions, &requested).is_ok());
+    }
+
+    #[test_case(Permissions::Object(PermissionDetails { outbound: None, ..Default::default() }), Some(OutboundPermissions::Urls([Url::parse("https://1.net").unwrap().into()].into())))]
+    #[test_case(Permissions::Object(PermissionDetails { outbound: Some(OutboundPermissions::Urls([Url::parse("https://1.net").unwrap().into()].into())), ..Default::default() }), Some(OutboundPermissions::Unrestricted))]
+    #[test_case(Permissions::Object(PermissionDetails { outbound: Some(OutboundPermissions::Urls([Url::parse("https://1.net").unwrap().into()].into())), ..Default::default() }), Some(OutboundPermissions::Urls([Url::parse("https://2.net").unwrap().into()].into())))]
+    fn reject_outbound_access_requested_by_manifest(
+        permissions: Permissions,
+        requested: Option<OutboundPermissions>,
+    ) {
+        assert!(validate_requested_outbound_permissions(&permissions, &requested).is_err());
     }
 }
@@ -0,0 +1,78 @@
+use super::super::{EntryPermissions, PermissionEntry};
+
+pub struct EntryPermissionsExtendedError(());
+
+pub fn validate_entry_permissions<T: PermissionEntry>(
+    parent: &Option<EntryPermissions<T>>,
+    child: &Option<EntryPermissions<T>>,
+) -> Result<(), EntryPermissionsExtendedError> {
+    match (&parent, &child) {
+        (_, None) => Ok(()),
+        (None, Some(_)) => Err(EntryPermissionsExtendedError(())),
+        (Some(parent), Some(child)) => validate_entries(parent, child),
+    }
+}
+
+fn validate_entries<T: PermissionEntry>(
+    parent: &EntryPermissions<T>,
+    child: &EntryPermissions<T>,
+) -> Result<(), EntryPermissionsExtendedError> {
+    match (parent, child) {
+        (EntryPermissions::Unrestricted, _) => Ok(()),
+        (EntryPermissions::Entries(_), EntryPermissions::Unrestricted) => {
+            Err(EntryPermissionsExtendedError(()))
+        }
+        (EntryPermissions::Entries(parent_entries), EntryPermissions::Entries(child_entries)) => {
+            if child_entries.is_subset(parent_entries) {
+                Ok(())
+            } else {
+                Err(EntryPermissionsExtendedError(()))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use utils::*;
+
+    use test_case::test_case;
+
+    #[test_case(None, None, true)]
+    #[test_case(unrestricted(), None, true)]
+    #[test_case(None, unrestricted(), false)]
+    #[test_case(None, entries(&[]), false)]
+    #[test_case(unrestricted(), unrestricted(), true)]
+    #[test_case(unrestricted(), entries(&[443]), true)]
+    #[test_case(entries(&[443]), unrestricted(), false)]
+    #[test_case(entries(&[]), entries(&[]), true)]
+    #[test_case(entries(&[443]), entries(&[]), true)]
+    #[test_case(entries(&[443]), entries(&[443]), true)]
+    #[test_case(entries(&[443, 8080]), entries(&[8080]), true)]
+    #[test_case(entries(&[]), entries(&[443]), false)]
+    #[test_case(entries(&[443]), entries(&[8080]), false)]
+    #[test_case(entries(&[443]), entries(&[443, 8080]), false)]
+    fn accept_child_entries_only_within_parent_ones(
+        parent: Option<EntryPermissions<u16>>,
+        child: Option<EntryPermissions<u16>>,
+        accepted: bool,
+    ) {
+        assert_eq!(
+            validate_entry_permissions(&parent, &child).is_ok(),
+            accepted
+        );
+    }
+
+    mod utils {
+        use super::*;
+
+        pub fn unrestricted() -> Option<EntryPermissions<u16>> {
+            Some(EntryPermissions::Unrestricted)
+        }
+
+        pub fn entries(entries: &[u16]) -> Option<EntryPermissions<u16>> {
+            Some(EntryPermissions::Entries(entries.iter().cloned().collect()))
+        }
+    }
+}
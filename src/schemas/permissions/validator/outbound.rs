@@ -23,7 +23,10 @@ fn validate_url_permissions(
             Err(OutboundPermissionsExtendedError(()))
         }
         (OutboundPermissions::Urls(parent_urls), OutboundPermissions::Urls(child_urls)) => {
-            if child_urls.is_subset(parent_urls) {
+            if child_urls
+                .iter()
+                .all(|child_url| parent_urls.iter().any(|url| url.covers(child_url)))
+            {
                 Ok(())
             } else {
                 Err(OutboundPermissionsExtendedError(()))
@@ -41,7 +44,7 @@ mod should {
     use url::Url;
 
     #[test_case(OutboundPermissions::Unrestricted)]
-    #[test_case(OutboundPermissions::Urls([Url::parse("https://1.net").unwrap()].into()))]
+    #[test_case(OutboundPermissions::Urls([Url::parse("https://1.net").unwrap().into()].into()))]
     fn accept_outbound_permissions_because_parent_has_unrestricted(child: OutboundPermissions) {
         let parent = Some(OutboundPermissions::Unrestricted);
 
@@ -103,15 +106,52 @@ mod should {
         assert!(validate_outbound_permissions(&parent, &child).is_err());
     }
 
+    #[test_case(&["https://*.example.com/*"], &["https://api.example.com/v1", "https://www.example.com/"])]
+    #[test_case(&["https://example.com/api/*", "https://1.net"], &["https://example.com/api/v2/*", "https://1.net"])]
+    #[test_case(&["*://example.com:*/*"], &["http://example.com:8080/"])]
+    fn accept_outbound_permissions_because_parent_patterns_cover_child_urls(
+        parent_urls: &[&str],
+        child_urls: &[&str],
+    ) {
+        let parent = url_list_to_outbound_permissions(parent_urls);
+        let child = url_list_to_outbound_permissions(child_urls);
+
+        assert!(validate_outbound_permissions(&parent, &child).is_ok());
+    }
+
+    #[test_case(&["https://example.net"], &["https://Example.net:443/"])]
+    #[test_case(&["https://example.net/api/"], &["https://EXAMPLE.net/api"])]
+    #[test_case(&["https://example.net/%7Euser/*"], &["https://example.net/~user/data"])]
+    #[test_case(&["https://xn--bcher-kva.example/"], &["https://bücher.example"])]
+    fn accept_outbound_permissions_because_child_urls_are_equal_after_normalization(
+        parent_urls: &[&str],
+        child_urls: &[&str],
+    ) {
+        let parent = url_list_to_outbound_permissions(parent_urls);
+        let child = url_list_to_outbound_permissions(child_urls);
+
+        assert!(validate_outbound_permissions(&parent, &child).is_ok());
+    }
+
+    #[test_case(&["https://*.example.com/*"], &["https://example.com/"])]
+    #[test_case(&["https://example.com/api/*"], &["https://example.com/*"])]
+    #[test_case(&["https://example.com/*"], &["https://example.com/", "https://xxx.net"])]
+    fn reject_outbound_permissions_because_parent_patterns_do_not_cover_child_urls(
+        parent_urls: &[&str],
+        child_urls: &[&str],
+    ) {
+        let parent = url_list_to_outbound_permissions(parent_urls);
+        let child = url_list_to_outbound_permissions(child_urls);
+
+        assert!(validate_outbound_permissions(&parent, &child).is_err());
+    }
+
     mod utils {
         use super::*;
 
         pub fn url_list_to_outbound_permissions(urls: &[&str]) -> Option<OutboundPermissions> {
             Some(OutboundPermissions::Urls(
-                urls.iter()
-                    .cloned()
-                    .map(|u| Url::parse(u).unwrap())
-                    .collect(),
+                urls.iter().cloned().map(|u| u.parse().unwrap()).collect(),
             ))
         }
     }
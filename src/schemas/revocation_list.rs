@@ -0,0 +1,47 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use super::{certificate::Fingerprint, validity_period::ValidityPeriod};
+use crate::serde_utils;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RevocationList {
+    /// Fingerprints of the revoked certificates, hex digits are compared case-insensitively
+    #[serde(serialize_with = "serde_utils::sorted_set")]
+    pub revoked_certificates: HashSet<Fingerprint>,
+    pub validity_period: ValidityPeriod,
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    #[test]
+    fn serialize_and_deserialize_revocation_list() {
+        let revocation_list = RevocationList {
+            revoked_certificates: ["abcd".to_string()].into(),
+            validity_period: ValidityPeriod {
+                not_before: "2023-01-01T00:00:00Z".parse().unwrap(),
+                not_after: "2024-01-01T00:00:00Z".parse().unwrap(),
+            },
+        };
+        let json = json!({
+            "revokedCertificates": ["abcd"],
+            "validityPeriod": {
+                "notBefore": "2023-01-01T00:00:00Z",
+                "notAfter": "2024-01-01T00:00:00Z"
+            }
+        });
+
+        assert_eq!(serde_json::to_value(&revocation_list).unwrap(), json);
+        assert_eq!(
+            serde_json::from_value::<RevocationList>(json).unwrap(),
+            revocation_list
+        );
+    }
+}
@@ -6,16 +6,34 @@ use crate::serde_utils::{bytes_to_hex, hex_to_bytes};
 pub const SIGNED_NODE_DESCRIPTOR_SCHEMA_ID: &str =
     "https://schemas.golem.network/v1/node-descriptor.schema.json";
 
-#[derive(Serialize, Deserialize, Debug)]
+/// Version 2 of the node descriptor schema adds the `nodeSignature` counter-signature,
+/// counter-signed node descriptors have to declare it as v1 validators reject the property.
+pub const SIGNED_NODE_DESCRIPTOR_V2_SCHEMA_ID: &str =
+    "https://schemas.golem.network/v2/node-descriptor.schema.json";
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 pub struct SignedNodeDescriptor {
     #[serde(rename = "$schema")]
     pub schema: String,
     pub node_descriptor: serde_json::Value,
     pub signature: Signature<SignedCertificate>,
+    /// Counter-signature made with the key of the node identity, proving that the node agreed to the descriptor.
+    /// Requires the v2 schema
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node_signature: Option<NodeSignature>,
 }
 
+/// Recoverable signature of the node descriptor, the signer is identified by the recovered node id.
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct NodeSignature {
+    pub algorithm: SignatureAlgorithm,
+    #[serde(serialize_with = "bytes_to_hex", deserialize_with = "hex_to_bytes")]
+    pub value: Vec<u8>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct Signature<T> {
     pub algorithm: SignatureAlgorithm,
@@ -48,10 +66,34 @@ impl Signature<SignedCertificate> {
     }
 }
 
+pub const SIGNED_REVOCATION_LIST_SCHEMA_ID: &str =
+    "https://schemas.golem.network/v1/revocation-list.schema.json";
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct SignedRevocationList {
+    #[serde(rename = "$schema")]
+    pub schema: String,
+    pub revocation_list: serde_json::Value,
+    pub signature: Signature<SignedCertificate>,
+}
+
+pub const SIGNED_MANIFEST_SCHEMA_ID: &str = "https://schemas.golem.network/v1/manifest.schema.json";
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct SignedManifest {
+    #[serde(rename = "$schema")]
+    pub schema: String,
+    /// Base64 encoded computation manifest
+    pub manifest: String,
+    pub signature: Signature<SignedCertificate>,
+}
+
 pub const SIGNED_CERTIFICATE_SCHEMA_ID: &str =
     "https://schemas.golem.network/v1/certificate.schema.json";
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 pub struct SignedCertificate {
     #[serde(rename = "$schema")]
@@ -60,13 +102,13 @@ pub struct SignedCertificate {
     pub signature: Box<Signature<Signer>>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
 pub struct SignatureAlgorithm {
     pub hash: HashAlgorithm,
     pub encryption: EncryptionAlgorithm,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 #[serde(untagged)]
 pub enum Signer {
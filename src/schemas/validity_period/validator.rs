@@ -1,4 +1,4 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 
 use super::ValidityPeriod;
 use crate::Error;
@@ -18,9 +18,18 @@ pub fn validate_validity_period(
 }
 
 pub fn validate_timestamp(period: &ValidityPeriod, ts: DateTime<Utc>) -> Result<(), Error> {
-    if period.not_before > ts {
+    validate_timestamp_with_clock_skew(period, ts, Duration::zero())
+}
+
+/// Validates timestamp, tolerating a difference of at most `clock_skew` between the clocks of the signer and the verifier.
+pub fn validate_timestamp_with_clock_skew(
+    period: &ValidityPeriod,
+    ts: DateTime<Utc>,
+    clock_skew: Duration,
+) -> Result<(), Error> {
+    if period.not_before - clock_skew > ts {
         Err(Error::NotValidYet(period.not_before))
-    } else if ts > period.not_after {
+    } else if ts - clock_skew > period.not_after {
         Err(Error::Expired(period.not_after))
     } else {
         Ok(())
@@ -150,6 +159,42 @@ mod tests {
 
             assert!(validate_timestamp(period, now).is_ok());
         }
+
+        #[test]
+        pub(crate) fn accept_timestamp_because_it_is_within_clock_skew() {
+            let period = &ValidityPeriod {
+                not_before: dt("2000-01-01T01:01:01Z"),
+                not_after: dt("2000-01-01T03:03:03Z"),
+            };
+            let skew = Duration::seconds(60);
+
+            assert!(
+                validate_timestamp_with_clock_skew(period, dt("2000-01-01T01:00:01Z"), skew)
+                    .is_ok()
+            );
+            assert!(
+                validate_timestamp_with_clock_skew(period, dt("2000-01-01T03:04:03Z"), skew)
+                    .is_ok()
+            );
+        }
+
+        #[test]
+        pub(crate) fn reject_timestamp_because_it_exceeds_clock_skew() {
+            let period = &ValidityPeriod {
+                not_before: dt("2000-01-01T01:01:01Z"),
+                not_after: dt("2000-01-01T03:03:03Z"),
+            };
+            let skew = Duration::seconds(60);
+
+            assert!(
+                validate_timestamp_with_clock_skew(period, dt("2000-01-01T01:00:00Z"), skew)
+                    .is_err()
+            );
+            assert!(
+                validate_timestamp_with_clock_skew(period, dt("2000-01-01T03:04:04Z"), skew)
+                    .is_err()
+            );
+        }
     }
 
     mod utils {
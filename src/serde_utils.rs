@@ -1,5 +1,7 @@
+use std::collections::HashSet;
+
 use hex::{FromHex, ToHex};
-use serde::{Deserialize, Deserializer, Serializer};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 macro_rules! named_unit_variant {
     ($variant:ident, $name:expr) => {
@@ -54,3 +56,14 @@ where
     String::deserialize(deserializer)
         .and_then(|string| Vec::from_hex(string).map_err(|err| Error::custom(err.to_string())))
 }
+
+/// Serializes the set in sorted order, so the same document always produces the same json and fingerprint.
+pub fn sorted_set<T, S>(set: &HashSet<T>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Serialize + Ord,
+    S: Serializer,
+{
+    let mut items = set.iter().collect::<Vec<_>>();
+    items.sort();
+    serializer.collect_seq(items)
+}
@@ -0,0 +1,34 @@
+use serde_json::Value;
+
+use crate::{cryptography::Key, schemas::signature::SignatureAlgorithm, Error, Result};
+
+#[cfg(feature = "smartcard")]
+pub mod openpgp_card;
+mod software;
+
+pub use software::SoftwareSigningBackend;
+
+/// Source of signatures, e.g. a key held in memory, a smartcard, an HSM or a remote signer.
+pub trait SigningBackend {
+    /// Public key verifying the signatures created by the backend.
+    fn public_key(&mut self) -> Result<Key>;
+
+    /// Signs JCS canonical json and returns the used signature algorithm with the signature value.
+    fn sign_canonical_json(
+        &mut self,
+        canonical_json: &[u8],
+    ) -> Result<(SignatureAlgorithm, Vec<u8>)>;
+}
+
+/// Signs json using the signing backend.
+/// # Arguments
+/// * `value` json to be signed, it is serialized to JCS canonical json before signing
+/// * `signing_backend`
+pub fn sign_json_with_backend(
+    value: &Value,
+    signing_backend: &mut dyn SigningBackend,
+) -> Result<(SignatureAlgorithm, Vec<u8>)> {
+    let canonical_json = serde_json_canonicalizer::to_vec(value)
+        .map_err(|e| Error::JcsSerializationError(e.to_string()))?;
+    signing_backend.sign_canonical_json(&canonical_json)
+}
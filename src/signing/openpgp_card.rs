@@ -0,0 +1,304 @@
+use openpgp_card::{
+    algorithm::{Algo, Curve},
+    crypto_data::EccType,
+    CardTransaction, KeyType,
+};
+
+use crate::{
+    cryptography::{create_digest, EncryptionAlgorithm, HashAlgorithm, Key},
+    schemas::signature::SignatureAlgorithm,
+    Error, Result,
+};
+
+use super::SigningBackend;
+
+// Details of the commands are from
+// 'Functional Specification of the OpenPGP application on ISO Smart Card Operating Systems'
+// https://gnupg.org/ftp/specs/OpenPGP-smart-card-application-3.4.1.pdf
+
+pub type Transaction<'a> = Box<dyn CardTransaction + Send + Sync + 'a>;
+
+/// Signs with the Ed25519 signing key of an OpenPGP card.
+/// The card signs the hash of the canonical json, see `EncryptionAlgorithm::EdDSAOpenPGP`.
+pub struct OpenPgpCardSigningBackend<'a> {
+    transaction: Transaction<'a>,
+    pin: Option<String>,
+    hash_algorithm: HashAlgorithm,
+}
+
+impl<'a> OpenPgpCardSigningBackend<'a> {
+    pub fn new(transaction: Transaction<'a>) -> Self {
+        Self {
+            transaction,
+            pin: None,
+            hash_algorithm: Default::default(),
+        }
+    }
+
+    /// PIN of the signing key (PW1), required for signing only.
+    pub fn with_pin(mut self, pin: impl Into<String>) -> Self {
+        self.pin = Some(pin.into());
+        self
+    }
+
+    pub fn with_hash_algorithm(mut self, hash_algorithm: HashAlgorithm) -> Self {
+        self.hash_algorithm = hash_algorithm;
+        self
+    }
+
+    fn verify_key_algo(&mut self) -> Result<()> {
+        let app_data = self
+            .transaction
+            .application_related_data()
+            .map_err(card_error)?;
+        let key_algorithm = app_data
+            .algorithm_attributes(KeyType::Signing)
+            .map_err(card_error)?;
+        match key_algorithm {
+            Algo::Rsa(_) => Err("RSA signing keys are not supported".to_string()),
+            Algo::Ecc(attr) => {
+                if attr.ecc_type() != EccType::EdDSA {
+                    Err(format!(
+                        "Only EdDSA signing keys are supported. Found: {:?}",
+                        attr.ecc_type()
+                    ))
+                } else if attr.curve() != Curve::Ed25519 {
+                    Err(format!(
+                        "Only ed25519 curve is supported. Found: {:?}",
+                        attr.curve()
+                    ))
+                } else {
+                    Ok(())
+                }
+            }
+            _ => Err("Unknown signing key algorithm".to_string()),
+        }
+        .map_err(Error::SigningBackend)
+    }
+
+    fn read_public_key(&mut self) -> Result<Key> {
+        // Specification section
+        // 7.2.14 GENERATE ASYMMETRIC KEY PAIR
+        const CMD: [u8; 11] = [
+            0x00, 0x47, 0x81, 0x00, 0x05, 0xb6, 0x03, 0x84, 0x01, 0x01, 0x00,
+        ];
+        let cmd_result = self.transaction.transmit(&CMD, 40).map_err(card_error)?;
+        if cmd_result.len() != 39 || cmd_result[37..39] != [0x90, 0x00] {
+            Err(Error::SigningBackend(format!(
+                "Reading public key failed. Response {:x?}",
+                cmd_result
+            )))
+        } else {
+            let mut key_bytes = [0u8; 32];
+            key_bytes.copy_from_slice(&cmd_result[5..37]);
+            Ok(key_bytes.into())
+        }
+    }
+
+    fn login(&mut self) -> Result<()> {
+        let pin = self
+            .pin
+            .as_ref()
+            .ok_or_else(|| Error::SigningBackend("PIN is required for signing".into()))?;
+        // 7.2.2 VERIFY
+        let mut cmd = vec![0x00, 0x20, 0x00, 0x81];
+        cmd.push(pin.len() as u8);
+        cmd.extend_from_slice(pin.as_bytes());
+        let cmd_result = self.transaction.transmit(&cmd, 2).map_err(card_error)?;
+        if cmd_result != [0x90, 0x00] {
+            Err(Error::SigningBackend(format!(
+                "Login failed. Response: {:x?}",
+                cmd_result
+            )))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn sign_hash(&mut self, hash: &[u8]) -> Result<Vec<u8>> {
+        // 7.2.10 PSO: COMPUTE DIGITAL SIGNATURE
+        let mut cmd = vec![0x00, 0x2a, 0x9e, 0x9a];
+        cmd.push(hash.len() as u8);
+        cmd.extend_from_slice(hash);
+        cmd.push(0x00);
+        let cmd_result = self.transaction.transmit(&cmd, 1024).map_err(card_error)?;
+        if cmd_result.len() != 66 || cmd_result[64..66] != [0x90, 0x00] {
+            Err(Error::SigningBackend(format!(
+                "Signing failed. Response {:x?}",
+                cmd_result
+            )))
+        } else {
+            Ok(cmd_result[..64].into())
+        }
+    }
+}
+
+impl SigningBackend for OpenPgpCardSigningBackend<'_> {
+    fn public_key(&mut self) -> Result<Key> {
+        self.verify_key_algo()?;
+        self.read_public_key()
+    }
+
+    fn sign_canonical_json(
+        &mut self,
+        canonical_json: &[u8],
+    ) -> Result<(SignatureAlgorithm, Vec<u8>)> {
+        self.verify_key_algo()?;
+        self.login()?;
+        let hash = create_digest(canonical_json, &self.hash_algorithm);
+        let signature_value = self.sign_hash(&hash)?;
+        let signature_algorithm = SignatureAlgorithm {
+            hash: self.hash_algorithm.clone(),
+            encryption: EncryptionAlgorithm::EdDSAOpenPGP,
+        };
+        Ok((signature_algorithm, signature_value))
+    }
+}
+
+fn card_error(error: openpgp_card::Error) -> Error {
+    Error::SigningBackend(error.to_string())
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+
+    use ed25519_dalek::{ExpandedSecretKey, PublicKey, SecretKey};
+    use openpgp_card::{CardCaps, PinType};
+    use serde_json::json;
+
+    use crate::{create_key_pair, signing::sign_json_with_backend, verify_signature_json};
+
+    type CardResult<T> = std::result::Result<T, openpgp_card::Error>;
+
+    const PIN: &str = "123456";
+
+    /// Emulates the commands of an OpenPGP card holding an Ed25519 signing key.
+    struct MockCardTransaction {
+        secret_key: SecretKey,
+        logged_in: bool,
+    }
+
+    impl MockCardTransaction {
+        fn new(private_key: &Key) -> Self {
+            let private_key = serde_json::to_value(private_key).unwrap();
+            let key_bytes = hex::decode(private_key["key"].as_str().unwrap()).unwrap();
+            Self {
+                secret_key: SecretKey::from_bytes(&key_bytes).unwrap(),
+                logged_in: false,
+            }
+        }
+    }
+
+    impl CardTransaction for MockCardTransaction {
+        fn transmit(&mut self, cmd: &[u8], _buf_size: usize) -> CardResult<Vec<u8>> {
+            const OK: [u8; 2] = [0x90, 0x00];
+            let public_key = PublicKey::from(&self.secret_key);
+            let response = match cmd[1] {
+                // GET DATA: application related data with Ed25519 signing key attributes
+                0xca => [
+                    0xc1, 0x0a, 0x16, 0x2b, 0x06, 0x01, 0x04, 0x01, 0xda, 0x47, 0x0f, 0x01,
+                ]
+                .to_vec(),
+                // GENERATE ASYMMETRIC KEY PAIR: read public key
+                0x47 => [
+                    &[0x7f, 0x49, 0x22, 0x86, 0x20],
+                    public_key.as_bytes().as_slice(),
+                ]
+                .concat(),
+                // VERIFY
+                0x20 if &cmd[5..] == PIN.as_bytes() => {
+                    self.logged_in = true;
+                    vec![]
+                }
+                // PSO: COMPUTE DIGITAL SIGNATURE
+                0x2a if self.logged_in => {
+                    let hash = &cmd[5..cmd.len() - 1];
+                    ExpandedSecretKey::from(&self.secret_key)
+                        .sign(hash, &public_key)
+                        .to_bytes()
+                        .to_vec()
+                }
+                _ => return Ok(vec![0x69, 0x82]),
+            };
+            Ok([response.as_slice(), &OK].concat())
+        }
+
+        fn init_card_caps(&mut self, _caps: CardCaps) {}
+
+        fn card_caps(&self) -> Option<&CardCaps> {
+            None
+        }
+
+        fn feature_pinpad_verify(&self) -> bool {
+            false
+        }
+
+        fn feature_pinpad_modify(&self) -> bool {
+            false
+        }
+
+        fn pinpad_verify(&mut self, _pin: PinType) -> CardResult<Vec<u8>> {
+            Err(openpgp_card::Error::UnsupportedFeature("pinpad".into()))
+        }
+
+        fn pinpad_modify(&mut self, _pin: PinType) -> CardResult<Vec<u8>> {
+            Err(openpgp_card::Error::UnsupportedFeature("pinpad".into()))
+        }
+    }
+
+    #[test]
+    fn read_public_key_from_card() {
+        let key_pair = create_key_pair();
+        let mut backend = OpenPgpCardSigningBackend::new(Box::new(MockCardTransaction::new(
+            &key_pair.private_key,
+        )));
+
+        assert_eq!(backend.public_key().unwrap(), key_pair.public_key);
+    }
+
+    #[test]
+    fn create_openpgp_signature_with_requested_hash() {
+        let key_pair = create_key_pair();
+        let mut backend = OpenPgpCardSigningBackend::new(Box::new(MockCardTransaction::new(
+            &key_pair.private_key,
+        )))
+        .with_pin(PIN)
+        .with_hash_algorithm(HashAlgorithm::Sha256);
+        let value = json!({ "test": "value" });
+
+        let (algorithm, signature_value) = sign_json_with_backend(&value, &mut backend).unwrap();
+
+        assert_eq!(algorithm.encryption, EncryptionAlgorithm::EdDSAOpenPGP);
+        assert_eq!(algorithm.hash, HashAlgorithm::Sha256);
+        assert!(
+            verify_signature_json(&value, &algorithm, signature_value, &key_pair.public_key)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn fail_signing_without_pin() {
+        let key_pair = create_key_pair();
+        let mut backend = OpenPgpCardSigningBackend::new(Box::new(MockCardTransaction::new(
+            &key_pair.private_key,
+        )));
+
+        let result = sign_json_with_backend(&json!({}), &mut backend);
+
+        assert!(matches!(result.unwrap_err(), Error::SigningBackend(_)));
+    }
+
+    #[test]
+    fn fail_signing_with_wrong_pin() {
+        let key_pair = create_key_pair();
+        let mut backend = OpenPgpCardSigningBackend::new(Box::new(MockCardTransaction::new(
+            &key_pair.private_key,
+        )))
+        .with_pin("000000");
+
+        let result = sign_json_with_backend(&json!({}), &mut backend);
+
+        assert!(matches!(result.unwrap_err(), Error::SigningBackend(_)));
+    }
+}
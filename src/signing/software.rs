@@ -0,0 +1,77 @@
+use crate::{
+    cryptography::{
+        default_signature_algorithm, public_key_from_private, sign_canonical_json, Key,
+    },
+    schemas::signature::SignatureAlgorithm,
+    Result,
+};
+
+use super::SigningBackend;
+
+/// Signs with a private key held in memory.
+#[derive(Debug, Clone)]
+pub struct SoftwareSigningBackend {
+    private_key: Key,
+    signature_algorithm: SignatureAlgorithm,
+}
+
+impl SoftwareSigningBackend {
+    /// Uses the default signature algorithm of the key, see [`crate::sign_json`].
+    pub fn new(private_key: Key) -> Self {
+        Self {
+            signature_algorithm: default_signature_algorithm(&private_key),
+            private_key,
+        }
+    }
+
+    pub fn with_signature_algorithm(mut self, signature_algorithm: SignatureAlgorithm) -> Self {
+        self.signature_algorithm = signature_algorithm;
+        self
+    }
+}
+
+impl SigningBackend for SoftwareSigningBackend {
+    fn public_key(&mut self) -> Result<Key> {
+        public_key_from_private(&self.private_key)
+    }
+
+    fn sign_canonical_json(
+        &mut self,
+        canonical_json: &[u8],
+    ) -> Result<(SignatureAlgorithm, Vec<u8>)> {
+        let signature_value =
+            sign_canonical_json(canonical_json, &self.private_key, &self.signature_algorithm)?;
+        Ok((self.signature_algorithm.clone(), signature_value))
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+
+    use serde_json::json;
+
+    use crate::{create_key_pair, signing::sign_json_with_backend, verify_signature_json};
+
+    #[test]
+    fn expose_public_key_of_the_private_key() {
+        let key_pair = create_key_pair();
+        let mut backend = SoftwareSigningBackend::new(key_pair.private_key);
+
+        assert_eq!(backend.public_key().unwrap(), key_pair.public_key);
+    }
+
+    #[test]
+    fn create_signature_verified_by_public_key() {
+        let key_pair = create_key_pair();
+        let mut backend = SoftwareSigningBackend::new(key_pair.private_key);
+        let value = json!({ "test": "value" });
+
+        let (algorithm, signature_value) = sign_json_with_backend(&value, &mut backend).unwrap();
+
+        assert!(
+            verify_signature_json(&value, &algorithm, signature_value, &key_pair.public_key)
+                .is_ok()
+        );
+    }
+}
@@ -0,0 +1,78 @@
+//! Certificate fixtures shared by the unit tests.
+
+use chrono::{DateTime, Utc};
+
+use crate::{
+    create_key_pair,
+    schemas::{
+        certificate::{
+            key_usage::{KeyUsage, Usage},
+            Certificate,
+        },
+        permissions::Permissions,
+        signature::SignedCertificate,
+        subject::{Contact, Subject},
+        validity_period::ValidityPeriod,
+    },
+    CertificateBuilder, KeyPair,
+};
+
+/// Timestamp within the validity period of the fixture certificates.
+pub fn now() -> DateTime<Utc> {
+    "2024-01-01T00:00:00Z".parse().unwrap()
+}
+
+/// Certificate valid from 2020 to 2030.
+pub fn certificate(
+    key_pair: &KeyPair,
+    key_usage: KeyUsage,
+    permissions: Permissions,
+) -> Certificate {
+    Certificate {
+        key_usage,
+        permissions,
+        public_key: key_pair.public_key.clone(),
+        subject: Subject {
+            display_name: "Test certificate".into(),
+            contact: Contact {
+                email: "test@example.tld".into(),
+                additional_properties: Default::default(),
+            },
+            additional_properties: Default::default(),
+        },
+        validity_period: ValidityPeriod {
+            not_before: "2020-01-01T00:00:00Z".parse().unwrap(),
+            not_after: "2030-01-01T00:00:00Z".parse().unwrap(),
+        },
+    }
+}
+
+/// Certificate signed by a new self-signed root and its key pair.
+pub fn signer(key_usage: KeyUsage, permissions: Permissions) -> (SignedCertificate, KeyPair) {
+    let root_key_pair = create_key_pair();
+    let root =
+        CertificateBuilder::new(certificate(&root_key_pair, KeyUsage::All, Permissions::All))
+            .self_sign(&root_key_pair.private_key)
+            .unwrap();
+    let key_pair = create_key_pair();
+    let signer = CertificateBuilder::new(certificate(&key_pair, key_usage, permissions))
+        .sign(root, &root_key_pair.private_key)
+        .unwrap();
+    (signer, key_pair)
+}
+
+/// Leaf certificate signed by an intermediate certificate signed by a new self-signed root.
+pub fn chain() -> SignedCertificate {
+    let (intermediate, intermediate_key_pair) = signer(
+        KeyUsage::Limited([Usage::SignCertificate, Usage::SignNode].into()),
+        Permissions::All,
+    );
+    let leaf_key_pair = create_key_pair();
+    CertificateBuilder::new(certificate(
+        &leaf_key_pair,
+        KeyUsage::Limited([Usage::SignNode].into()),
+        Permissions::All,
+    ))
+    .sign(intermediate, &intermediate_key_pair.private_key)
+    .unwrap()
+}
@@ -1,25 +1,51 @@
 use chrono::{DateTime, Utc};
 use hex::ToHex;
+use serde::Deserialize;
 use serde_json::Value;
 
 use crate::{
-    cryptography::{create_default_hash, verify_signature_json},
+    cryptography::{create_hash, verify_signature_json_with_node_id},
     schemas::{
         certificate::{
-            key_usage::validator::{validate_certificates_key_usage, validate_sign_node},
-            Certificate, Fingerprint,
+            key_usage::validator::{
+                validate_sign_manifest, validate_sign_node, validate_sign_revocation_list,
+            },
+            Fingerprint,
         },
+        manifest::{decode_manifest, Manifest},
         node_descriptor::NodeDescriptor,
-        permissions::validator::validate_permissions,
-        signature::{SignedCertificate, SignedNodeDescriptor, Signer},
-        validity_period::validator::{validate_timestamp, validate_validity_period},
-        SIGNED_CERTIFICATE_SCHEMA_ID, SIGNED_NODE_DESCRIPTOR_SCHEMA_ID,
+        permissions::validator::{validate_permissions, validate_requested_outbound_permissions},
+        revocation_list::RevocationList,
+        signature::{
+            SignedCertificate, SignedManifest, SignedNodeDescriptor, SignedRevocationList,
+        },
+        validity_period::validator::validate_validity_period,
+        SIGNED_CERTIFICATE_SCHEMA_ID, SIGNED_MANIFEST_SCHEMA_ID, SIGNED_NODE_DESCRIPTOR_SCHEMA_ID,
+        SIGNED_NODE_DESCRIPTOR_V2_SCHEMA_ID, SIGNED_REVOCATION_LIST_SCHEMA_ID,
     },
-    Error, Result,
+    ChainPosition, Error, Result,
 };
 
-use self::validated_data::{ValidatedCertificate, ValidatedNodeDescriptor};
+use self::{
+    batch::SignatureVerifier,
+    cache::CertificateCache,
+    chain::ParsedChain,
+    options::{TimeCheck, ValidationOptions},
+    report::{check_certificate_chain, ValidationCheck, ValidationReport},
+    trust_store::TrustStore,
+    validated_data::{
+        ValidatedCertificate, ValidatedManifest, ValidatedNodeDescriptor, ValidatedRevocationList,
+    },
+};
 
+pub mod batch;
+pub mod cache;
+pub(crate) mod chain;
+pub mod lint;
+pub mod options;
+pub mod report;
+pub mod revocation;
+pub mod trust_store;
 pub mod validated_data;
 
 /// Deserializes and validates certificate.
@@ -42,14 +68,89 @@ pub fn validate_certificate(
     value: Value,
     timestamp: Option<DateTime<Utc>>,
 ) -> Result<ValidatedCertificate> {
-    validate_schema(&value, SIGNED_CERTIFICATE_SCHEMA_ID, "certificate")?;
-    let signed_certificate: SignedCertificate = serde_json::from_value(value)
-        .map_err(|e| Error::JsonDoesNotConformToSchema(e.to_string()))?;
-    let mut validated_certificate = validate_signed_certificate(&signed_certificate, timestamp)?;
-    validated_certificate
-        .certificate_chain_fingerprints
-        .reverse();
-    Ok(validated_certificate)
+    validate_certificate_value(value, &timestamp.into(), None)
+}
+
+/// Deserializes and validates certificate.
+/// # Arguments
+/// * `data` serialized certificate
+/// * `options` time of validation, clock skew, maximum chain depth, trusted roots and revocation lists
+pub fn validate_certificate_str_with_options(
+    data: &str,
+    options: &ValidationOptions,
+) -> Result<ValidatedCertificate> {
+    let value: Value = serde_json::from_str(data).map_err(|e| Error::InvalidJson(e.to_string()))?;
+    validate_certificate_with_options(value, options)
+}
+
+/// Validates certificate.
+/// # Arguments
+/// * `value` certificate
+/// * `options` time of validation, clock skew, maximum chain depth, trusted roots and revocation lists
+pub fn validate_certificate_with_options(
+    value: Value,
+    options: &ValidationOptions,
+) -> Result<ValidatedCertificate> {
+    validate_certificate_value(value, options, options.trust_store())
+}
+
+/// Deserializes certificate and checks every certificate of its chain without stopping at the first failure.
+/// # Arguments
+/// * `data` serialized certificate
+/// * `timestamp` optional timestamp to verify validity
+pub fn validate_certificate_str_report(
+    data: &str,
+    timestamp: Option<DateTime<Utc>>,
+) -> ValidationReport {
+    match serde_json::from_str(data) {
+        Ok(value) => validate_certificate_report(value, timestamp),
+        Err(e) => {
+            ValidationReport::from_error(ValidationCheck::Schema, Error::InvalidJson(e.to_string()))
+        }
+    }
+}
+
+/// Checks every certificate of the chain without stopping at the first failure.
+/// # Arguments
+/// * `value` certificate
+/// * `timestamp` optional timestamp to verify validity
+pub fn validate_certificate_report(
+    value: Value,
+    timestamp: Option<DateTime<Utc>>,
+) -> ValidationReport {
+    validate_certificate_report_with_options(value, &timestamp.into())
+}
+
+/// Checks every certificate of the chain without stopping at the first failure.
+/// The maximum chain depth, trusted roots and revocation lists of the options are not checked.
+/// # Arguments
+/// * `value` certificate
+/// * `options` time of validation and clock skew
+pub fn validate_certificate_report_with_options(
+    value: Value,
+    options: &ValidationOptions,
+) -> ValidationReport {
+    let signed_certificate = validate_schema(&value, SIGNED_CERTIFICATE_SCHEMA_ID, "certificate")
+        .and_then(|_| {
+            serde_json::from_value::<SignedCertificate>(value)
+                .map_err(|e| Error::JsonDoesNotConformToSchema(e.to_string()))
+        });
+    match signed_certificate {
+        Ok(signed_certificate) => {
+            let mut report = check_certificate_chain(
+                &ParsedChain::parse(&signed_certificate),
+                options.time_check(),
+                &SignatureVerifier::default(),
+            );
+            if let Some(validated_certificate) = &mut report.validated_certificate {
+                validated_certificate
+                    .certificate_chain_fingerprints
+                    .reverse();
+            }
+            report
+        }
+        Err(error) => ValidationReport::from_error(ValidationCheck::Schema, error),
+    }
 }
 
 /// Deserializes and validates node descriptor.
@@ -72,30 +173,394 @@ pub fn validate_node_descriptor(
     value: Value,
     timestamp: Option<DateTime<Utc>>,
 ) -> Result<ValidatedNodeDescriptor> {
-    validate_schema(&value, SIGNED_NODE_DESCRIPTOR_SCHEMA_ID, "node descriptor")?;
+    validate_node_descriptor_value(value, &timestamp.into(), None)
+}
+
+/// Deserializes and validates node descriptor.
+/// # Arguments
+/// * `data` serialized node descriptor
+/// * `options` time of validation, clock skew, maximum chain depth, trusted roots, revocation lists and whether the node signature is required
+pub fn validate_node_descriptor_str_with_options(
+    data: &str,
+    options: &ValidationOptions,
+) -> Result<ValidatedNodeDescriptor> {
+    let value: Value = serde_json::from_str(data).map_err(|e| Error::InvalidJson(e.to_string()))?;
+    validate_node_descriptor_with_options(value, options)
+}
+
+/// Validates node descriptor.
+/// # Arguments
+/// * `value` node descriptor
+/// * `options` time of validation, clock skew, maximum chain depth, trusted roots, revocation lists and whether the node signature is required
+pub fn validate_node_descriptor_with_options(
+    value: Value,
+    options: &ValidationOptions,
+) -> Result<ValidatedNodeDescriptor> {
+    validate_node_descriptor_value(value, options, options.trust_store())
+}
+
+/// Deserializes and validates node descriptor, requiring the counter-signature of the node identity.
+/// # Arguments
+/// * `data` serialized node descriptor
+/// * `timestamp` optional timestamp to verify validity
+pub fn validate_node_descriptor_str_strict(
+    data: &str,
+    timestamp: Option<DateTime<Utc>>,
+) -> Result<ValidatedNodeDescriptor> {
+    let value: Value = serde_json::from_str(data).map_err(|e| Error::InvalidJson(e.to_string()))?;
+    validate_node_descriptor_strict(value, timestamp)
+}
+
+/// Validates node descriptor, requiring the counter-signature of the node identity.
+/// # Arguments
+/// * `value` node descriptor
+/// * `timestamp` optional timestamp to verify validity
+pub fn validate_node_descriptor_strict(
+    value: Value,
+    timestamp: Option<DateTime<Utc>>,
+) -> Result<ValidatedNodeDescriptor> {
+    let options = ValidationOptions::from(timestamp).with_node_signature_required(true);
+    validate_node_descriptor_value(value, &options, None)
+}
+
+/// Deserializes and validates certificate, requiring its chain to be anchored in a trusted root.
+/// # Arguments
+/// * `data` serialized certificate
+/// * `timestamp` optional timestamp to verify validity
+/// * `trust_store` trusted root certificates
+pub fn validate_certificate_str_with_trust_store(
+    data: &str,
+    timestamp: Option<DateTime<Utc>>,
+    trust_store: &TrustStore,
+) -> Result<ValidatedCertificate> {
+    let value: Value = serde_json::from_str(data).map_err(|e| Error::InvalidJson(e.to_string()))?;
+    validate_certificate_with_trust_store(value, timestamp, trust_store)
+}
+
+/// Validates certificate, requiring its chain to be anchored in a trusted root.
+/// # Arguments
+/// * `value` certificate
+/// * `timestamp` optional timestamp to verify validity
+/// * `trust_store` trusted root certificates
+pub fn validate_certificate_with_trust_store(
+    value: Value,
+    timestamp: Option<DateTime<Utc>>,
+    trust_store: &TrustStore,
+) -> Result<ValidatedCertificate> {
+    validate_certificate_value(value, &timestamp.into(), Some(trust_store))
+}
+
+/// Deserializes and validates node descriptor, requiring its chain to be anchored in a trusted root.
+/// # Arguments
+/// * `data` serialized node descriptor
+/// * `timestamp` optional timestamp to verify validity
+/// * `trust_store` trusted root certificates
+pub fn validate_node_descriptor_str_with_trust_store(
+    data: &str,
+    timestamp: Option<DateTime<Utc>>,
+    trust_store: &TrustStore,
+) -> Result<ValidatedNodeDescriptor> {
+    let value: Value = serde_json::from_str(data).map_err(|e| Error::InvalidJson(e.to_string()))?;
+    validate_node_descriptor_with_trust_store(value, timestamp, trust_store)
+}
+
+/// Validates node descriptor, requiring its chain to be anchored in a trusted root.
+/// # Arguments
+/// * `value` node descriptor
+/// * `timestamp` optional timestamp to verify validity
+/// * `trust_store` trusted root certificates
+pub fn validate_node_descriptor_with_trust_store(
+    value: Value,
+    timestamp: Option<DateTime<Utc>>,
+    trust_store: &TrustStore,
+) -> Result<ValidatedNodeDescriptor> {
+    validate_node_descriptor_value(value, &timestamp.into(), Some(trust_store))
+}
+
+/// Deserializes and validates revocation list.
+/// # Arguments
+/// * `data` serialized revocation list
+/// * `timestamp` optional timestamp to verify validity
+pub fn validate_revocation_list_str(
+    data: &str,
+    timestamp: Option<DateTime<Utc>>,
+) -> Result<ValidatedRevocationList> {
+    let value: Value = serde_json::from_str(data).map_err(|e| Error::InvalidJson(e.to_string()))?;
+    validate_revocation_list(value, timestamp)
+}
+
+/// Validates revocation list.
+/// # Arguments
+/// * `value` revocation list
+/// * `timestamp` optional timestamp to verify validity
+pub fn validate_revocation_list(
+    value: Value,
+    timestamp: Option<DateTime<Utc>>,
+) -> Result<ValidatedRevocationList> {
+    validate_revocation_list_with_options(value, &timestamp.into())
+}
+
+/// Deserializes and validates revocation list.
+/// # Arguments
+/// * `data` serialized revocation list
+/// * `options` time of validation, clock skew, maximum chain depth, trusted roots and revocation lists
+pub fn validate_revocation_list_str_with_options(
+    data: &str,
+    options: &ValidationOptions,
+) -> Result<ValidatedRevocationList> {
+    let value: Value = serde_json::from_str(data).map_err(|e| Error::InvalidJson(e.to_string()))?;
+    validate_revocation_list_with_options(value, options)
+}
+
+/// Validates revocation list.
+/// # Arguments
+/// * `value` revocation list
+/// * `options` time of validation, clock skew, maximum chain depth, trusted roots and revocation lists
+pub fn validate_revocation_list_with_options(
+    value: Value,
+    options: &ValidationOptions,
+) -> Result<ValidatedRevocationList> {
+    validate_schema(&value, SIGNED_REVOCATION_LIST_SCHEMA_ID, "revocation list")?;
+    let signed_revocation_list: SignedRevocationList = serde_json::from_value(value)
+        .map_err(|e| Error::JsonDoesNotConformToSchema(e.to_string()))?;
+    let chain = ParsedChain::parse(&signed_revocation_list.signature.signer);
+    validate_signer_options(&chain, options)?;
+    let mut validated_revocation_list = validate_signed_revocation_list(
+        &signed_revocation_list,
+        &chain,
+        options.time_check(),
+        options.cache(),
+    )?;
+    validated_revocation_list
+        .certificate_chain_fingerprints
+        .reverse();
+    validate_not_revoked(
+        &chain,
+        &validated_revocation_list.certificate_chain_fingerprints,
+        options,
+        1,
+    )?;
+    Ok(validated_revocation_list)
+}
+
+/// Deserializes and validates signed manifest.
+/// # Arguments
+/// * `data` serialized signed manifest
+/// * `timestamp` optional timestamp to verify validity
+pub fn validate_manifest_str(
+    data: &str,
+    timestamp: Option<DateTime<Utc>>,
+) -> Result<ValidatedManifest> {
+    let value: Value = serde_json::from_str(data).map_err(|e| Error::InvalidJson(e.to_string()))?;
+    validate_manifest(value, timestamp)
+}
+
+/// Validates signed manifest.
+/// # Arguments
+/// * `value` signed manifest
+/// * `timestamp` optional timestamp to verify validity
+pub fn validate_manifest(
+    value: Value,
+    timestamp: Option<DateTime<Utc>>,
+) -> Result<ValidatedManifest> {
+    validate_manifest_with_options(value, &timestamp.into())
+}
+
+/// Deserializes and validates signed manifest.
+/// # Arguments
+/// * `data` serialized signed manifest
+/// * `options` time of validation, clock skew, maximum chain depth, trusted roots and revocation lists
+pub fn validate_manifest_str_with_options(
+    data: &str,
+    options: &ValidationOptions,
+) -> Result<ValidatedManifest> {
+    let value: Value = serde_json::from_str(data).map_err(|e| Error::InvalidJson(e.to_string()))?;
+    validate_manifest_with_options(value, options)
+}
+
+/// Validates signed manifest.
+/// # Arguments
+/// * `value` signed manifest
+/// * `options` time of validation, clock skew, maximum chain depth, trusted roots and revocation lists
+pub fn validate_manifest_with_options(
+    value: Value,
+    options: &ValidationOptions,
+) -> Result<ValidatedManifest> {
+    validate_schema(&value, SIGNED_MANIFEST_SCHEMA_ID, "manifest")?;
+    let signed_manifest: SignedManifest = serde_json::from_value(value)
+        .map_err(|e| Error::JsonDoesNotConformToSchema(e.to_string()))?;
+    let chain = ParsedChain::parse(&signed_manifest.signature.signer);
+    validate_signer_options(&chain, options)?;
+    let mut validated_manifest = validate_signed_manifest(
+        &signed_manifest,
+        &chain,
+        options.time_check(),
+        options.cache(),
+    )?;
+    validated_manifest.certificate_chain_fingerprints.reverse();
+    validate_not_revoked(
+        &chain,
+        &validated_manifest.certificate_chain_fingerprints,
+        options,
+        1,
+    )?;
+    Ok(validated_manifest)
+}
+
+fn validate_certificate_value(
+    value: Value,
+    options: &ValidationOptions,
+    trust_store: Option<&TrustStore>,
+) -> Result<ValidatedCertificate> {
+    validate_schema(&value, SIGNED_CERTIFICATE_SCHEMA_ID, "certificate")?;
+    let signed_certificate: SignedCertificate = serde_json::from_value(value)
+        .map_err(|e| Error::JsonDoesNotConformToSchema(e.to_string()))?;
+    validate_signed_certificate_with_options(
+        &signed_certificate,
+        options,
+        trust_store,
+        &SignatureVerifier::default(),
+    )
+}
+
+/// Validates deserialized certificate, certificate chain fingerprints are ordered from the certificate.
+pub(crate) fn validate_signed_certificate_with_options(
+    signed_certificate: &SignedCertificate,
+    options: &ValidationOptions,
+    trust_store: Option<&TrustStore>,
+    verifier: &SignatureVerifier,
+) -> Result<ValidatedCertificate> {
+    let chain = ParsedChain::parse(signed_certificate);
+    validate_chain_options(&chain, options, trust_store, 0)?;
+    let time_check = options.time_check();
+    let mut validated_certificate = match options.cache() {
+        Some(cache) => cache.validate(&chain, time_check, verifier),
+        None => validate_signed_certificate(&chain, time_check, verifier),
+    }?;
+    validated_certificate
+        .certificate_chain_fingerprints
+        .reverse();
+    validate_not_revoked(
+        &chain,
+        &validated_certificate.certificate_chain_fingerprints,
+        options,
+        0,
+    )?;
+    Ok(validated_certificate)
+}
+
+fn validate_node_descriptor_value(
+    value: Value,
+    options: &ValidationOptions,
+    trust_store: Option<&TrustStore>,
+) -> Result<ValidatedNodeDescriptor> {
+    if value["$schema"] != SIGNED_NODE_DESCRIPTOR_V2_SCHEMA_ID {
+        validate_schema(&value, SIGNED_NODE_DESCRIPTOR_SCHEMA_ID, "node descriptor")?;
+    }
     let signed_node_descriptor: SignedNodeDescriptor = serde_json::from_value(value)
         .map_err(|e| Error::JsonDoesNotConformToSchema(e.to_string()))?;
-    let mut validated_node_descriptor =
-        validate_signed_node_descriptor(signed_node_descriptor, timestamp)?;
+    validate_node_descriptor_schema(&signed_node_descriptor)?;
+    validate_signed_node_descriptor_with_options(
+        &signed_node_descriptor,
+        options,
+        trust_store,
+        &SignatureVerifier::default(),
+    )
+}
+
+/// Validates deserialized node descriptor, certificate chain fingerprints are ordered from the signing certificate.
+pub(crate) fn validate_signed_node_descriptor_with_options(
+    signed_node_descriptor: &SignedNodeDescriptor,
+    options: &ValidationOptions,
+    trust_store: Option<&TrustStore>,
+    verifier: &SignatureVerifier,
+) -> Result<ValidatedNodeDescriptor> {
+    let chain = ParsedChain::parse(&signed_node_descriptor.signature.signer);
+    validate_chain_options(&chain, options, trust_store, 1)?;
+    let mut validated_node_descriptor = validate_signed_node_descriptor(
+        signed_node_descriptor,
+        &chain,
+        options.time_check(),
+        options.cache(),
+        verifier,
+        options.require_node_signature(),
+    )?;
     validated_node_descriptor
         .certificate_chain_fingerprints
         .reverse();
+    validate_not_revoked(
+        &chain,
+        &validated_node_descriptor.certificate_chain_fingerprints,
+        options,
+        1,
+    )?;
     Ok(validated_node_descriptor)
 }
 
+/// Checks the trusted roots and maximum chain depth of the options for the certificate signing a document.
+fn validate_signer_options(chain: &ParsedChain, options: &ValidationOptions) -> Result<()> {
+    validate_chain_options(chain, options, options.trust_store(), 1)
+}
+
+/// Checks that the chain is anchored in a trusted root and is not too deep.
+/// An untrusted root is reported at the position of the root, a too deep chain at the position of the validated document.
+/// # Arguments
+/// * `document_depth` depth of the chain leaf, 0 when it is the validated certificate and 1 when it signs the validated document
+fn validate_chain_options(
+    chain: &ParsedChain,
+    options: &ValidationOptions,
+    trust_store: Option<&TrustStore>,
+    document_depth: usize,
+) -> Result<()> {
+    let root_depth = chain.root_depth() + document_depth;
+    if let Some(trust_store) = trust_store {
+        trust_store
+            .verify_anchor(chain)
+            .map_err(|error| Error::InChain {
+                position: chain.root().position(root_depth),
+                error: Box::new(error),
+            })?;
+    }
+    options
+        .validate_chain_depth(root_depth)
+        .map_err(|error| Error::InChain {
+            position: match document_depth {
+                0 => chain.leaf().position(0),
+                _ => ChainPosition::default(),
+            },
+            error: Box::new(error),
+        })
+}
+
+/// Checks that no certificate of the validated chain is revoked by the revocation lists of the options.
+/// The revoked certificate is reported at its position in the chain.
+/// # Arguments
+/// * `certificate_chain_fingerprints` fingerprints of the validated chain, ordered from the leaf
+/// * `document_depth` depth of the chain leaf, 0 when it is the validated certificate and 1 when it signs the validated document
+fn validate_not_revoked(
+    chain: &ParsedChain,
+    certificate_chain_fingerprints: &[Fingerprint],
+    options: &ValidationOptions,
+    document_depth: usize,
+) -> Result<()> {
+    match options
+        .revocation_lists()
+        .find_revoked(certificate_chain_fingerprints)
+    {
+        Some(depth) => Err(Error::InChain {
+            position: chain.links()[depth].position(depth + document_depth),
+            error: Box::new(Error::Revoked(
+                certificate_chain_fingerprints[depth].to_owned(),
+            )),
+        }),
+        None => Ok(()),
+    }
+}
+
 fn validate_schema(value: &Value, schema_id: &str, structure_name: &str) -> Result<()> {
     value["$schema"]
         .as_str()
-        .map(|schema| {
-            if schema == schema_id {
-                Ok(())
-            } else {
-                Err(Error::UnsupportedSchema {
-                    schema: schema.to_owned(),
-                    structure_name: structure_name.to_owned(),
-                })
-            }
-        })
+        .map(|schema| validate_schema_id(schema, schema_id, structure_name))
         .unwrap_or_else(|| {
             Err(Error::JsonDoesNotConformToSchema(format!(
                 "Missing `schema` property in {structure_name}"
@@ -103,29 +568,95 @@ fn validate_schema(value: &Value, schema_id: &str, structure_name: &str) -> Resu
         })
 }
 
+fn validate_schema_id(schema: &str, schema_id: &str, structure_name: &str) -> Result<()> {
+    if schema == schema_id {
+        Ok(())
+    } else {
+        Err(Error::UnsupportedSchema {
+            schema: schema.to_owned(),
+            structure_name: structure_name.to_owned(),
+        })
+    }
+}
+
+/// Node descriptors declare the v1 or the v2 schema, the `nodeSignature` counter-signature requires v2.
+pub(crate) fn validate_node_descriptor_schema(
+    signed_node_descriptor: &SignedNodeDescriptor,
+) -> Result<()> {
+    match (
+        signed_node_descriptor.schema.as_str(),
+        &signed_node_descriptor.node_signature,
+    ) {
+        (SIGNED_NODE_DESCRIPTOR_V2_SCHEMA_ID, _) | (SIGNED_NODE_DESCRIPTOR_SCHEMA_ID, None) => {
+            Ok(())
+        }
+        (SIGNED_NODE_DESCRIPTOR_SCHEMA_ID, Some(_)) => {
+            Err(Error::JsonDoesNotConformToSchema(format!(
+                "Property `nodeSignature` requires schema {SIGNED_NODE_DESCRIPTOR_V2_SCHEMA_ID}"
+            )))
+        }
+        (schema, _) => {
+            validate_schema_id(schema, SIGNED_NODE_DESCRIPTOR_SCHEMA_ID, "node descriptor")
+        }
+    }
+}
+
 /// Validates signed node descriptor.
 /// # Arguments
 /// * `signed_node_descriptor`
-/// * `timestamp` optional timestamp to verify validity of the leaf certificate (last certificate in the chain).
-///    Validity periods of parent (issuer) certificates from the chain must fully include validity period of a child.
+/// * `chain` parsed chain of the signing certificate
+/// * `time_check` optional timestamp to verify validity of the node descriptor, and of its chain when the whole chain is checked.
+///   Validity periods of parent (issuer) certificates from the chain must fully include validity period of a child.
+/// * `cache` optional cache of validated certificate chains
+/// * `verifier` verifies signatures immediately or defers them to a batch
+/// * `require_node_signature` reject node descriptors not counter-signed by the node identity.
+///   The counter-signature is verified whenever present.
+///
+/// Errors are wrapped with the position in the chain, the node descriptor is at depth 0.
 fn validate_signed_node_descriptor(
-    signed_node_descriptor: SignedNodeDescriptor,
-    timestamp: Option<DateTime<Utc>>,
+    signed_node_descriptor: &SignedNodeDescriptor,
+    chain: &ParsedChain,
+    time_check: TimeCheck,
+    cache: Option<&CertificateCache>,
+    verifier: &SignatureVerifier,
+    require_node_signature: bool,
 ) -> Result<ValidatedNodeDescriptor> {
-    let node_descriptor: NodeDescriptor =
-        serde_json::from_value(signed_node_descriptor.node_descriptor.clone())
-            .map_err(|e| Error::JsonDoesNotConformToSchema(e.to_string()))?;
-
-    let signing_certificate = signed_node_descriptor.signature.signer;
-    let validated_certificate = validate_signed_certificate(&signing_certificate, None)?;
+    check_signed_node_descriptor(
+        signed_node_descriptor,
+        chain,
+        time_check,
+        cache,
+        verifier,
+        require_node_signature,
+    )
+    .map_err(|error| match error {
+        Error::InChain { .. } => error,
+        error => Error::InChain {
+            position: ChainPosition::default(),
+            error: Box::new(error),
+        },
+    })
+}
 
-    let leaf_certificate: Certificate = serde_json::from_value(signing_certificate.certificate)
+fn check_signed_node_descriptor(
+    signed_node_descriptor: &SignedNodeDescriptor,
+    chain: &ParsedChain,
+    time_check: TimeCheck,
+    cache: Option<&CertificateCache>,
+    verifier: &SignatureVerifier,
+    require_node_signature: bool,
+) -> Result<ValidatedNodeDescriptor> {
+    let node_descriptor = NodeDescriptor::deserialize(&signed_node_descriptor.node_descriptor)
         .map_err(|e| Error::JsonDoesNotConformToSchema(e.to_string()))?;
-    verify_signature_json(
+
+    let validated_certificate =
+        validate_signing_certificate(chain, time_check.for_signer(), cache, verifier)?;
+
+    verifier.verify_json(
         &signed_node_descriptor.node_descriptor,
-        &signed_node_descriptor.signature.algorithm.encryption,
+        &signed_node_descriptor.signature.algorithm,
         &signed_node_descriptor.signature.value,
-        &leaf_certificate.public_key,
+        chain.leaf_public_key()?,
     )?;
 
     validate_permissions(
@@ -138,84 +669,168 @@ fn validate_signed_node_descriptor(
         &node_descriptor.validity_period,
     )?;
 
-    timestamp
-        .map(|ts| validate_timestamp(&node_descriptor.validity_period, ts))
-        .unwrap_or(Ok(()))?;
+    time_check.validate(&node_descriptor.validity_period)?;
+
+    let possession_proven = match &signed_node_descriptor.node_signature {
+        Some(node_signature) => {
+            verify_signature_json_with_node_id(
+                &signed_node_descriptor.node_descriptor,
+                &node_signature.algorithm,
+                &node_signature.value,
+                &node_descriptor.node_id,
+            )?;
+            true
+        }
+        None if require_node_signature => return Err(Error::MissingNodeSignature),
+        None => false,
+    };
 
     Ok(ValidatedNodeDescriptor {
         certificate_chain_fingerprints: validated_certificate.certificate_chain_fingerprints,
         permissions: node_descriptor.permissions,
         node_id: node_descriptor.node_id,
+        possession_proven,
     })
 }
 
-fn create_certificate_fingerprint(signed_certificate: &SignedCertificate) -> Result<Fingerprint> {
-    create_fingerprint_for_value(&signed_certificate.certificate)
-}
+/// Validates signed manifest.
+/// # Arguments
+/// * `signed_manifest`
+/// * `chain` parsed chain of the signing certificate
+/// * `time_check` optional timestamp to verify validity of the signing certificate, or of its whole chain, and expiration of the manifest.
+///   Outbound access requested by the manifest must be permitted by the signing certificate.
+/// * `cache` optional cache of validated certificate chains
+fn validate_signed_manifest(
+    signed_manifest: &SignedManifest,
+    chain: &ParsedChain,
+    time_check: TimeCheck,
+    cache: Option<&CertificateCache>,
+) -> Result<ValidatedManifest> {
+    let manifest_json = decode_manifest(&signed_manifest.manifest)?;
+    let manifest =
+        Manifest::deserialize(&manifest_json).map_err(|e| Error::InvalidManifest(e.to_string()))?;
 
-fn create_fingerprint_for_value(value: &Value) -> Result<Fingerprint> {
-    create_default_hash(value).map(|binary| binary.encode_hex())
-}
+    let verifier = SignatureVerifier::default();
+    let validated_certificate = validate_signing_certificate(chain, time_check, cache, &verifier)?;
 
-/// Validates signed certificate.
-/// # Arguments
-/// * `signed_certificate`
-/// * `timestamp` optional timestamp to verify validity of the leaf certificate (last certificate in the chain).
-///    Validity periods of parent (issuer) certificates from the chain must fully include validity period of a child.
-fn validate_signed_certificate(
-    signed_certificate: &SignedCertificate,
-    timestamp: Option<DateTime<Utc>>,
-) -> Result<ValidatedCertificate> {
-    let parent = match &signed_certificate.signature.signer {
-        Signer::SelfSigned => {
-            let certificate: Certificate =
-                serde_json::from_value(signed_certificate.certificate.clone())
-                    .map_err(|e| Error::JsonDoesNotConformToSchema(e.to_string()))?;
-            verify_signature_json(
-                &signed_certificate.certificate,
-                &signed_certificate.signature.algorithm.encryption,
-                &signed_certificate.signature.value,
-                &certificate.public_key,
-            )?;
-            ValidatedCertificate {
-                certificate_chain_fingerprints: vec![],
-                permissions: certificate.permissions,
-                key_usage: certificate.key_usage,
-                validity_period: certificate.validity_period,
-                subject: certificate.subject,
-            }
-        }
-        Signer::Certificate(signed_parent) => {
-            let parent: Certificate = serde_json::from_value(signed_parent.certificate.clone())
-                .map_err(|e| Error::JsonDoesNotConformToSchema(e.to_string()))?;
-            verify_signature_json(
-                &signed_certificate.certificate,
-                &signed_certificate.signature.algorithm.encryption,
-                &signed_certificate.signature.value,
-                &parent.public_key,
-            )?;
-            validate_signed_certificate(signed_parent, None)?
+    verifier.verify_json(
+        &Value::String(signed_manifest.manifest.clone()),
+        &signed_manifest.signature.algorithm,
+        &signed_manifest.signature.value,
+        chain.leaf_public_key()?,
+    )?;
+
+    validate_sign_manifest(&validated_certificate.key_usage)?;
+    let outbound_permissions = manifest.outbound_permissions();
+    validate_requested_outbound_permissions(
+        &validated_certificate.permissions,
+        &outbound_permissions,
+    )?;
+
+    match (time_check.timestamp(), manifest.expires_at) {
+        (Some(ts), Some(expires_at)) if ts - time_check.clock_skew() > expires_at => {
+            Err(Error::Expired(expires_at))
         }
-    };
+        _ => Ok(()),
+    }?;
 
-    let certificate: Certificate = serde_json::from_value(signed_certificate.certificate.clone())
+    Ok(ValidatedManifest {
+        certificate_chain_fingerprints: validated_certificate.certificate_chain_fingerprints,
+        manifest: manifest_json,
+        outbound_permissions,
+    })
+}
+
+/// Validates signed revocation list.
+/// # Arguments
+/// * `signed_revocation_list`
+/// * `chain` parsed chain of the signing certificate
+/// * `time_check` optional timestamp to verify validity of the revocation list, and of its chain when the whole chain is checked.
+///   Validity periods of parent (issuer) certificates from the chain must fully include validity period of a child.
+/// * `cache` optional cache of validated certificate chains
+fn validate_signed_revocation_list(
+    signed_revocation_list: &SignedRevocationList,
+    chain: &ParsedChain,
+    time_check: TimeCheck,
+    cache: Option<&CertificateCache>,
+) -> Result<ValidatedRevocationList> {
+    let revocation_list = RevocationList::deserialize(&signed_revocation_list.revocation_list)
         .map_err(|e| Error::JsonDoesNotConformToSchema(e.to_string()))?;
 
-    validate_permissions(&parent.permissions, &certificate.permissions)?;
-    validate_certificates_key_usage(&parent.key_usage, &certificate.key_usage)?;
-    validate_validity_period(&parent.validity_period, &certificate.validity_period)?;
-    timestamp
-        .map(|ts| validate_timestamp(&certificate.validity_period, ts))
-        .unwrap_or(Ok(()))?;
-
-    let mut fingerprints = parent.certificate_chain_fingerprints;
-    fingerprints.push(create_certificate_fingerprint(signed_certificate)?);
-
-    Ok(ValidatedCertificate {
-        certificate_chain_fingerprints: fingerprints,
-        permissions: certificate.permissions,
-        key_usage: certificate.key_usage,
-        validity_period: certificate.validity_period,
-        subject: certificate.subject,
+    let verifier = SignatureVerifier::default();
+    let validated_certificate =
+        validate_signing_certificate(chain, time_check.for_signer(), cache, &verifier)?;
+
+    verifier.verify_json(
+        &signed_revocation_list.revocation_list,
+        &signed_revocation_list.signature.algorithm,
+        &signed_revocation_list.signature.value,
+        chain.leaf_public_key()?,
+    )?;
+
+    validate_sign_revocation_list(&validated_certificate.key_usage)?;
+    validate_validity_period(
+        &validated_certificate.validity_period,
+        &revocation_list.validity_period,
+    )?;
+
+    time_check.validate(&revocation_list.validity_period)?;
+
+    Ok(ValidatedRevocationList {
+        certificate_chain_fingerprints: validated_certificate.certificate_chain_fingerprints,
+        // Chain fingerprints are lowercase hex, revoked fingerprints are compared with them
+        revoked_certificates: revocation_list
+            .revoked_certificates
+            .into_iter()
+            .map(|fingerprint| fingerprint.to_lowercase())
+            .collect(),
+        validity_period: revocation_list.validity_period,
+    })
+}
+
+/// Fingerprint is created with the hash algorithm declared in the signature of the certificate.
+fn create_certificate_fingerprint(signed_certificate: &SignedCertificate) -> Result<Fingerprint> {
+    create_hash(
+        &signed_certificate.certificate,
+        &signed_certificate.signature.algorithm.hash,
+    )
+    .map(|binary| binary.encode_hex())
+}
+
+/// Validates certificate signing a document, positions of errors in its chain are shifted below the document.
+fn validate_signing_certificate(
+    chain: &ParsedChain,
+    time_check: TimeCheck,
+    cache: Option<&CertificateCache>,
+    verifier: &SignatureVerifier,
+) -> Result<ValidatedCertificate> {
+    match cache {
+        Some(cache) => cache.validate(chain, time_check, verifier),
+        None => validate_signed_certificate(chain, time_check, verifier),
+    }
+    .map_err(|error| match error {
+        Error::InChain {
+            mut position,
+            error,
+        } => {
+            position.depth += 1;
+            Error::InChain { position, error }
+        }
+        error => error,
     })
 }
+
+/// Validates signed certificate, see [`report::check_certificate_chain`].
+/// Errors are wrapped with the position in the chain, the certificate is at depth 0.
+/// # Arguments
+/// * `chain` parsed certificate chain, each certificate is deserialized and canonicalized once
+/// * `time_check` optional timestamp to verify validity of the leaf certificate (last certificate in the chain),
+///   or of every certificate when the whole chain is checked.
+///   Validity periods of parent (issuer) certificates from the chain must fully include validity period of a child.
+pub(crate) fn validate_signed_certificate(
+    chain: &ParsedChain,
+    time_check: TimeCheck,
+    verifier: &SignatureVerifier,
+) -> Result<ValidatedCertificate> {
+    check_certificate_chain(chain, time_check, verifier).into_result()
+}
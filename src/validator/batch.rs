@@ -0,0 +1,239 @@
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+
+use serde_json::Value;
+
+use crate::{
+    cryptography::{
+        canonicalize, prepare_batch_signature, verify_batch, verify_signature_canonical_json,
+        BatchSignature,
+    },
+    schemas::{
+        signature::{SignatureAlgorithm, SignedCertificate, SignedNodeDescriptor},
+        SIGNED_CERTIFICATE_SCHEMA_ID,
+    },
+    Key, Result,
+};
+
+use super::{
+    options::ValidationOptions,
+    validate_node_descriptor_schema, validate_schema_id, validate_signed_certificate_with_options,
+    validate_signed_node_descriptor_with_options,
+    validated_data::{ValidatedCertificate, ValidatedNodeDescriptor},
+};
+
+/// Verifies signatures immediately, or collects the Ed25519 ones to be verified later in a batch.
+#[derive(Debug, Default)]
+pub(crate) struct SignatureVerifier {
+    deferred: Option<RefCell<Vec<BatchSignature>>>,
+}
+
+impl SignatureVerifier {
+    pub fn deferred() -> Self {
+        Self {
+            deferred: Some(Default::default()),
+        }
+    }
+
+    pub fn is_deferred(&self) -> bool {
+        self.deferred.is_some()
+    }
+
+    /// See [`verify_signature_canonical_json`], Ed25519 signatures are only checked to be well-formed when verification is deferred.
+    pub fn verify(
+        &self,
+        canonical_json: &[u8],
+        signature_algorithm: &SignatureAlgorithm,
+        signature_value: &[u8],
+        public_key: &Key,
+    ) -> Result<()> {
+        if let Some(deferred) = &self.deferred {
+            let batch_signature = prepare_batch_signature(
+                canonical_json,
+                signature_algorithm,
+                signature_value,
+                public_key,
+            )?;
+            if let Some(batch_signature) = batch_signature {
+                deferred.borrow_mut().push(batch_signature);
+                return Ok(());
+            }
+        }
+        verify_signature_canonical_json(
+            canonical_json,
+            signature_algorithm,
+            signature_value,
+            public_key,
+        )
+    }
+
+    /// Canonicalizes the json and verifies its signature, see [`Self::verify`].
+    pub fn verify_json(
+        &self,
+        value: &Value,
+        signature_algorithm: &SignatureAlgorithm,
+        signature_value: &[u8],
+        public_key: &Key,
+    ) -> Result<()> {
+        self.verify(
+            &canonicalize(value)?,
+            signature_algorithm,
+            signature_value,
+            public_key,
+        )
+    }
+
+    fn into_deferred(self) -> Vec<BatchSignature> {
+        self.deferred.map(RefCell::into_inner).unwrap_or_default()
+    }
+}
+
+/// Validates many signed certificates, verifying their Ed25519 signatures in a batch.
+/// See [`validate_node_descriptors_batch`], results are in the order of the certificates.
+/// # Arguments
+/// * `signed_certificates` certificates to validate
+/// * `options` time of validation, clock skew, maximum chain depth and trusted roots
+pub fn validate_certificates_batch(
+    signed_certificates: &[SignedCertificate],
+    options: &ValidationOptions,
+) -> Vec<Result<ValidatedCertificate>> {
+    validate_batch(signed_certificates, |signed_certificate, verifier| {
+        validate_schema_id(
+            &signed_certificate.schema,
+            SIGNED_CERTIFICATE_SCHEMA_ID,
+            "certificate",
+        )?;
+        validate_signed_certificate_with_options(
+            signed_certificate,
+            options,
+            options.trust_store(),
+            verifier,
+        )
+    })
+}
+
+/// Validates many signed node descriptors, verifying their Ed25519 signatures in a batch.
+/// Structural, permission and validity checks of every node descriptor run first, then the signatures
+/// of those that passed are verified at once. Signatures shared by many node descriptors, like those of
+/// a common certificate chain, are verified once. When the batch fails each signature is verified on its own.
+/// Node descriptors that failed are validated again one by one, so each result is the same as
+/// of [`super::validate_node_descriptor_with_options`]. Results are in the order of the node descriptors.
+/// # Arguments
+/// * `signed_node_descriptors` node descriptors to validate
+/// * `options` time of validation, clock skew, maximum chain depth, trusted roots and whether the node signature is required
+pub fn validate_node_descriptors_batch(
+    signed_node_descriptors: &[SignedNodeDescriptor],
+    options: &ValidationOptions,
+) -> Vec<Result<ValidatedNodeDescriptor>> {
+    validate_batch(
+        signed_node_descriptors,
+        |signed_node_descriptor, verifier| {
+            validate_node_descriptor_schema(signed_node_descriptor)?;
+            validate_signed_node_descriptor_with_options(
+                signed_node_descriptor,
+                options,
+                options.trust_store(),
+                verifier,
+            )
+        },
+    )
+}
+
+fn validate_batch<D, T>(
+    documents: &[D],
+    validate: impl Fn(&D, &SignatureVerifier) -> Result<T>,
+) -> Vec<Result<T>> {
+    let deferred: Vec<_> = documents
+        .iter()
+        .map(|document| {
+            let verifier = SignatureVerifier::deferred();
+            let result = validate(document, &verifier);
+            (result, verifier.into_deferred())
+        })
+        .collect();
+
+    let signatures: HashSet<&BatchSignature> = deferred
+        .iter()
+        .filter(|(result, _)| result.is_ok())
+        .flat_map(|(_, signatures)| signatures)
+        .collect();
+    let signatures: Vec<_> = signatures.into_iter().collect();
+    let verified: HashMap<&BatchSignature, bool> = if verify_batch(&signatures).is_ok() {
+        signatures.into_iter().map(|s| (s, true)).collect()
+    } else {
+        signatures
+            .into_iter()
+            .map(|s| (s, verify_batch(&[s]).is_ok()))
+            .collect()
+    };
+
+    let accepted: Vec<bool> = deferred
+        .iter()
+        .map(|(result, signatures)| {
+            result.is_ok() && signatures.iter().all(|s| verified.get(s) == Some(&true))
+        })
+        .collect();
+
+    documents
+        .iter()
+        .zip(deferred)
+        .zip(accepted)
+        .map(|((document, (result, _)), accepted)| {
+            if accepted {
+                result
+            } else {
+                validate(document, &SignatureVerifier::default())
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+
+    use serde_json::json;
+
+    use crate::{create_key_pair, sign_json, Error};
+
+    #[test]
+    fn defer_well_formed_ed25519_signatures() {
+        let key_pair = create_key_pair();
+        let other_key_pair = create_key_pair();
+        let value = json!({ "data": 1 });
+        let (algorithm, signature) = sign_json(&value, &other_key_pair.private_key).unwrap();
+        let verifier = SignatureVerifier::deferred();
+
+        let deferred_result =
+            verifier.verify_json(&value, &algorithm, &signature, &key_pair.public_key);
+        let malformed_result =
+            verifier.verify_json(&value, &algorithm, &[0; 3], &key_pair.public_key);
+
+        assert_eq!(deferred_result, Ok(()));
+        assert_eq!(malformed_result, Err(Error::InvalidSignatureValue));
+        let deferred = verifier.into_deferred();
+        assert_eq!(deferred.len(), 1);
+        assert_eq!(
+            verify_batch(&deferred.iter().collect::<Vec<_>>()),
+            Err(Error::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn verify_signatures_immediately_by_default() {
+        let key_pair = create_key_pair();
+        let value = json!({ "data": 1 });
+        let (algorithm, signature) = sign_json(&value, &key_pair.private_key).unwrap();
+        let verifier = SignatureVerifier::default();
+
+        let result = verifier.verify_json(
+            &json!({ "data": 2 }),
+            &algorithm,
+            &signature,
+            &key_pair.public_key,
+        );
+
+        assert_eq!(result, Err(Error::InvalidSignature));
+        assert!(verifier.into_deferred().is_empty());
+    }
+}
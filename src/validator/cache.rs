@@ -0,0 +1,274 @@
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+use crate::{
+    schemas::{certificate::Fingerprint, signature::SignedCertificate},
+    ChainPosition, Error, Result,
+};
+
+use super::{
+    batch::SignatureVerifier, chain::ParsedChain, options::TimeCheck, validate_signed_certificate,
+    validated_data::ValidatedCertificate,
+};
+
+/// Thread-safe cache of validated certificate chains, keyed by the fingerprint of the certificate.
+/// Only the checks that do not depend on the validation options are cached: schema, signatures and constraints of the chain.
+/// Validity at the timestamp, chain depth, trusted roots and revocation lists of the options are checked on every validation.
+/// An entry is used only for exactly the same chain, a certificate signed again by another signer is validated anew.
+#[derive(Debug)]
+pub struct CertificateCache {
+    max_entries: usize,
+    ttl: Duration,
+    entries: Mutex<HashMap<Fingerprint, CacheEntry>>,
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    signed_certificate: SignedCertificate,
+    validated_certificate: ValidatedCertificate,
+    inserted_at: Instant,
+}
+
+impl CacheEntry {
+    fn is_expired(&self, ttl: Duration) -> bool {
+        self.inserted_at.elapsed() >= ttl
+    }
+}
+
+impl CertificateCache {
+    /// # Arguments
+    /// * `max_entries` number of chains kept, the oldest entry is evicted when the cache is full
+    /// * `ttl` time after which a chain is validated again
+    pub fn new(max_entries: usize, ttl: Duration) -> Self {
+        Self {
+            max_entries,
+            ttl,
+            entries: Default::default(),
+        }
+    }
+
+    /// Number of cached chains, including the expired ones not evicted yet.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    /// Validates signed certificate like [`validate_signed_certificate`], verifying signatures of the chain only when it is not cached.
+    /// Validity periods in the chain include those of their children, so checking the timestamp against
+    /// the certificate covers the whole chain. Chains with signatures deferred to a batch are not cached.
+    pub(crate) fn validate(
+        &self,
+        chain: &ParsedChain,
+        time_check: TimeCheck,
+        verifier: &SignatureVerifier,
+    ) -> Result<ValidatedCertificate> {
+        let signed_certificate = chain.leaf().signed_certificate;
+        let fingerprint = chain.leaf().fingerprint.clone().ok();
+        let cached = fingerprint
+            .as_ref()
+            .and_then(|fingerprint| self.get(fingerprint, signed_certificate));
+        let validated_certificate = match cached {
+            Some(validated_certificate) => validated_certificate,
+            None => {
+                let validated_certificate =
+                    validate_signed_certificate(chain, TimeCheck::default(), verifier)?;
+                if let (Some(fingerprint), false) = (&fingerprint, verifier.is_deferred()) {
+                    self.insert(fingerprint, signed_certificate, &validated_certificate);
+                }
+                validated_certificate
+            }
+        };
+        time_check
+            .validate(&validated_certificate.validity_period)
+            .map_err(|error| Error::InChain {
+                position: ChainPosition {
+                    depth: 0,
+                    fingerprint,
+                    display_name: Some(validated_certificate.subject.display_name.clone()),
+                },
+                error: Box::new(error),
+            })?;
+        Ok(validated_certificate)
+    }
+
+    fn get(
+        &self,
+        fingerprint: &Fingerprint,
+        signed_certificate: &SignedCertificate,
+    ) -> Option<ValidatedCertificate> {
+        let mut entries = self.lock();
+        match entries.get(fingerprint) {
+            Some(entry) if entry.is_expired(self.ttl) => {
+                entries.remove(fingerprint);
+                None
+            }
+            Some(entry) if entry.signed_certificate == *signed_certificate => {
+                Some(entry.validated_certificate.clone())
+            }
+            _ => None,
+        }
+    }
+
+    fn insert(
+        &self,
+        fingerprint: &Fingerprint,
+        signed_certificate: &SignedCertificate,
+        validated_certificate: &ValidatedCertificate,
+    ) {
+        if self.max_entries == 0 {
+            return;
+        }
+        let mut entries = self.lock();
+        if !entries.contains_key(fingerprint) && entries.len() >= self.max_entries {
+            entries.retain(|_, entry| !entry.is_expired(self.ttl));
+            if entries.len() >= self.max_entries {
+                let oldest = entries
+                    .iter()
+                    .min_by_key(|(_, entry)| entry.inserted_at)
+                    .map(|(fingerprint, _)| fingerprint.clone());
+                if let Some(oldest) = oldest {
+                    entries.remove(&oldest);
+                }
+            }
+        }
+        entries.insert(
+            fingerprint.clone(),
+            CacheEntry {
+                signed_certificate: signed_certificate.clone(),
+                validated_certificate: validated_certificate.clone(),
+                inserted_at: Instant::now(),
+            },
+        );
+    }
+
+    /// Entries are replaced as a whole, so they stay consistent even if another thread panicked holding the lock.
+    fn lock(&self) -> MutexGuard<'_, HashMap<Fingerprint, CacheEntry>> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+
+    use crate::validator::options::ValidationOptions;
+    use utils::*;
+
+    #[test]
+    fn validate_chain_once_and_reuse_the_result() {
+        let cache = CertificateCache::new(10, Duration::from_secs(60));
+        let chain = chain();
+
+        let first = cache
+            .validate(&parse(&chain), at(now()), &immediate())
+            .unwrap();
+        let second = cache
+            .validate(&parse(&chain), at(now()), &immediate())
+            .unwrap();
+
+        assert_eq!(cache.len(), 1);
+        assert_eq!(first, second);
+        assert_eq!(
+            first,
+            validate_signed_certificate(&parse(&chain), at(now()), &immediate()).unwrap()
+        );
+    }
+
+    #[test]
+    fn check_timestamp_of_cached_chain() {
+        let cache = CertificateCache::new(10, Duration::from_secs(60));
+        let chain = chain();
+        let expired = "2031-01-01T00:00:00Z".parse().unwrap();
+        cache
+            .validate(&parse(&chain), at(now()), &immediate())
+            .unwrap();
+
+        let result = cache.validate(&parse(&chain), at(expired), &immediate());
+
+        assert_eq!(
+            result.unwrap_err(),
+            validate_signed_certificate(&parse(&chain), at(expired), &immediate()).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn validate_chain_with_other_signature_of_cached_certificate() {
+        let cache = CertificateCache::new(10, Duration::from_secs(60));
+        let chain = chain();
+        cache
+            .validate(&parse(&chain), at(now()), &immediate())
+            .unwrap();
+        let mut tampered = chain.clone();
+        tampered.signature.value[0] ^= 1;
+
+        let result = cache.validate(&parse(&tampered), at(now()), &immediate());
+
+        assert_eq!(
+            result.unwrap_err().without_chain_position(),
+            Error::InvalidSignature
+        );
+    }
+
+    #[test]
+    fn not_reuse_expired_entries() {
+        let cache = CertificateCache::new(10, Duration::ZERO);
+        let chain = chain();
+        cache
+            .validate(&parse(&chain), at(now()), &immediate())
+            .unwrap();
+        let fingerprint = fingerprint(&chain);
+
+        assert_eq!(cache.get(&fingerprint, &chain), None);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn evict_oldest_entry_when_full() {
+        let cache = CertificateCache::new(1, Duration::from_secs(60));
+        let first = chain();
+        let second = chain();
+
+        cache
+            .validate(&parse(&first), at(now()), &immediate())
+            .unwrap();
+        cache
+            .validate(&parse(&second), at(now()), &immediate())
+            .unwrap();
+
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.get(&fingerprint(&first), &first), None);
+        assert!(cache.get(&fingerprint(&second), &second).is_some());
+    }
+
+    mod utils {
+        use super::*;
+
+        use chrono::{DateTime, Utc};
+
+        pub use crate::test_utils::{chain, now};
+
+        pub fn parse(chain: &SignedCertificate) -> ParsedChain<'_> {
+            ParsedChain::parse(chain)
+        }
+
+        pub fn fingerprint(chain: &SignedCertificate) -> Fingerprint {
+            parse(chain).leaf().fingerprint.clone().unwrap()
+        }
+
+        pub fn immediate() -> SignatureVerifier {
+            SignatureVerifier::default()
+        }
+
+        pub fn at(timestamp: DateTime<Utc>) -> TimeCheck {
+            ValidationOptions::new().at(timestamp).time_check()
+        }
+    }
+}
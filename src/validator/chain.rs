@@ -0,0 +1,143 @@
+use hex::ToHex;
+use serde::Deserialize;
+
+use crate::{
+    cryptography::{canonicalize, create_digest, Key},
+    schemas::{
+        certificate::{Certificate, Fingerprint},
+        signature::{SignedCertificate, Signer},
+    },
+    ChainPosition, Error, Result,
+};
+
+/// Certificate of a chain, deserialized and canonicalized once.
+/// The canonical json is both the signed message and the input of the fingerprint.
+pub(crate) struct ParsedCertificate<'a> {
+    pub signed_certificate: &'a SignedCertificate,
+    /// Deserialization error is kept as a message, it is reported at the position of the certificate
+    pub certificate: std::result::Result<Certificate, String>,
+    pub canonical_json: Result<Vec<u8>>,
+    pub fingerprint: Result<Fingerprint>,
+}
+
+impl<'a> ParsedCertificate<'a> {
+    fn parse(signed_certificate: &'a SignedCertificate) -> Self {
+        let canonical_json = canonicalize(&signed_certificate.certificate);
+        let fingerprint = canonical_json
+            .as_ref()
+            .map(|canonical_json| {
+                create_digest(canonical_json, &signed_certificate.signature.algorithm.hash)
+                    .encode_hex()
+            })
+            .map_err(Clone::clone);
+        Self {
+            signed_certificate,
+            certificate: Certificate::deserialize(&signed_certificate.certificate)
+                .map_err(|e| e.to_string()),
+            canonical_json,
+            fingerprint,
+        }
+    }
+
+    pub fn certificate(&self) -> Result<&Certificate> {
+        self.certificate
+            .as_ref()
+            .map_err(|e| Error::JsonDoesNotConformToSchema(e.clone()))
+    }
+
+    pub fn position(&self, depth: usize) -> ChainPosition {
+        ChainPosition {
+            depth,
+            fingerprint: self.fingerprint.clone().ok(),
+            display_name: self
+                .certificate
+                .as_ref()
+                .ok()
+                .map(|certificate| certificate.subject.display_name.clone()),
+        }
+    }
+}
+
+/// Certificate chain parsed once for all checks, ordered from the leaf (depth 0) to the self-signed root.
+pub(crate) struct ParsedChain<'a> {
+    links: Vec<ParsedCertificate<'a>>,
+}
+
+impl<'a> ParsedChain<'a> {
+    pub fn parse(signed_certificate: &'a SignedCertificate) -> Self {
+        let mut links = vec![];
+        let mut current = signed_certificate;
+        loop {
+            links.push(ParsedCertificate::parse(current));
+            match &current.signature.signer {
+                Signer::SelfSigned => return Self { links },
+                Signer::Certificate(parent) => current = parent,
+            }
+        }
+    }
+
+    pub fn links(&self) -> &[ParsedCertificate<'a>] {
+        &self.links
+    }
+
+    pub fn leaf(&self) -> &ParsedCertificate<'a> {
+        &self.links[0]
+    }
+
+    pub fn root(&self) -> &ParsedCertificate<'a> {
+        &self.links[self.root_depth()]
+    }
+
+    /// Number of certificates above the leaf, 0 for a self-signed certificate.
+    pub fn root_depth(&self) -> usize {
+        self.links.len() - 1
+    }
+
+    /// Public key of the leaf certificate, which signs the documents.
+    pub fn leaf_public_key(&self) -> Result<&Key> {
+        self.leaf()
+            .certificate()
+            .map(|certificate| &certificate.public_key)
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+
+    use crate::{
+        create_key_pair,
+        schemas::{certificate::key_usage::KeyUsage, permissions::Permissions},
+        test_utils::certificate,
+        validator::create_certificate_fingerprint,
+        CertificateBuilder,
+    };
+
+    #[test]
+    fn parse_every_certificate_of_the_chain_from_the_leaf() {
+        let root_key_pair = create_key_pair();
+        let leaf_key_pair = create_key_pair();
+        let root =
+            CertificateBuilder::new(certificate(&root_key_pair, KeyUsage::All, Permissions::All))
+                .self_sign(&root_key_pair.private_key)
+                .unwrap();
+        let leaf =
+            CertificateBuilder::new(certificate(&leaf_key_pair, KeyUsage::All, Permissions::All))
+                .sign(root.clone(), &root_key_pair.private_key)
+                .unwrap();
+
+        let chain = ParsedChain::parse(&leaf);
+
+        assert_eq!(chain.root_depth(), 1);
+        assert_eq!(chain.leaf_public_key(), Ok(&leaf_key_pair.public_key));
+        assert_eq!(chain.root().signed_certificate, &root);
+        assert_eq!(
+            chain.leaf().fingerprint,
+            create_certificate_fingerprint(&leaf)
+        );
+        assert_eq!(
+            chain.root().fingerprint,
+            create_certificate_fingerprint(&root)
+        );
+    }
+}
@@ -0,0 +1,188 @@
+use serde_json::Value;
+
+use crate::schemas::manifest::decode_manifest;
+use crate::schemas::permissions::{
+    OutboundPermissions, PermissionDetails, Permissions, UrlPattern,
+};
+
+/// Issue of a document that does not affect its validity.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum LintWarning {
+    /// Outbound permission entry or URL requested by a manifest is not written in its normalized form, it is compared as `normalized`
+    NonNormalizedUrl {
+        /// Position in the chain of the certificate, node descriptor or manifest, 0 is the linted document
+        depth: usize,
+        url: String,
+        normalized: String,
+    },
+}
+
+/// Looks for issues in the permissions of a signed certificate or node descriptor, in the outbound URLs requested by a signed manifest
+/// and in the certificates of its chain.
+/// Parts that do not conform to the schema are skipped, these are reported by the validation.
+/// # Arguments
+/// * `value` signed certificate, node descriptor or manifest
+pub fn lint_document(value: &Value) -> Vec<LintWarning> {
+    let mut warnings = vec![];
+    let mut current = Some(value);
+    let mut depth = 0;
+    while let Some(document) = current {
+        let signed_data = document
+            .get("certificate")
+            .or_else(|| document.get("nodeDescriptor"));
+        if let Some(permissions) = signed_data.and_then(|data| data.get("permissions")) {
+            if let Ok(permissions) = serde_json::from_value(permissions.clone()) {
+                warnings.extend(lint_permissions(&permissions, depth));
+            }
+        }
+        if let Some(manifest) = document.get("manifest").and_then(Value::as_str) {
+            warnings.extend(lint_manifest(manifest, depth));
+        }
+        current = document
+            .get("signature")
+            .and_then(|signature| signature.get("signer"))
+            .filter(|signer| signer.is_object());
+        depth += 1;
+    }
+    warnings
+}
+
+fn lint_permissions(permissions: &Permissions, depth: usize) -> Vec<LintWarning> {
+    match permissions {
+        Permissions::Object(PermissionDetails {
+            outbound: Some(OutboundPermissions::Urls(urls)),
+            ..
+        }) => {
+            let mut urls: Vec<_> = urls.iter().filter(|url| !url.is_normalized()).collect();
+            urls.sort();
+            urls.into_iter()
+                .map(|url| LintWarning::NonNormalizedUrl {
+                    depth,
+                    url: url.source().to_string(),
+                    normalized: url.to_string(),
+                })
+                .collect()
+        }
+        _ => vec![],
+    }
+}
+
+fn lint_manifest(manifest: &str, depth: usize) -> Vec<LintWarning> {
+    let Ok(manifest) = decode_manifest(manifest) else {
+        return vec![];
+    };
+    let Some(urls) = manifest
+        .pointer("/compManifest/net/inet/out/urls")
+        .and_then(Value::as_array)
+    else {
+        return vec![];
+    };
+    let mut urls: Vec<UrlPattern> = urls
+        .iter()
+        .filter_map(Value::as_str)
+        .filter_map(|url| url.parse().ok())
+        .filter(|url: &UrlPattern| !url.is_normalized())
+        .collect();
+    urls.sort();
+    urls.into_iter()
+        .map(|url| LintWarning::NonNormalizedUrl {
+            depth,
+            url: url.source().to_string(),
+            normalized: url.to_string(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use serde_json::json;
+
+    #[test]
+    fn warn_about_non_normalized_urls_in_the_chain() {
+        let document = json!({
+            "nodeDescriptor": {
+                "permissions": { "outbound": { "urls": ["https://example.net/", "https://Example.net/api/"] } }
+            },
+            "signature": {
+                "signer": {
+                    "certificate": {
+                        "permissions": { "outbound": { "urls": ["https://example.net:443/*"] } }
+                    },
+                    "signature": { "signer": "self" }
+                }
+            }
+        });
+
+        let warnings = lint_document(&document);
+
+        assert_eq!(
+            warnings,
+            vec![
+                LintWarning::NonNormalizedUrl {
+                    depth: 0,
+                    url: "https://Example.net/api/".into(),
+                    normalized: "https://example.net/api".into(),
+                },
+                LintWarning::NonNormalizedUrl {
+                    depth: 1,
+                    url: "https://example.net:443/*".into(),
+                    normalized: "https://example.net/*".into(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn warn_about_non_normalized_urls_requested_by_manifest() {
+        let manifest = json!({
+            "version": "0.1.0",
+            "compManifest": {
+                "net": { "inet": { "out": { "urls": ["https://example.net/api", "HTTPS://example.net:443/"] } } }
+            }
+        });
+        let document = json!({
+            "manifest": STANDARD.encode(manifest.to_string()),
+            "signature": {
+                "signer": {
+                    "certificate": {
+                        "permissions": { "outbound": { "urls": ["https://Example.net/"] } }
+                    },
+                    "signature": { "signer": "self" }
+                }
+            }
+        });
+
+        let warnings = lint_document(&document);
+
+        assert_eq!(
+            warnings,
+            vec![
+                LintWarning::NonNormalizedUrl {
+                    depth: 0,
+                    url: "HTTPS://example.net:443/".into(),
+                    normalized: "https://example.net/".into(),
+                },
+                LintWarning::NonNormalizedUrl {
+                    depth: 1,
+                    url: "https://Example.net/".into(),
+                    normalized: "https://example.net/".into(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn not_warn_about_normalized_or_invalid_permissions() {
+        let document = json!({
+            "certificate": {
+                "permissions": { "outbound": { "urls": ["https://example.net/api", "not a url"] } }
+            },
+            "signature": { "signer": "self" }
+        });
+
+        assert_eq!(lint_document(&document), vec![]);
+    }
+}
@@ -0,0 +1,259 @@
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::{
+    schemas::validity_period::{validator::validate_timestamp_with_clock_skew, ValidityPeriod},
+    Error, Result,
+};
+
+use super::{cache::CertificateCache, revocation::RevocationLists, trust_store::TrustStore};
+
+/// Source of the time the documents are validated at.
+pub trait Clock: Debug + Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// Current time of the system.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// Clock stopped at a point in time, useful in tests.
+#[derive(Debug, Clone, Copy)]
+pub struct FixedClock(pub DateTime<Utc>);
+
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.0
+    }
+}
+
+/// Options of validating a document and its certificate chain.
+/// The default options do not check validity at a point in time, do not limit the chain, trust any root
+/// and do not check revocation.
+#[derive(Debug, Clone, Default)]
+pub struct ValidationOptions {
+    clock: Option<Arc<dyn Clock>>,
+    clock_skew: Duration,
+    check_whole_chain: bool,
+    max_chain_depth: Option<usize>,
+    require_node_signature: bool,
+    trust_store: Option<TrustStore>,
+    revocation_lists: RevocationLists,
+    cache: Option<Arc<CertificateCache>>,
+}
+
+impl ValidationOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Verifies validity at the given timestamp.
+    pub fn at(self, timestamp: DateTime<Utc>) -> Self {
+        self.with_clock(FixedClock(timestamp))
+    }
+
+    /// Verifies validity at the time of the clock, read once per validation.
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Some(Arc::new(clock));
+        self
+    }
+
+    /// Accepts documents that are not valid yet or expired by at most `clock_skew`.
+    pub fn with_clock_skew(mut self, clock_skew: Duration) -> Self {
+        self.clock_skew = clock_skew;
+        self
+    }
+
+    /// Verifies validity of every certificate of the chain at the timestamp, not just of the validated document.
+    pub fn with_whole_chain_checked(mut self, check_whole_chain: bool) -> Self {
+        self.check_whole_chain = check_whole_chain;
+        self
+    }
+
+    /// Rejects documents with more than `max_chain_depth` certificates above them in the chain.
+    /// A self-signed certificate has depth 0, a node descriptor signed by a root certificate has depth 1.
+    pub fn with_max_chain_depth(mut self, max_chain_depth: usize) -> Self {
+        self.max_chain_depth = Some(max_chain_depth);
+        self
+    }
+
+    /// Rejects node descriptors not counter-signed by the node identity, the counter-signature is verified whenever present.
+    pub fn with_node_signature_required(mut self, require_node_signature: bool) -> Self {
+        self.require_node_signature = require_node_signature;
+        self
+    }
+
+    /// Requires the chain to be anchored in one of the trusted roots.
+    pub fn with_trust_store(mut self, trust_store: TrustStore) -> Self {
+        self.trust_store = Some(trust_store);
+        self
+    }
+
+    /// Rejects chains containing a certificate revoked by one of the revocation lists.
+    pub fn with_revocation_lists(mut self, revocation_lists: RevocationLists) -> Self {
+        self.revocation_lists = revocation_lists;
+        self
+    }
+
+    /// Skips verification of certificate chains already validated, the cache can be shared by many validations.
+    pub fn with_cache(mut self, cache: Arc<CertificateCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    pub(crate) fn require_node_signature(&self) -> bool {
+        self.require_node_signature
+    }
+
+    pub(crate) fn trust_store(&self) -> Option<&TrustStore> {
+        self.trust_store.as_ref()
+    }
+
+    pub(crate) fn revocation_lists(&self) -> &RevocationLists {
+        &self.revocation_lists
+    }
+
+    pub(crate) fn cache(&self) -> Option<&CertificateCache> {
+        self.cache.as_deref()
+    }
+
+    pub(crate) fn time_check(&self) -> TimeCheck {
+        TimeCheck {
+            timestamp: self.clock.as_ref().map(|clock| clock.now()),
+            clock_skew: self.clock_skew,
+            check_whole_chain: self.check_whole_chain,
+        }
+    }
+
+    /// # Arguments
+    /// * `depth` depth of the root certificate, the validated document is at depth 0
+    pub(crate) fn validate_chain_depth(&self, depth: usize) -> Result<()> {
+        match self.max_chain_depth {
+            Some(max_depth) if depth > max_depth => Err(Error::ChainTooDeep { depth, max_depth }),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Options of the validators taking an optional timestamp.
+impl From<Option<DateTime<Utc>>> for ValidationOptions {
+    fn from(timestamp: Option<DateTime<Utc>>) -> Self {
+        match timestamp {
+            Some(timestamp) => Self::new().at(timestamp),
+            None => Self::new(),
+        }
+    }
+}
+
+/// Validity checks of a single validation, with the time read from the clock.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct TimeCheck {
+    timestamp: Option<DateTime<Utc>>,
+    clock_skew: Duration,
+    pub check_whole_chain: bool,
+}
+
+impl TimeCheck {
+    pub fn timestamp(&self) -> Option<DateTime<Utc>> {
+        self.timestamp
+    }
+
+    pub fn clock_skew(&self) -> Duration {
+        self.clock_skew
+    }
+
+    pub fn validate(&self, validity_period: &ValidityPeriod) -> Result<()> {
+        match self.timestamp {
+            Some(timestamp) => {
+                validate_timestamp_with_clock_skew(validity_period, timestamp, self.clock_skew)
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Certificates signing a document are checked only when the whole chain is checked,
+    /// their validity periods include the validity period of the document.
+    pub fn for_signer(&self) -> Self {
+        if self.check_whole_chain {
+            *self
+        } else {
+            Self {
+                timestamp: None,
+                ..*self
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+
+    use test_case::test_case;
+
+    fn period() -> ValidityPeriod {
+        ValidityPeriod {
+            not_before: "2024-01-01T00:00:00Z".parse().unwrap(),
+            not_after: "2024-12-31T00:00:00Z".parse().unwrap(),
+        }
+    }
+
+    #[test_case("2023-12-31T23:55:00Z", true)]
+    #[test_case("2023-12-31T23:50:00Z", false)]
+    #[test_case("2024-12-31T00:05:00Z", true)]
+    #[test_case("2024-12-31T00:10:00Z", false)]
+    fn tolerate_clock_skew(timestamp: &str, valid: bool) {
+        let options = ValidationOptions::new()
+            .at(timestamp.parse().unwrap())
+            .with_clock_skew(Duration::minutes(5));
+
+        assert_eq!(options.time_check().validate(&period()).is_ok(), valid);
+    }
+
+    #[test]
+    fn read_time_from_clock() {
+        #[derive(Debug)]
+        struct ExpiredClock;
+
+        impl Clock for ExpiredClock {
+            fn now(&self) -> DateTime<Utc> {
+                "2025-01-01T00:00:00Z".parse().unwrap()
+            }
+        }
+
+        let options = ValidationOptions::new().with_clock(ExpiredClock);
+
+        assert_eq!(
+            options.time_check().validate(&period()),
+            Err(Error::Expired(period().not_after))
+        );
+    }
+
+    #[test]
+    fn skip_validity_check_without_clock() {
+        let time_check = ValidationOptions::from(None).time_check();
+
+        assert!(time_check.validate(&period()).is_ok());
+    }
+
+    #[test_case(None, 5, true)]
+    #[test_case(Some(2), 2, true)]
+    #[test_case(Some(2), 3, false)]
+    fn limit_chain_depth(max_chain_depth: Option<usize>, depth: usize, valid: bool) {
+        let options = ValidationOptions::new();
+        let options = match max_chain_depth {
+            Some(max_chain_depth) => options.with_max_chain_depth(max_chain_depth),
+            None => options,
+        };
+
+        assert_eq!(options.validate_chain_depth(depth).is_ok(), valid);
+    }
+}
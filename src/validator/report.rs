@@ -0,0 +1,355 @@
+use crate::{
+    schemas::{
+        certificate::key_usage::validator::validate_certificates_key_usage,
+        permissions::validator::validate_permissions,
+        validity_period::validator::validate_validity_period,
+    },
+    ChainPosition, Error, Result,
+};
+
+use super::{
+    batch::SignatureVerifier, chain::ParsedChain, options::TimeCheck,
+    validated_data::ValidatedCertificate,
+};
+
+/// Check performed on a certificate of the chain.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ValidationCheck {
+    /// Certificate conforms to the schema
+    Schema,
+    /// Signature of the certificate is verified by the public key of its signer
+    Signature,
+    /// Permissions are included in those of the signer
+    Permissions,
+    /// Key usage is included in that of the signer, which has to be permitted to sign certificates
+    KeyUsage,
+    /// Validity period is included in that of the signer
+    ValidityPeriod,
+    /// Certificate is valid at the requested timestamp
+    Timestamp,
+}
+
+/// Failed check of a certificate in the chain.
+#[derive(Debug, PartialEq)]
+pub struct Finding {
+    /// Position of the certificate in the chain, 0 is the validated (leaf) certificate
+    pub position: ChainPosition,
+    pub check: ValidationCheck,
+    pub error: Error,
+}
+
+/// Outcome of checking every certificate of a chain.
+#[derive(Debug, PartialEq)]
+pub struct ValidationReport {
+    /// Failed checks in the order the chain is validated, from the signatures towards the root and back to the leaf
+    pub findings: Vec<Finding>,
+    /// Present when there are no findings
+    pub validated_certificate: Option<ValidatedCertificate>,
+}
+
+impl ValidationReport {
+    pub fn is_valid(&self) -> bool {
+        self.findings.is_empty()
+    }
+
+    /// Returns the validated certificate or the error of the first finding with its position in the chain.
+    pub fn into_result(self) -> Result<ValidatedCertificate> {
+        match (self.findings.into_iter().next(), self.validated_certificate) {
+            (Some(finding), _) => Err(Error::InChain {
+                position: finding.position,
+                error: Box::new(finding.error),
+            }),
+            (None, Some(validated_certificate)) => Ok(validated_certificate),
+            (None, None) => unreachable!("report without findings contains the certificate"),
+        }
+    }
+
+    pub(crate) fn from_error(check: ValidationCheck, error: Error) -> Self {
+        Self {
+            findings: vec![Finding {
+                position: ChainPosition::default(),
+                check,
+                error,
+            }],
+            validated_certificate: None,
+        }
+    }
+}
+
+struct ChainChecker<'a> {
+    chain: &'a ParsedChain<'a>,
+    verifier: &'a SignatureVerifier,
+    findings: Vec<Finding>,
+    schema_reported: Vec<bool>,
+}
+
+impl<'a> ChainChecker<'a> {
+    fn new(chain: &'a ParsedChain<'a>, verifier: &'a SignatureVerifier) -> Self {
+        let schema_reported = vec![false; chain.links().len()];
+        Self {
+            chain,
+            verifier,
+            findings: vec![],
+            schema_reported,
+        }
+    }
+
+    fn record(&mut self, depth: usize, check: ValidationCheck, result: Result<()>) {
+        if let Err(error) = result {
+            self.findings.push(Finding {
+                position: self.chain.links()[depth].position(depth),
+                check,
+                error,
+            });
+        }
+    }
+
+    /// Schema errors are reported once, when the certificate is first needed.
+    fn check_schema(&mut self, depth: usize) {
+        if let Err(error) = self.chain.links()[depth].certificate() {
+            if !self.schema_reported[depth] {
+                self.schema_reported[depth] = true;
+                self.record(depth, ValidationCheck::Schema, Err(error));
+            }
+        }
+    }
+
+    fn check_signature(&mut self, depth: usize, signer_depth: usize) {
+        if let Ok(signer) = &self.chain.links()[signer_depth].certificate {
+            let link = &self.chain.links()[depth];
+            let result =
+                link.canonical_json
+                    .as_ref()
+                    .map_err(Clone::clone)
+                    .and_then(|canonical_json| {
+                        self.verifier.verify(
+                            canonical_json,
+                            &link.signed_certificate.signature.algorithm,
+                            &link.signed_certificate.signature.value,
+                            &signer.public_key,
+                        )
+                    });
+            self.record(depth, ValidationCheck::Signature, result);
+        }
+    }
+
+    fn check_constraints(&mut self, depth: usize, parent_depth: usize) {
+        let links = self.chain.links();
+        if let (Ok(parent), Ok(certificate)) =
+            (&links[parent_depth].certificate, &links[depth].certificate)
+        {
+            let results = [
+                (
+                    ValidationCheck::Permissions,
+                    validate_permissions(&parent.permissions, &certificate.permissions),
+                ),
+                (
+                    ValidationCheck::KeyUsage,
+                    validate_certificates_key_usage(&parent.key_usage, &certificate.key_usage),
+                ),
+                (
+                    ValidationCheck::ValidityPeriod,
+                    validate_validity_period(&parent.validity_period, &certificate.validity_period),
+                ),
+            ];
+            for (check, result) in results {
+                self.record(depth, check, result);
+            }
+        }
+    }
+
+    fn check_timestamp(&mut self, depth: usize, time_check: &TimeCheck) {
+        if let Ok(certificate) = &self.chain.links()[depth].certificate {
+            let result = time_check.validate(&certificate.validity_period);
+            self.record(depth, ValidationCheck::Timestamp, result);
+        }
+    }
+
+    fn into_report(self) -> ValidationReport {
+        let validated_certificate = if self.findings.is_empty() {
+            validated_certificate(self.chain)
+        } else {
+            None
+        };
+        ValidationReport {
+            findings: self.findings,
+            validated_certificate,
+        }
+    }
+}
+
+/// Checks every certificate of the chain without stopping at the first failure.
+/// Findings are ordered as the checks are performed, so the first one is the error of fail-fast validation.
+/// Certificate chain fingerprints of the validated certificate are ordered from the root.
+/// # Arguments
+/// * `chain` parsed certificate chain
+/// * `time_check` optional timestamp to verify validity of the leaf certificate (first certificate in the chain),
+///   or of every certificate when the whole chain is checked.
+///   Validity periods of parent (issuer) certificates from the chain must fully include validity period of a child.
+/// * `verifier` verifies signatures immediately or defers them to a batch
+pub(crate) fn check_certificate_chain<'a>(
+    chain: &'a ParsedChain<'a>,
+    time_check: TimeCheck,
+    verifier: &'a SignatureVerifier,
+) -> ValidationReport {
+    let mut checker = ChainChecker::new(chain, verifier);
+    let root_depth = chain.root_depth();
+
+    // Signatures are verified from the leaf towards the root, the root is self-signed
+    for depth in 0..=root_depth {
+        let signer_depth = (depth + 1).min(root_depth);
+        checker.check_schema(signer_depth);
+        checker.check_signature(depth, signer_depth);
+    }
+
+    // Constraints are checked from the root towards the leaf, the root is checked against itself
+    for depth in (0..=root_depth).rev() {
+        checker.check_schema(depth);
+        checker.check_constraints(depth, (depth + 1).min(root_depth));
+    }
+    if time_check.timestamp().is_some() {
+        let checked_depth = if time_check.check_whole_chain {
+            root_depth
+        } else {
+            0
+        };
+        for depth in 0..=checked_depth {
+            checker.check_timestamp(depth, &time_check);
+        }
+    }
+
+    checker.into_report()
+}
+
+fn validated_certificate(chain: &ParsedChain) -> Option<ValidatedCertificate> {
+    let certificate_chain_fingerprints = chain
+        .links()
+        .iter()
+        .rev()
+        .map(|link| link.fingerprint.clone().ok())
+        .collect::<Option<Vec<_>>>()?;
+    let leaf = chain.leaf().certificate.as_ref().ok()?.clone();
+    Some(ValidatedCertificate {
+        certificate_chain_fingerprints,
+        permissions: leaf.permissions,
+        key_usage: leaf.key_usage,
+        validity_period: leaf.validity_period,
+        subject: leaf.subject,
+    })
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+
+    use serde_json::json;
+
+    use crate::{
+        test_utils::{chain, now},
+        validate_certificate, validate_certificate_report,
+        validator::{options::ValidationOptions, validate_certificate_report_with_options},
+    };
+
+    #[test]
+    fn report_no_findings_for_valid_chain() {
+        let chain = chain();
+
+        let report =
+            validate_certificate_report(serde_json::to_value(&chain).unwrap(), Some(now()));
+
+        assert!(report.is_valid());
+        assert_eq!(
+            report
+                .validated_certificate
+                .unwrap()
+                .certificate_chain_fingerprints,
+            validate_certificate(serde_json::to_value(&chain).unwrap(), Some(now()))
+                .unwrap()
+                .certificate_chain_fingerprints
+        );
+    }
+
+    #[test]
+    fn report_every_failed_check_of_the_chain() {
+        let mut chain = serde_json::to_value(chain()).unwrap();
+        let intermediate = &mut chain["signature"]["signer"]["certificate"];
+        intermediate["validityPeriod"]["notAfter"] = json!("2200-01-01T00:00:00Z");
+        chain["certificate"]["keyUsage"] = json!("all");
+        let expired = "2100-01-01T00:00:00Z".parse().unwrap();
+
+        let report = validate_certificate_report(chain.clone(), Some(expired));
+
+        let checks: Vec<_> = report
+            .findings
+            .iter()
+            .map(|finding| (finding.position.depth, finding.check))
+            .collect();
+        assert_eq!(
+            checks,
+            vec![
+                (0, ValidationCheck::Signature),
+                (1, ValidationCheck::Signature),
+                (1, ValidationCheck::ValidityPeriod),
+                (0, ValidationCheck::KeyUsage),
+                (0, ValidationCheck::Timestamp),
+            ]
+        );
+        assert!(report.findings.iter().all(|finding| {
+            finding.position.fingerprint.is_some()
+                && finding.position.display_name.as_deref() == Some("Test certificate")
+        }));
+        assert_eq!(report.validated_certificate, None);
+        assert_eq!(
+            validate_certificate(chain, Some(expired)).unwrap_err(),
+            Error::InChain {
+                position: report.findings[0].position.clone(),
+                error: Box::new(Error::InvalidSignature),
+            }
+        );
+    }
+
+    #[test]
+    fn report_timestamp_of_every_certificate_when_whole_chain_is_checked() {
+        let mut chain = serde_json::to_value(chain()).unwrap();
+        chain["signature"]["signer"]["signature"]["signer"]["certificate"]["validityPeriod"]
+            ["notAfter"] = json!("2023-01-01T00:00:00Z");
+        let options = ValidationOptions::new()
+            .at(now())
+            .with_whole_chain_checked(true);
+
+        let leaf_only = validate_certificate_report(chain.clone(), Some(now()));
+        let whole_chain = validate_certificate_report_with_options(chain, &options);
+
+        let timestamp_findings = |report: &ValidationReport| -> Vec<_> {
+            report
+                .findings
+                .iter()
+                .filter(|finding| finding.check == ValidationCheck::Timestamp)
+                .map(|finding| finding.position.depth)
+                .collect()
+        };
+        assert_eq!(timestamp_findings(&leaf_only), Vec::<usize>::new());
+        assert_eq!(timestamp_findings(&whole_chain), vec![2]);
+    }
+
+    #[test]
+    fn report_certificate_not_conforming_to_schema() {
+        let mut chain = serde_json::to_value(chain()).unwrap();
+        chain["signature"]["signer"]["certificate"]["publicKey"] = json!("invalid");
+
+        let report = validate_certificate_report(chain, Some(now()));
+
+        let checks: Vec<_> = report
+            .findings
+            .iter()
+            .map(|finding| (finding.position.depth, finding.check))
+            .collect();
+        assert_eq!(
+            checks,
+            vec![
+                (1, ValidationCheck::Schema),
+                (1, ValidationCheck::Signature)
+            ]
+        );
+    }
+}
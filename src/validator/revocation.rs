@@ -0,0 +1,113 @@
+use crate::{schemas::certificate::Fingerprint, Error, Result};
+
+use super::validated_data::ValidatedRevocationList;
+
+/// Set of validated revocation lists used to reject chains containing revoked certificates.
+/// A revocation list only applies to chains anchored in the same root certificate as the chain of its signer.
+#[derive(Debug, Clone, Default)]
+pub struct RevocationLists {
+    lists: Vec<ValidatedRevocationList>,
+}
+
+impl RevocationLists {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn add(&mut self, revocation_list: ValidatedRevocationList) {
+        self.lists.push(revocation_list);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lists.is_empty()
+    }
+
+    /// Verifies that none of the certificates in the chain is revoked.
+    /// # Arguments
+    /// * `certificate_chain_fingerprints` fingerprints of the chain as returned by the validators (leaf first, root last)
+    pub fn verify_not_revoked(&self, certificate_chain_fingerprints: &[Fingerprint]) -> Result<()> {
+        match self.find_revoked(certificate_chain_fingerprints) {
+            Some(depth) => Err(Error::Revoked(
+                certificate_chain_fingerprints[depth].to_owned(),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Depth of the first revoked certificate of the chain, ordered from the leaf to the root.
+    pub(crate) fn find_revoked(
+        &self,
+        certificate_chain_fingerprints: &[Fingerprint],
+    ) -> Option<usize> {
+        let root = certificate_chain_fingerprints.last();
+        self.lists
+            .iter()
+            .filter(|list| list.certificate_chain_fingerprints.last() == root)
+            .find_map(|list| {
+                certificate_chain_fingerprints
+                    .iter()
+                    .position(|fingerprint| list.revoked_certificates.contains(fingerprint))
+            })
+    }
+}
+
+impl From<Vec<ValidatedRevocationList>> for RevocationLists {
+    fn from(lists: Vec<ValidatedRevocationList>) -> Self {
+        Self { lists }
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use utils::*;
+
+    #[test]
+    fn reject_chain_with_revoked_certificate() {
+        let lists: RevocationLists = vec![revocation_list(&["crl", "root"], &["partner"])].into();
+
+        let result = lists.verify_not_revoked(&chain(&["leaf", "partner", "root"]));
+
+        assert_eq!(result.unwrap_err(), Error::Revoked("partner".into()));
+    }
+
+    #[test]
+    fn accept_chain_without_revoked_certificate() {
+        let lists: RevocationLists = vec![revocation_list(&["crl", "root"], &["other"])].into();
+
+        assert!(lists
+            .verify_not_revoked(&chain(&["leaf", "partner", "root"]))
+            .is_ok());
+    }
+
+    #[test]
+    fn ignore_revocation_list_from_another_root() {
+        let lists: RevocationLists =
+            vec![revocation_list(&["crl", "other-root"], &["partner"])].into();
+
+        assert!(lists
+            .verify_not_revoked(&chain(&["leaf", "partner", "root"]))
+            .is_ok());
+    }
+
+    mod utils {
+        use super::*;
+
+        use crate::schemas::validity_period::ValidityPeriod;
+
+        pub fn revocation_list(chain: &[&str], revoked: &[&str]) -> ValidatedRevocationList {
+            ValidatedRevocationList {
+                certificate_chain_fingerprints: chain.iter().map(|f| f.to_string()).collect(),
+                revoked_certificates: revoked.iter().map(|f| f.to_string()).collect(),
+                validity_period: ValidityPeriod {
+                    not_before: "2000-01-01T00:00:00Z".parse().unwrap(),
+                    not_after: "2100-01-01T00:00:00Z".parse().unwrap(),
+                },
+            }
+        }
+
+        pub fn chain(fingerprints: &[&str]) -> Vec<Fingerprint> {
+            fingerprints.iter().map(|f| f.to_string()).collect()
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+use serde_json::Value;
+
+use crate::{
+    cryptography::Key,
+    schemas::{
+        certificate::Fingerprint, signature::SignedCertificate, SIGNED_CERTIFICATE_SCHEMA_ID,
+    },
+    Error, Result,
+};
+
+use super::{chain::ParsedChain, create_certificate_fingerprint};
+
+/// Set of root certificates trusted as anchors of certificate chains.
+/// Roots are identified either by the fingerprint of the certificate or by its public key.
+#[derive(Debug, Clone, Default)]
+pub struct TrustStore {
+    fingerprints: HashSet<Fingerprint>,
+    public_keys: Vec<Key>,
+}
+
+impl TrustStore {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn add_fingerprint(&mut self, fingerprint: impl Into<Fingerprint>) {
+        self.fingerprints.insert(fingerprint.into().to_lowercase());
+    }
+
+    pub fn add_public_key(&mut self, public_key: Key) {
+        if !self.public_keys.contains(&public_key) {
+            self.public_keys.push(public_key);
+        }
+    }
+
+    /// Trusts the given certificate by its fingerprint.
+    pub fn add_certificate(&mut self, signed_certificate: &SignedCertificate) -> Result<()> {
+        let fingerprint = create_certificate_fingerprint(signed_certificate)?;
+        self.add_fingerprint(fingerprint);
+        Ok(())
+    }
+
+    /// Loads a trust anchor from a file.
+    /// # Arguments
+    /// * `path` json file containing either a signed certificate or a public key
+    pub fn load_file(&mut self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let invalid_trust_anchor = |reason: String| Error::InvalidTrustAnchor {
+            path: path.to_string_lossy().into(),
+            reason,
+        };
+        let content = fs::read_to_string(path).map_err(|e| invalid_trust_anchor(e.to_string()))?;
+        let value: Value =
+            serde_json::from_str(&content).map_err(|e| invalid_trust_anchor(e.to_string()))?;
+        match value["$schema"].as_str() {
+            Some(SIGNED_CERTIFICATE_SCHEMA_ID) => {
+                let signed_certificate: SignedCertificate = serde_json::from_value(value)
+                    .map_err(|e| invalid_trust_anchor(e.to_string()))?;
+                self.add_certificate(&signed_certificate)
+            }
+            Some(schema) => Err(invalid_trust_anchor(format!("unsupported schema {schema}"))),
+            None => {
+                let public_key: Key = serde_json::from_value(value)
+                    .map_err(|e| invalid_trust_anchor(e.to_string()))?;
+                self.add_public_key(public_key);
+                Ok(())
+            }
+        }
+    }
+
+    /// Loads all `.json` files from the directory as trust anchors, see [`TrustStore::load_file`].
+    pub fn load_dir(&mut self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let entries = fs::read_dir(path).map_err(|e| Error::InvalidTrustAnchor {
+            path: path.to_string_lossy().into(),
+            reason: e.to_string(),
+        })?;
+        for entry in entries {
+            let entry_path = entry
+                .map_err(|e| Error::InvalidTrustAnchor {
+                    path: path.to_string_lossy().into(),
+                    reason: e.to_string(),
+                })?
+                .path();
+            if entry_path.is_file() && entry_path.extension().is_some_and(|ext| ext == "json") {
+                self.load_file(&entry_path)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Creates a trust store from a file or from all `.json` files of a directory.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self> {
+        let mut trust_store = Self::new();
+        if path.as_ref().is_dir() {
+            trust_store.load_dir(path)?;
+        } else {
+            trust_store.load_file(path)?;
+        }
+        Ok(trust_store)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fingerprints.is_empty() && self.public_keys.is_empty()
+    }
+
+    pub fn is_trusted(&self, fingerprint: &str, public_key: &Key) -> bool {
+        self.fingerprints.contains(&fingerprint.to_lowercase())
+            || self.public_keys.contains(public_key)
+    }
+
+    /// Verifies that the root of the certificate chain is one of the trust anchors.
+    pub(crate) fn verify_anchor(&self, chain: &ParsedChain) -> Result<()> {
+        let root = chain.root();
+        let fingerprint = root.fingerprint.clone()?;
+        let certificate = root.certificate()?;
+        if self.is_trusted(&fingerprint, &certificate.public_key) {
+            Ok(())
+        } else {
+            Err(Error::UntrustedRoot(fingerprint))
+        }
+    }
+}
@@ -1,20 +1,29 @@
+use std::collections::HashSet;
+
+use serde::Serialize;
+use serde_json::Value;
+use url::Url;
 use ya_client_model::NodeId;
 
 use crate::schemas::{
     certificate::{key_usage::KeyUsage, Fingerprint},
-    permissions::Permissions,
+    permissions::{OutboundGrant, OutboundPermissions, Permissions},
     subject::Subject,
     validity_period::ValidityPeriod,
 };
 
-#[derive(Debug, PartialEq)]
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
 pub struct ValidatedNodeDescriptor {
     pub certificate_chain_fingerprints: Vec<Fingerprint>,
     pub permissions: Permissions,
     pub node_id: NodeId,
+    /// Node descriptor was counter-signed with the key of the node identity
+    pub possession_proven: bool,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Serialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
 pub struct ValidatedCertificate {
     pub certificate_chain_fingerprints: Vec<Fingerprint>,
     pub permissions: Permissions,
@@ -22,3 +31,43 @@ pub struct ValidatedCertificate {
     pub validity_period: ValidityPeriod,
     pub subject: Subject,
 }
+
+impl ValidatedNodeDescriptor {
+    /// Checks whether the node is permitted to access the URL.
+    pub fn allows_outbound(&self, url: &Url) -> bool {
+        self.permissions.allows_outbound(url)
+    }
+
+    /// Returns the permission entry granting the node access to the URL, see [`Permissions::outbound_grant`].
+    pub fn outbound_grant(&self, url: &Url) -> Option<OutboundGrant> {
+        self.permissions.outbound_grant(url)
+    }
+}
+
+impl ValidatedCertificate {
+    /// Checks whether the certificate is permitted to access the URL.
+    pub fn allows_outbound(&self, url: &Url) -> bool {
+        self.permissions.allows_outbound(url)
+    }
+
+    /// Returns the permission entry granting the certificate access to the URL, see [`Permissions::outbound_grant`].
+    pub fn outbound_grant(&self, url: &Url) -> Option<OutboundGrant> {
+        self.permissions.outbound_grant(url)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ValidatedRevocationList {
+    pub certificate_chain_fingerprints: Vec<Fingerprint>,
+    pub revoked_certificates: HashSet<Fingerprint>,
+    pub validity_period: ValidityPeriod,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ValidatedManifest {
+    pub certificate_chain_fingerprints: Vec<Fingerprint>,
+    /// Decoded manifest json
+    pub manifest: Value,
+    /// Outbound access requested by the manifest
+    pub outbound_permissions: Option<OutboundPermissions>,
+}
@@ -0,0 +1,44 @@
+use golem_certificate::{load_encrypted_key, sign_json, verify_signature_json, Error, Key};
+use serde_json::json;
+
+const PASSPHRASE: &str = "test passphrase";
+
+fn read_public_key() -> Key {
+    let public_key =
+        std::fs::read_to_string("tests/resources/encrypted_key/encrypted.pub.json").unwrap();
+    serde_json::from_str(&public_key).unwrap()
+}
+
+#[test]
+fn sign_with_loaded_encrypted_key() {
+    let private_key = load_encrypted_key(
+        "tests/resources/encrypted_key/encrypted.key.json",
+        PASSPHRASE,
+    )
+    .unwrap();
+    let value = json!({ "test": "value" });
+
+    let (algorithm, signature_value) = sign_json(&value, &private_key).unwrap();
+
+    assert!(verify_signature_json(&value, &algorithm, signature_value, &read_public_key()).is_ok());
+}
+
+#[test]
+fn reject_wrong_passphrase() {
+    let result = load_encrypted_key(
+        "tests/resources/encrypted_key/encrypted.key.json",
+        "wrong passphrase",
+    );
+
+    assert_eq!(result.unwrap_err(), Error::InvalidPassphrase);
+}
+
+#[test]
+fn fail_loading_plain_key_as_encrypted() {
+    let result = load_encrypted_key(
+        "tests/resources/encrypted_key/encrypted.pub.json",
+        PASSPHRASE,
+    );
+
+    assert!(matches!(result.unwrap_err(), Error::InvalidEncryptedKey(_)));
+}
@@ -0,0 +1,46 @@
+use golem_certificate::{export_private_key, export_public_key, import_key, Key, KeyFormat};
+use test_case::test_case;
+
+// Keys generated with `openssl genpkey -algorithm ed25519` and `ssh-keygen -t ed25519`
+const OPENSSL_PUBLIC_KEY: &str = "95a8313366954c37f3ff652ed4e0a1f8c83c2cdec03472eb2364e44a9f6b8e70";
+const OPENSSH_PUBLIC_KEY: &str = "6e13be41353f258bd939ba6eea7cba6c83a0d59f0e2a2f035a34cbc4eeb499b4";
+
+fn read_resource(filename: &str) -> String {
+    std::fs::read_to_string(format!("tests/resources/key_format/{filename}")).unwrap()
+}
+
+fn public_key(hex_key: &str) -> Key {
+    let key: [u8; 32] = hex::decode(hex_key).unwrap().try_into().unwrap();
+    key.into()
+}
+
+#[test_case("openssl.key.pem", OPENSSL_PUBLIC_KEY, true)]
+#[test_case("openssl.pub.pem", OPENSSL_PUBLIC_KEY, false)]
+#[test_case("id_ed25519", OPENSSH_PUBLIC_KEY, true)]
+#[test_case("id_ed25519.pub", OPENSSH_PUBLIC_KEY, false)]
+fn import_keys_of_external_tools(filename: &str, expected_public_key: &str, is_private: bool) {
+    let imported = import_key(&read_resource(filename)).unwrap();
+
+    assert_eq!(imported.public_key, public_key(expected_public_key));
+    assert_eq!(imported.private_key.is_some(), is_private);
+}
+
+#[test]
+fn export_keys_in_openssl_encoding() {
+    let imported = import_key(&read_resource("openssl.key.pem")).unwrap();
+
+    let private_key = export_private_key(&imported.private_key.unwrap(), KeyFormat::Pem).unwrap();
+    let public_key = export_public_key(&imported.public_key, KeyFormat::Pem).unwrap();
+
+    assert_eq!(private_key, read_resource("openssl.key.pem"));
+    assert_eq!(public_key, read_resource("openssl.pub.pem"));
+}
+
+#[test]
+fn export_openssh_public_key() {
+    let imported = import_key(&read_resource("id_ed25519")).unwrap();
+
+    let public_key = export_public_key(&imported.public_key, KeyFormat::OpenSsh).unwrap();
+
+    assert_eq!(public_key.trim(), read_resource("id_ed25519.pub").trim());
+}
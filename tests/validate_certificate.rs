@@ -2,24 +2,41 @@ use std::collections::HashSet;
 
 use chrono::{DateTime, Utc};
 use golem_certificate::{
+    create_key_pair,
     schemas::{
-        certificate::key_usage::{KeyUsage, Usage},
-        permissions::{OutboundPermissions, PermissionDetails, Permissions},
+        certificate::{
+            key_usage::{KeyUsage, Usage},
+            Certificate,
+        },
+        permissions::{
+            OutboundGrant, OutboundPermissions, PermissionCategory, PermissionDetails, Permissions,
+        },
         subject::{Contact, Subject},
         validity_period::ValidityPeriod,
     },
-    validator::{validate_certificate_str, validated_data::ValidatedCertificate},
-    Error,
+    validate_certificate_with_options, validate_certificates_batch,
+    validator::{
+        validate_certificate, validate_certificate_str, validate_certificate_str_report,
+        validate_certificate_str_with_options, validated_data::ValidatedCertificate,
+    },
+    CertificateBuilder, EncryptionAlgorithm, Error, HashAlgorithm, KeyPair, SignedCertificate,
+    ValidationCheck, ValidationOptions,
 };
 use pretty_assertions::assert_eq;
+use serde_json::{json, Value};
 use test_case::test_case;
 
+// Fixtures are valid in 2023-2024, so a fixed point in time keeps the tests stable.
+fn timestamp() -> DateTime<Utc> {
+    "2024-01-01T00:00:00Z".parse().unwrap()
+}
+
 #[test]
 fn happy_path_details() {
     let certificate =
         std::fs::read_to_string("tests/resources/certificate/happy_path.signed.json").unwrap();
 
-    let result = validate_certificate_str(&certificate, Some(Utc::now())).unwrap();
+    let result = validate_certificate_str(&certificate, Some(timestamp())).unwrap();
 
     assert_eq!(
         result,
@@ -38,12 +55,26 @@ fn happy_path_details() {
                 "4f0c5b10741a8746141badf3b21325176a0e4e84dfe39747cb857b1c58dc65380ce85eb76a9986303f228a97a17012e77cc9e30ca595c077553309ade6cd2eb6".into(),
                 "80c84b2701126669966f46c1159cae89c58fb088e8bf94b318358fa4ca33ee56d8948511a397e5aba6aa5b88fff36f2541a91b133cde0fb816e8592b695c04c3".into()
                 ],
-            permissions: Permissions::Object(PermissionDetails { outbound: Some(OutboundPermissions::Unrestricted) }),
+            permissions: Permissions::Object(PermissionDetails { outbound: Some(OutboundPermissions::Unrestricted), ..Default::default() }),
             key_usage: KeyUsage::Limited(HashSet::from_iter(vec![Usage::SignNode].into_iter())),
         }
     );
 }
 
+#[test]
+fn query_unrestricted_outbound_access() {
+    let certificate =
+        std::fs::read_to_string("tests/resources/certificate/happy_path.signed.json").unwrap();
+    let result = validate_certificate_str(&certificate, Some(timestamp())).unwrap();
+    let url = url::Url::parse("https://any.example.org/path").unwrap();
+
+    assert!(result.allows_outbound(&url));
+    assert_eq!(
+        result.outbound_grant(&url),
+        Some(OutboundGrant::Unrestricted)
+    );
+}
+
 #[test_case("happy_path.signed.json")]
 #[test_case("happy_path_smartcard_root.signed.json")]
 #[test_case("happy_path_smartcard_leaf.signed.json")]
@@ -51,7 +82,7 @@ fn happy_path(filename: &str) {
     let certificate =
         std::fs::read_to_string(format!("tests/resources/certificate/{filename}")).unwrap();
 
-    assert!(validate_certificate_str(&certificate, Some(Utc::now())).is_ok());
+    assert!(validate_certificate_str(&certificate, Some(timestamp())).is_ok());
 }
 
 #[test_case("not_signed.json", Error::JsonDoesNotConformToSchema("missing field `signature`".to_string()))]
@@ -64,7 +95,7 @@ fn happy_path(filename: &str) {
 )]
 #[test_case(
     "invalid_permissions.signed.json",
-   Error::PermissionsExtended{parent: Permissions::Object(PermissionDetails{outbound: Some(OutboundPermissions::Unrestricted)}), child: Permissions::All}
+   Error::PermissionsExtended{category: PermissionCategory::All}
 )]
 #[test_case("extended_validity_period.signed.json", Error::ValidityPeriodExtended{parent: ValidityPeriod{not_before: "2023-01-01T00:00:00Z".parse().unwrap(), not_after: "2025-01-01T00:00:00Z".parse().unwrap()}, child: ValidityPeriod{not_before: "2023-01-01T00:00:00Z".parse().unwrap(), not_after: "2099-01-01T00:00:00Z".parse().unwrap()}})]
 #[test_case("cert_cannot_sign_other_cert.signed.json", Error::CertSignNotPermitted)]
@@ -72,7 +103,130 @@ fn should_return_err(filename: &str, expected_err: Error) {
     let certificate =
         std::fs::read_to_string(format!("tests/resources/certificate/{filename}")).unwrap();
 
-    let result = validate_certificate_str(&certificate, Some(Utc::now()));
+    let result = validate_certificate_str(&certificate, Some(timestamp()));
+
+    assert_eq!(result.unwrap_err().without_chain_position(), expected_err);
+}
+
+#[test_case("expired.signed.json", 0, ValidationCheck::Timestamp)]
+#[test_case("invalid_signature.signed.json", 0, ValidationCheck::Signature)]
+#[test_case("invalid_key_usage.signed.json", 0, ValidationCheck::KeyUsage)]
+#[test_case(
+    "cert_cannot_sign_other_cert.signed.json",
+    0,
+    ValidationCheck::KeyUsage
+)]
+fn report_failed_check_with_chain_position(filename: &str, depth: usize, check: ValidationCheck) {
+    let certificate =
+        std::fs::read_to_string(format!("tests/resources/certificate/{filename}")).unwrap();
+
+    let report = validate_certificate_str_report(&certificate, Some(timestamp()));
+    let result = validate_certificate_str(&certificate, Some(timestamp()));
+
+    let finding = &report.findings[0];
+    assert_eq!((finding.position.depth, finding.check), (depth, check));
+    assert!(finding.position.fingerprint.is_some());
+    assert_eq!(
+        result.unwrap_err(),
+        Error::InChain {
+            position: finding.position.clone(),
+            error: Box::new(finding.error.clone()),
+        }
+    );
+}
+
+#[test_case("happy_path.signed.json", "sha3-256", Error::UnsupportedSignatureAlgorithm { encryption: EncryptionAlgorithm::EdDSA, hash: HashAlgorithm::Sha3_256 })]
+#[test_case(
+    "happy_path_smartcard_root.signed.json",
+    "sha256",
+    Error::InvalidSignature
+)]
+fn should_reject_signature_with_modified_hash_algorithm(
+    filename: &str,
+    hash: &str,
+    expected_err: Error,
+) {
+    let certificate =
+        std::fs::read_to_string(format!("tests/resources/certificate/{filename}")).unwrap();
+    let mut certificate: serde_json::Value = serde_json::from_str(&certificate).unwrap();
+    certificate["signature"]["algorithm"]["hash"] = hash.into();
+
+    let result = validate_certificate(certificate, Some(timestamp()));
+
+    assert_eq!(result.unwrap_err().without_chain_position(), expected_err);
+}
+
+#[test]
+fn should_report_too_deep_chain_at_certificate() {
+    let certificate =
+        std::fs::read_to_string("tests/resources/certificate/happy_path.signed.json").unwrap();
+    let options = ValidationOptions::new()
+        .at(timestamp())
+        .with_max_chain_depth(1);
+
+    let result = validate_certificate_str_with_options(&certificate, &options);
+
+    let error = result.unwrap_err();
+    let position = error.chain_position().unwrap();
+    assert_eq!(position.depth, 0);
+    assert_eq!(position.display_name.as_deref(), Some("Example leaf cert"));
+    assert_eq!(
+        error.without_chain_position(),
+        Error::ChainTooDeep {
+            depth: 2,
+            max_depth: 1
+        }
+    );
+}
+
+#[test]
+fn should_validate_certificates_sharing_a_chain_in_batch() {
+    let root_key_pair = create_key_pair();
+    let root = CertificateBuilder::new(batch_certificate(&root_key_pair, json!("all")))
+        .self_sign(&root_key_pair.private_key)
+        .unwrap();
+    let leaves: Vec<_> = (0..3)
+        .map(|_| {
+            CertificateBuilder::new(batch_certificate(&create_key_pair(), json!(["signNode"])))
+                .sign(root.clone(), &root_key_pair.private_key)
+                .unwrap()
+        })
+        .collect();
+    let mut tampered = serde_json::to_value(&leaves[1]).unwrap();
+    tampered["certificate"]["subject"]["displayName"] = json!("Tampered leaf");
+    let signed_certificates: Vec<SignedCertificate> = vec![
+        leaves[0].clone(),
+        serde_json::from_value(tampered).unwrap(),
+        leaves[2].clone(),
+    ];
+    let options = ValidationOptions::new().at(timestamp());
+
+    let results = validate_certificates_batch(&signed_certificates, &options);
+
+    let expected: Vec<_> = signed_certificates
+        .iter()
+        .map(|signed_certificate| {
+            validate_certificate_with_options(
+                serde_json::to_value(signed_certificate).unwrap(),
+                &options,
+            )
+        })
+        .collect();
+    assert_eq!(results, expected);
+    assert!(results[0].is_ok());
+    assert!(results[2].is_ok());
+    let error = results[1].clone().unwrap_err();
+    assert_eq!(error.chain_position().unwrap().depth, 0);
+    assert_eq!(error.without_chain_position(), Error::InvalidSignature);
+}
 
-    assert_eq!(result.unwrap_err(), expected_err);
+fn batch_certificate(key_pair: &KeyPair, key_usage: Value) -> Certificate {
+    serde_json::from_value(json!({
+        "keyUsage": key_usage,
+        "permissions": "all",
+        "publicKey": key_pair.public_key,
+        "subject": { "displayName": "Batch test", "contact": { "email": "batch@example.tld" } },
+        "validityPeriod": { "notBefore": "2023-01-01T00:00:00Z", "notAfter": "2025-01-01T00:00:00Z" }
+    }))
+    .unwrap()
 }
@@ -0,0 +1,119 @@
+use chrono::{DateTime, Utc};
+use golem_certificate::{
+    schemas::permissions::OutboundPermissions, sign_manifest, validate_manifest,
+    validate_manifest_str, Error, SignedCertificate, SignedManifest,
+};
+use test_case::test_case;
+use url::Url;
+
+const ROOT_FINGERPRINT: &str = "45af9be35e3e4ee2c2c5f4ae01b3e7c4fad29ee6ed16d42d24b13060a606ac2d8408639a3262e97f35f8a95e2818144bdbf702cf4b44b3966d705f928fc7ce6b";
+
+fn timestamp() -> DateTime<Utc> {
+    "2024-01-01T00:00:00Z".parse().unwrap()
+}
+
+fn read_resource(filename: &str) -> String {
+    std::fs::read_to_string(format!("tests/resources/manifest/{filename}")).unwrap()
+}
+
+#[test]
+fn happy_path() {
+    let result =
+        validate_manifest_str(&read_resource("allowed.signed.json"), Some(timestamp())).unwrap();
+
+    assert_eq!(
+        result.outbound_permissions,
+        Some(OutboundPermissions::Urls(
+            [Url::parse("https://example.com/").unwrap().into()].into()
+        ))
+    );
+    assert_eq!(
+        result.certificate_chain_fingerprints.last().unwrap(),
+        ROOT_FINGERPRINT
+    );
+    assert_eq!(result.manifest["version"], "0.1.0");
+}
+
+#[test]
+fn accept_manifest_without_network_access() {
+    let result =
+        validate_manifest_str(&read_resource("no_network.signed.json"), Some(timestamp())).unwrap();
+
+    assert_eq!(result.outbound_permissions, None);
+}
+
+#[test_case("not_allowed.signed.json")]
+#[test_case("unrestricted.signed.json")]
+fn reject_manifest_requesting_outbound_access_not_permitted_by_certificate(filename: &str) {
+    let result = validate_manifest_str(&read_resource(filename), Some(timestamp()));
+
+    assert!(matches!(
+        result.unwrap_err(),
+        Error::PermissionsExtended { .. }
+    ));
+}
+
+#[test]
+fn reject_manifest_signed_without_manifest_usage() {
+    let result = validate_manifest_str(
+        &read_resource("cert_cannot_sign_manifest.signed.json"),
+        Some(timestamp()),
+    );
+
+    assert_eq!(result.unwrap_err(), Error::ManifestSignNotPermitted);
+}
+
+#[test]
+fn reject_expired_manifest() {
+    let result = validate_manifest_str(
+        &read_resource("allowed.signed.json"),
+        Some("2085-01-01T00:00:00Z".parse().unwrap()),
+    );
+
+    assert_eq!(
+        result.unwrap_err(),
+        Error::Expired("2080-01-01T00:00:00Z".parse().unwrap())
+    );
+}
+
+#[test]
+fn reject_modified_manifest() {
+    let mut signed_manifest: SignedManifest =
+        serde_json::from_str(&read_resource("allowed.signed.json")).unwrap();
+    let other: SignedManifest =
+        serde_json::from_str(&read_resource("no_network.signed.json")).unwrap();
+    signed_manifest.manifest = other.manifest;
+
+    let result = validate_manifest(
+        serde_json::to_value(signed_manifest).unwrap(),
+        Some(timestamp()),
+    );
+
+    assert_eq!(result.unwrap_err(), Error::InvalidSignature);
+}
+
+#[test]
+fn reject_manifest_which_is_not_base64_encoded_json() {
+    let mut signed_manifest: SignedManifest =
+        serde_json::from_str(&read_resource("allowed.signed.json")).unwrap();
+    signed_manifest.manifest = "not a manifest".into();
+
+    let result = validate_manifest(
+        serde_json::to_value(signed_manifest).unwrap(),
+        Some(timestamp()),
+    );
+
+    assert!(matches!(result.unwrap_err(), Error::InvalidManifest(_)));
+}
+
+#[test]
+fn sign_manifest_fails_for_invalid_manifest() {
+    let signed_manifest: SignedManifest =
+        serde_json::from_str(&read_resource("allowed.signed.json")).unwrap();
+    let certificate: SignedCertificate = signed_manifest.signature.signer;
+    let key = golem_certificate::create_key_pair().private_key;
+
+    let result = sign_manifest("bm90IGpzb24=", certificate, &key);
+
+    assert!(result.is_err());
+}
@@ -1,19 +1,41 @@
-use chrono::Utc;
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
 use golem_certificate::{
-    schemas::permissions::{OutboundPermissions, PermissionDetails, Permissions},
-    validator::{validate_node_descriptor_str, validated_data::ValidatedNodeDescriptor},
-    Error,
+    create_key_pair,
+    schemas::{
+        certificate::Certificate,
+        permissions::{
+            OutboundGrant, OutboundPermissions, PermissionCategory, PermissionDetails, Permissions,
+        },
+        validity_period::ValidityPeriod,
+        SIGNED_NODE_DESCRIPTOR_SCHEMA_ID,
+    },
+    sign_json, validate_node_descriptors_batch,
+    validator::{
+        validate_node_descriptor_str, validate_node_descriptor_str_strict,
+        validate_node_descriptor_str_with_options, validate_node_descriptor_with_options,
+        validated_data::ValidatedNodeDescriptor,
+    },
+    CertificateBuilder, CertificateCache, ChainPosition, Error, FixedClock, KeyPair, Signature,
+    SignedNodeDescriptor, ValidationOptions,
 };
+use serde_json::{json, Value};
 use test_case::test_case;
 use url::Url;
 use ya_client_model::NodeId;
 
+// Fixtures are valid in 2023-2024, so a fixed point in time keeps the tests stable.
+fn timestamp() -> DateTime<Utc> {
+    "2024-01-01T00:00:00Z".parse().unwrap()
+}
+
 #[test]
 fn happy_path() {
     let node_descriptor =
         std::fs::read_to_string("tests/resources/node_descriptor/happy_path.signed.json").unwrap();
 
-    let result = validate_node_descriptor_str(&node_descriptor, Some(Utc::now())).unwrap();
+    let result = validate_node_descriptor_str(&node_descriptor, Some(timestamp())).unwrap();
 
     assert_eq!(
         result,
@@ -23,22 +45,41 @@ fn happy_path() {
                 .unwrap(),
             permissions: Permissions::Object(PermissionDetails {
                 outbound: Some(OutboundPermissions::Urls(
-                    [Url::parse("https://example.net/").unwrap()].into()
-                ))
+                    [Url::parse("https://example.net/").unwrap().into()].into()
+                )),
+                ..Default::default()
             }),
             certificate_chain_fingerprints: vec![
                 "cb16a2ed213c1cf7e14faa7cf05743bc145b8555ec2eedb6b12ba0d31d17846d2ed4341b048f2e43b1ca5195a347bfeb0cd663c9e6002a4adb7cc7385112d3cc".into(),
                 "80c84b2701126669966f46c1159cae89c58fb088e8bf94b318358fa4ca33ee56d8948511a397e5aba6aa5b88fff36f2541a91b133cde0fb816e8592b695c04c3".into(),
-            ]
+            ],
+            possession_proven: false,
         }
     );
 }
 
+#[test_case("https://example.net/", true)]
+#[test_case("https://EXAMPLE.net:443", true)]
+#[test_case("https://example.net/other", false)]
+#[test_case("http://example.net/", false)]
+fn query_outbound_access_of_node(url: &str, expected: bool) {
+    let node_descriptor =
+        std::fs::read_to_string("tests/resources/node_descriptor/happy_path.signed.json").unwrap();
+    let result = validate_node_descriptor_str(&node_descriptor, Some(timestamp())).unwrap();
+    let url = Url::parse(url).unwrap();
+
+    assert_eq!(result.allows_outbound(&url), expected);
+    assert_eq!(
+        result.outbound_grant(&url),
+        expected.then(|| OutboundGrant::Url("https://example.net".parse().unwrap()))
+    );
+}
+
 #[test_case("not_signed.json", Error::JsonDoesNotConformToSchema("missing field `signature`".to_string()))]
 #[test_case("invalid_signature.signed.json", Error::InvalidSignatureValue)]
 #[test_case("expired.signed.json", Error::Expired("2023-01-02T00:00:00Z".parse().unwrap()))]
 #[test_case("invalid_permissions_chain.signed.json",
-   Error::PermissionsExtended{parent: Permissions::Object(PermissionDetails{outbound: Some(OutboundPermissions::Unrestricted)}), child: Permissions::All}
+   Error::PermissionsExtended{category: PermissionCategory::All}
 )]
 #[test_case(
     "invalid_cert_chain_signature.signed.json",
@@ -49,7 +90,320 @@ fn should_return_err(filename: &str, expected_err: Error) {
     let node_descriptor =
         std::fs::read_to_string(format!("tests/resources/node_descriptor/{filename}")).unwrap();
 
-    let result = validate_node_descriptor_str(&node_descriptor, Some(Utc::now()));
+    let result = validate_node_descriptor_str(&node_descriptor, Some(timestamp()));
+
+    assert_eq!(result.unwrap_err().without_chain_position(), expected_err);
+}
+
+#[test]
+fn prove_possession_of_node_identity() {
+    let node_descriptor =
+        std::fs::read_to_string("tests/resources/node_descriptor/node_signed.signed.json").unwrap();
+
+    let result = validate_node_descriptor_str_strict(&node_descriptor, Some(timestamp())).unwrap();
+
+    assert!(result.possession_proven);
+    assert_eq!(
+        result.node_id,
+        "0x5a82c868a0d3e8c24c7628c615fa1231ace54e39"
+            .parse::<NodeId>()
+            .unwrap()
+    );
+}
+
+#[test]
+fn reject_node_signature_in_v1_node_descriptor() {
+    let node_descriptor =
+        std::fs::read_to_string("tests/resources/node_descriptor/node_signed.signed.json").unwrap();
+    let mut node_descriptor: Value = serde_json::from_str(&node_descriptor).unwrap();
+    node_descriptor["$schema"] = json!(SIGNED_NODE_DESCRIPTOR_SCHEMA_ID);
+
+    let result = validate_node_descriptor_with_options(
+        node_descriptor,
+        &ValidationOptions::new().at(timestamp()),
+    );
+
+    assert!(matches!(
+        result.unwrap_err(),
+        Error::JsonDoesNotConformToSchema(_)
+    ));
+}
+
+#[test]
+fn reject_node_signature_of_other_node() {
+    let node_descriptor = std::fs::read_to_string(
+        "tests/resources/node_descriptor/node_signed_by_other_node.signed.json",
+    )
+    .unwrap();
+
+    let result = validate_node_descriptor_str(&node_descriptor, Some(timestamp()));
+
+    assert_eq!(
+        result.unwrap_err().without_chain_position(),
+        Error::NodeIdMismatch {
+            expected: "0x5a82c868a0d3e8c24c7628c615fa1231ace54e39"
+                .parse()
+                .unwrap(),
+            recovered: "0x1f7641d39331fe4a17c26adf6544ecf1e73274ca"
+                .parse()
+                .unwrap(),
+        }
+    );
+}
+
+#[test]
+fn require_node_signature_in_strict_mode() {
+    let node_descriptor =
+        std::fs::read_to_string("tests/resources/node_descriptor/happy_path.signed.json").unwrap();
+
+    let result = validate_node_descriptor_str_strict(&node_descriptor, Some(timestamp()));
+
+    assert_eq!(
+        result.unwrap_err().without_chain_position(),
+        Error::MissingNodeSignature
+    );
+}
+
+#[test]
+fn report_chain_position_of_failing_certificate() {
+    let node_descriptor = std::fs::read_to_string(
+        "tests/resources/node_descriptor/invalid_cert_chain_signature.signed.json",
+    )
+    .unwrap();
+
+    let result = validate_node_descriptor_str(&node_descriptor, Some(timestamp()));
+
+    assert_eq!(
+        result.unwrap_err(),
+        Error::InChain {
+            position: ChainPosition {
+                depth: 1,
+                fingerprint: Some("cb16a2ed213c1cf7e14faa7cf05743bc145b8555ec2eedb6b12ba0d31d17846d2ed4341b048f2e43b1ca5195a347bfeb0cd663c9e6002a4adb7cc7385112d3cc".into()),
+                display_name: Some("Example partner cert".into()),
+            },
+            error: Box::new(Error::InvalidSignatureValue),
+        }
+    );
+}
+
+#[test]
+fn report_node_descriptor_at_chain_depth_zero() {
+    let node_descriptor = std::fs::read_to_string(
+        "tests/resources/node_descriptor/cert_cannot_sign_node.signed.json",
+    )
+    .unwrap();
 
-    assert_eq!(result.unwrap_err(), expected_err);
+    let result = validate_node_descriptor_str(&node_descriptor, Some(timestamp()));
+
+    assert_eq!(
+        result.unwrap_err(),
+        Error::InChain {
+            position: ChainPosition::default(),
+            error: Box::new(Error::NodeSignNotPermitted),
+        }
+    );
+}
+
+#[test_case(Duration::zero(), false)]
+#[test_case(Duration::minutes(5), false)]
+#[test_case(Duration::minutes(10), true)]
+fn accept_expired_node_descriptor_within_clock_skew(clock_skew: Duration, valid: bool) {
+    let node_descriptor =
+        std::fs::read_to_string("tests/resources/node_descriptor/expired.signed.json").unwrap();
+    let options = ValidationOptions::new()
+        .with_clock(FixedClock("2023-01-02T00:10:00Z".parse().unwrap()))
+        .with_clock_skew(clock_skew);
+
+    let result = validate_node_descriptor_str_with_options(&node_descriptor, &options);
+
+    assert_eq!(result.is_ok(), valid);
+}
+
+#[test_case(false)]
+#[test_case(true)]
+fn check_timestamp_of_whole_chain(check_whole_chain: bool) {
+    let node_descriptor =
+        std::fs::read_to_string("tests/resources/node_descriptor/happy_path.signed.json").unwrap();
+    let options = ValidationOptions::new()
+        .at(timestamp())
+        .with_whole_chain_checked(check_whole_chain);
+
+    let result = validate_node_descriptor_str_with_options(&node_descriptor, &options);
+
+    assert_eq!(
+        result.unwrap(),
+        validate_node_descriptor_str(&node_descriptor, Some(timestamp())).unwrap()
+    );
+}
+
+#[test_case(false, 0, Error::ValidityPeriodExtended { parent: validity_period("2023-06-01T00:00:00Z"), child: validity_period("2025-01-01T00:00:00Z") })]
+#[test_case(true, 1, Error::Expired("2023-06-01T00:00:00Z".parse().unwrap()))]
+fn reject_node_descriptor_signed_by_expired_certificate_only_when_whole_chain_is_checked(
+    check_whole_chain: bool,
+    expected_depth: usize,
+    expected_err: Error,
+) {
+    let root_key_pair = create_key_pair();
+    let leaf_key_pair = create_key_pair();
+    let root = CertificateBuilder::new(certificate(
+        &root_key_pair,
+        json!("all"),
+        "2025-01-01T00:00:00Z",
+    ))
+    .self_sign(&root_key_pair.private_key)
+    .unwrap();
+    let leaf = CertificateBuilder::new(certificate(
+        &leaf_key_pair,
+        json!(["signNode"]),
+        "2023-06-01T00:00:00Z",
+    ))
+    .sign(root, &root_key_pair.private_key)
+    .unwrap();
+    // The builder rejects a node descriptor outliving its signer, so it is signed directly
+    let node_descriptor = json!({
+        "nodeId": "0x338e02f29b63155beec8253af7ad367dd44b40c6",
+        "permissions": "all",
+        "validityPeriod": validity_period("2025-01-01T00:00:00Z")
+    });
+    let (algorithm, value) = sign_json(&node_descriptor, &leaf_key_pair.private_key).unwrap();
+    let signed_node_descriptor = SignedNodeDescriptor {
+        schema: SIGNED_NODE_DESCRIPTOR_SCHEMA_ID.into(),
+        node_descriptor,
+        signature: Signature::create(algorithm, value, leaf),
+        node_signature: None,
+    };
+    let options = ValidationOptions::new()
+        .at(timestamp())
+        .with_whole_chain_checked(check_whole_chain);
+
+    let result = validate_node_descriptor_with_options(
+        serde_json::to_value(signed_node_descriptor).unwrap(),
+        &options,
+    );
+
+    let error = result.unwrap_err();
+    assert_eq!(error.chain_position().unwrap().depth, expected_depth);
+    assert_eq!(error.without_chain_position(), expected_err);
+}
+
+fn certificate(key_pair: &KeyPair, key_usage: Value, not_after: &str) -> Certificate {
+    serde_json::from_value(json!({
+        "keyUsage": key_usage,
+        "permissions": "all",
+        "publicKey": key_pair.public_key,
+        "subject": { "displayName": "Whole chain test", "contact": { "email": "chain@example.tld" } },
+        "validityPeriod": validity_period(not_after)
+    }))
+    .unwrap()
+}
+
+fn validity_period(not_after: &str) -> ValidityPeriod {
+    ValidityPeriod {
+        not_before: "2023-01-01T00:00:00Z".parse().unwrap(),
+        not_after: not_after.parse().unwrap(),
+    }
+}
+
+#[test_case(2, None)]
+#[test_case(1, Some(Error::InChain { position: ChainPosition::default(), error: Box::new(Error::ChainTooDeep { depth: 2, max_depth: 1 }) }))]
+fn limit_chain_depth(max_chain_depth: usize, expected_err: Option<Error>) {
+    let node_descriptor =
+        std::fs::read_to_string("tests/resources/node_descriptor/happy_path.signed.json").unwrap();
+    let options = ValidationOptions::new()
+        .at(timestamp())
+        .with_max_chain_depth(max_chain_depth);
+
+    let result = validate_node_descriptor_str_with_options(&node_descriptor, &options);
+
+    assert_eq!(result.err(), expected_err);
+}
+
+#[test]
+fn reuse_cached_signer_chain() {
+    let node_descriptor =
+        std::fs::read_to_string("tests/resources/node_descriptor/happy_path.signed.json").unwrap();
+    let cache = Arc::new(CertificateCache::new(
+        100,
+        std::time::Duration::from_secs(60),
+    ));
+    let options = ValidationOptions::new()
+        .at(timestamp())
+        .with_cache(cache.clone());
+
+    let first = validate_node_descriptor_str_with_options(&node_descriptor, &options).unwrap();
+    let second = validate_node_descriptor_str_with_options(&node_descriptor, &options).unwrap();
+
+    assert_eq!(cache.len(), 1);
+    assert_eq!(first, second);
+    assert_eq!(
+        first,
+        validate_node_descriptor_str(&node_descriptor, Some(timestamp())).unwrap()
+    );
+}
+
+#[test]
+fn validate_node_descriptors_in_batch() {
+    let read = |filename: &str| -> Value {
+        let path = format!("tests/resources/node_descriptor/{filename}");
+        serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+    };
+    let mut tampered = read("happy_path.signed.json");
+    tampered["nodeDescriptor"]["nodeId"] = json!("0x0000000000000000000000000000000000000001");
+    let documents = vec![
+        read("happy_path.signed.json"),
+        tampered,
+        read("expired.signed.json"),
+        read("node_signed.signed.json"),
+        read("invalid_cert_chain_signature.signed.json"),
+        read("happy_path.signed.json"),
+    ];
+    let signed_node_descriptors: Vec<SignedNodeDescriptor> = documents
+        .iter()
+        .map(|document| serde_json::from_value(document.clone()).unwrap())
+        .collect();
+    let options = ValidationOptions::new().at(timestamp());
+
+    let results = validate_node_descriptors_batch(&signed_node_descriptors, &options);
+
+    let expected: Vec<_> = documents
+        .into_iter()
+        .map(|document| validate_node_descriptor_with_options(document, &options))
+        .collect();
+    assert_eq!(results, expected);
+    assert!(results[0].is_ok());
+    assert_eq!(
+        results[1]
+            .as_ref()
+            .unwrap_err()
+            .clone()
+            .without_chain_position(),
+        Error::InvalidSignature
+    );
+}
+
+#[test]
+fn require_node_signature_in_batch() {
+    let read = |filename: &str| -> SignedNodeDescriptor {
+        let path = format!("tests/resources/node_descriptor/{filename}");
+        serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+    };
+    let signed_node_descriptors = vec![
+        read("happy_path.signed.json"),
+        read("node_signed.signed.json"),
+    ];
+    let options = ValidationOptions::new()
+        .at(timestamp())
+        .with_node_signature_required(true);
+
+    let results = validate_node_descriptors_batch(&signed_node_descriptors, &options);
+
+    assert_eq!(
+        results[0]
+            .as_ref()
+            .unwrap_err()
+            .clone()
+            .without_chain_position(),
+        Error::MissingNodeSignature
+    );
+    assert!(results[1].as_ref().unwrap().possession_proven);
 }
@@ -0,0 +1,216 @@
+use chrono::{DateTime, Utc};
+use golem_certificate::{
+    create_key_pair,
+    schemas::{certificate::Certificate, SIGNED_REVOCATION_LIST_SCHEMA_ID},
+    sign_json, validate_certificate, validate_certificate_str_with_options,
+    validate_certificate_with_options, validate_node_descriptor_str_with_options,
+    validate_revocation_list, validate_revocation_list_str, CertificateBuilder, CertificateCache,
+    ChainPosition, Error, Key, RevocationLists, Signature, SignedRevocationList, ValidationOptions,
+};
+use serde_json::{json, Value};
+use std::{sync::Arc, time::Duration};
+use test_case::test_case;
+
+const ROOT_FINGERPRINT: &str = "45af9be35e3e4ee2c2c5f4ae01b3e7c4fad29ee6ed16d42d24b13060a606ac2d8408639a3262e97f35f8a95e2818144bdbf702cf4b44b3966d705f928fc7ce6b";
+const PARTNER_FINGERPRINT: &str = "2d38e5912254a3714b88b38b44efc9884f835f775c83ecdf6ab1d835a05ad3e515be4897bed05b28beb557669cde15b6e65edfeea19d23e79ca70f217217ce62";
+
+fn timestamp() -> DateTime<Utc> {
+    "2024-01-01T00:00:00Z".parse().unwrap()
+}
+
+fn read_resource(filename: &str) -> String {
+    std::fs::read_to_string(format!("tests/resources/revocation_list/{filename}")).unwrap()
+}
+
+fn load_revocation_lists(filenames: &[&str]) -> RevocationLists {
+    filenames
+        .iter()
+        .map(|filename| validate_revocation_list_str(&read_resource(filename), Some(timestamp())))
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap()
+        .into()
+}
+
+fn options(revocation_lists: RevocationLists) -> ValidationOptions {
+    ValidationOptions::from(Some(timestamp())).with_revocation_lists(revocation_lists)
+}
+
+#[test]
+fn happy_path() {
+    let result = validate_revocation_list_str(
+        &read_resource("revoked_partner.signed.json"),
+        Some(timestamp()),
+    )
+    .unwrap();
+
+    assert_eq!(
+        result.revoked_certificates,
+        [PARTNER_FINGERPRINT.to_string()].into()
+    );
+    assert_eq!(
+        result.certificate_chain_fingerprints.last().unwrap(),
+        ROOT_FINGERPRINT
+    );
+}
+
+#[test]
+fn reject_revocation_list_signed_without_revocation_usage() {
+    let result = validate_revocation_list_str(
+        &read_resource("cert_cannot_sign_revocation_list.signed.json"),
+        Some(timestamp()),
+    );
+
+    assert_eq!(result.unwrap_err(), Error::RevocationListSignNotPermitted);
+}
+
+#[test]
+fn reject_expired_revocation_list() {
+    let result = validate_revocation_list_str(
+        &read_resource("revoked_partner.signed.json"),
+        Some("2095-01-01T00:00:00Z".parse().unwrap()),
+    );
+
+    assert_eq!(
+        result.unwrap_err(),
+        Error::Expired("2090-01-01T00:00:00Z".parse().unwrap())
+    );
+}
+
+#[test_case(&[])]
+#[test_case(&["empty.signed.json"])]
+fn accept_certificate_not_revoked(revocation_lists: &[&str]) {
+    let revocation_lists = load_revocation_lists(revocation_lists);
+
+    let result = validate_certificate_str_with_options(
+        &read_resource("leaf_certificate.signed.json"),
+        &options(revocation_lists),
+    );
+
+    assert!(result.is_ok());
+}
+
+#[test]
+fn reject_certificate_with_revoked_parent() {
+    let revocation_lists =
+        load_revocation_lists(&["empty.signed.json", "revoked_partner.signed.json"]);
+
+    let result = validate_certificate_str_with_options(
+        &read_resource("leaf_certificate.signed.json"),
+        &options(revocation_lists),
+    );
+
+    let error = result.unwrap_err();
+    assert_eq!(error.chain_position().unwrap().depth, 1);
+    assert_eq!(
+        error.without_chain_position(),
+        Error::Revoked(PARTNER_FINGERPRINT.into())
+    );
+}
+
+#[test]
+fn reject_node_descriptor_with_revoked_certificate_in_chain() {
+    let revocation_lists = load_revocation_lists(&["revoked_partner.signed.json"]);
+
+    let result = validate_node_descriptor_str_with_options(
+        &read_resource("node_descriptor.signed.json"),
+        &options(revocation_lists),
+    );
+
+    let error = result.unwrap_err();
+    assert_eq!(error.chain_position().unwrap().depth, 2);
+    assert_eq!(
+        error.without_chain_position(),
+        Error::Revoked(PARTNER_FINGERPRINT.into())
+    );
+}
+
+#[test]
+fn ignore_revocation_list_of_another_root() {
+    let revocation_lists = load_revocation_lists(&["revoked_partner.signed.json"]);
+    let certificate =
+        std::fs::read_to_string("tests/resources/certificate/happy_path.signed.json").unwrap();
+
+    let result = validate_certificate_str_with_options(&certificate, &options(revocation_lists));
+
+    assert!(result.is_ok());
+}
+
+#[test]
+fn reject_revoked_certificate_validated_before_with_the_same_cache() {
+    let cache = Arc::new(CertificateCache::new(16, Duration::from_secs(60)));
+    let certificate = read_resource("leaf_certificate.signed.json");
+    validate_certificate_str_with_options(
+        &certificate,
+        &ValidationOptions::from(Some(timestamp())).with_cache(cache.clone()),
+    )
+    .unwrap();
+    let revocation_lists = load_revocation_lists(&["revoked_partner.signed.json"]);
+
+    let result = validate_certificate_str_with_options(
+        &certificate,
+        &options(revocation_lists).with_cache(cache),
+    );
+
+    assert_eq!(
+        result.unwrap_err().without_chain_position(),
+        Error::Revoked(PARTNER_FINGERPRINT.into())
+    );
+}
+
+#[test]
+fn reject_certificate_revoked_by_uppercase_fingerprint() {
+    let root_key_pair = create_key_pair();
+    let leaf_key_pair = create_key_pair();
+    let root = CertificateBuilder::new(certificate(&root_key_pair.public_key, json!("all")))
+        .self_sign(&root_key_pair.private_key)
+        .unwrap();
+    let leaf = CertificateBuilder::new(certificate(&leaf_key_pair.public_key, json!(["signNode"])))
+        .sign(root.clone(), &root_key_pair.private_key)
+        .unwrap();
+    let leaf = serde_json::to_value(leaf).unwrap();
+    let leaf_fingerprint = validate_certificate(leaf.clone(), None)
+        .unwrap()
+        .certificate_chain_fingerprints[0]
+        .clone();
+    let revocation_list = json!({
+        "revokedCertificates": [leaf_fingerprint.to_uppercase()],
+        "validityPeriod": { "notBefore": "2023-01-01T00:00:00Z", "notAfter": "2025-01-01T00:00:00Z" }
+    });
+    let (algorithm, value) = sign_json(&revocation_list, &root_key_pair.private_key).unwrap();
+    let signed_revocation_list = SignedRevocationList {
+        schema: SIGNED_REVOCATION_LIST_SCHEMA_ID.into(),
+        revocation_list,
+        signature: Signature::create(algorithm, value, root),
+    };
+    let revocation_lists: RevocationLists = vec![validate_revocation_list(
+        serde_json::to_value(signed_revocation_list).unwrap(),
+        Some(timestamp()),
+    )
+    .unwrap()]
+    .into();
+
+    let result = validate_certificate_with_options(leaf, &options(revocation_lists));
+
+    assert_eq!(
+        result.unwrap_err(),
+        Error::InChain {
+            position: ChainPosition {
+                depth: 0,
+                fingerprint: Some(leaf_fingerprint.clone()),
+                display_name: Some("Revocation test".into()),
+            },
+            error: Box::new(Error::Revoked(leaf_fingerprint)),
+        }
+    );
+}
+
+fn certificate(public_key: &Key, key_usage: Value) -> Certificate {
+    serde_json::from_value(json!({
+        "keyUsage": key_usage,
+        "permissions": "all",
+        "publicKey": public_key,
+        "subject": { "displayName": "Revocation test", "contact": { "email": "revocation@example.tld" } },
+        "validityPeriod": { "notBefore": "2023-01-01T00:00:00Z", "notAfter": "2025-01-01T00:00:00Z" }
+    }))
+    .unwrap()
+}
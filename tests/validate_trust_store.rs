@@ -0,0 +1,140 @@
+use chrono::{DateTime, Utc};
+use golem_certificate::{
+    validate_certificate_str_with_trust_store, validate_manifest_str_with_options,
+    validate_node_descriptor_str_with_trust_store, Error, TrustStore, ValidationOptions,
+};
+use test_case::test_case;
+
+const ROOT_FINGERPRINT: &str = "80c84b2701126669966f46c1159cae89c58fb088e8bf94b318358fa4ca33ee56d8948511a397e5aba6aa5b88fff36f2541a91b133cde0fb816e8592b695c04c3";
+
+fn timestamp() -> DateTime<Utc> {
+    "2024-01-01T00:00:00Z".parse().unwrap()
+}
+
+fn read_certificate() -> String {
+    std::fs::read_to_string("tests/resources/certificate/happy_path.signed.json").unwrap()
+}
+
+#[test]
+fn accept_certificate_with_trusted_root_fingerprint() {
+    let mut trust_store = TrustStore::new();
+    trust_store.add_fingerprint(ROOT_FINGERPRINT);
+
+    let result = validate_certificate_str_with_trust_store(
+        &read_certificate(),
+        Some(timestamp()),
+        &trust_store,
+    )
+    .unwrap();
+
+    assert_eq!(
+        result.certificate_chain_fingerprints.last().unwrap(),
+        ROOT_FINGERPRINT
+    );
+}
+
+#[test_case("tests/resources/trust_store/root.pub.json")]
+#[test_case("tests/resources/trust_store")]
+fn accept_certificate_with_trust_store_loaded_from_path(path: &str) {
+    let trust_store = TrustStore::from_path(path).unwrap();
+
+    let result = validate_certificate_str_with_trust_store(
+        &read_certificate(),
+        Some(timestamp()),
+        &trust_store,
+    );
+
+    assert!(result.is_ok());
+}
+
+#[test_case(TrustStore::new())]
+#[test_case(TrustStore::from_path("tests/resources/trust_store/smartcard_root.signed.json").unwrap())]
+fn reject_certificate_with_untrusted_root(trust_store: TrustStore) {
+    let result = validate_certificate_str_with_trust_store(
+        &read_certificate(),
+        Some(timestamp()),
+        &trust_store,
+    );
+
+    let error = result.unwrap_err();
+    let position = error.chain_position().unwrap();
+    assert_eq!(position.depth, 2);
+    assert_eq!(position.fingerprint.as_deref(), Some(ROOT_FINGERPRINT));
+    assert_eq!(
+        error.without_chain_position(),
+        Error::UntrustedRoot(ROOT_FINGERPRINT.into())
+    );
+}
+
+#[test]
+fn accept_smartcard_root_loaded_from_certificate_file() {
+    let trust_store =
+        TrustStore::from_path("tests/resources/trust_store/smartcard_root.signed.json").unwrap();
+    let certificate = std::fs::read_to_string(
+        "tests/resources/certificate/happy_path_smartcard_leaf.signed.json",
+    )
+    .unwrap();
+
+    let result =
+        validate_certificate_str_with_trust_store(&certificate, Some(timestamp()), &trust_store);
+
+    assert!(result.is_ok());
+}
+
+#[test]
+fn accept_node_descriptor_with_trusted_root() {
+    let trust_store = TrustStore::from_path("tests/resources/trust_store").unwrap();
+    let node_descriptor =
+        std::fs::read_to_string("tests/resources/node_descriptor/happy_path.signed.json").unwrap();
+
+    let result = validate_node_descriptor_str_with_trust_store(
+        &node_descriptor,
+        Some(timestamp()),
+        &trust_store,
+    );
+
+    assert!(result.is_ok());
+}
+
+#[test]
+fn reject_node_descriptor_with_untrusted_root() {
+    let trust_store = TrustStore::new();
+    let node_descriptor =
+        std::fs::read_to_string("tests/resources/node_descriptor/happy_path.signed.json").unwrap();
+
+    let result = validate_node_descriptor_str_with_trust_store(
+        &node_descriptor,
+        Some(timestamp()),
+        &trust_store,
+    );
+
+    let error = result.unwrap_err();
+    assert_eq!(error.chain_position().unwrap().depth, 2);
+    assert!(matches!(
+        error.without_chain_position(),
+        Error::UntrustedRoot(_)
+    ));
+}
+
+#[test]
+fn fail_loading_trust_anchor_from_missing_file() {
+    let result = TrustStore::from_path("tests/resources/trust_store/missing.json");
+
+    assert!(matches!(
+        result.unwrap_err(),
+        Error::InvalidTrustAnchor { .. }
+    ));
+}
+
+#[test]
+fn reject_manifest_not_anchored_in_trust_store_of_options() {
+    let manifest = std::fs::read_to_string("tests/resources/manifest/allowed.signed.json").unwrap();
+    let options = ValidationOptions::new().with_trust_store(TrustStore::new());
+
+    let result = validate_manifest_str_with_options(&manifest, &options);
+
+    assert!(matches!(
+        result.unwrap_err().without_chain_position(),
+        Error::UntrustedRoot(_)
+    ));
+}
@@ -0,0 +1,311 @@
+//! JavaScript bindings of Golem Certificate, build with `wasm-pack build wasm`.
+//! Documents are validated by the same rules as in Rust, results are converted to JavaScript values
+//! and errors are thrown as `GolemCertificateError` objects.
+
+use chrono::{DateTime, Utc};
+use golem_certificate as gcert;
+use golem_certificate::schemas::{certificate::Certificate, node_descriptor::NodeDescriptor};
+use hex::ToHex;
+use serde::{de::DeserializeOwned, Serialize};
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(typescript_custom_section)]
+const TYPES: &'static str = r#"
+export interface Key {
+    algorithm: "EdDSA" | "EdDSAph" | "EdDSAOpenPGP" | "ECDSASecp256k1";
+    key: string;
+    parameters?: Record<string, unknown>;
+}
+
+export interface KeyPair {
+    publicKey: Key;
+    privateKey: Key;
+}
+
+export type Usage = "signCertificate" | "signManifest" | "signNode" | "signRevocationList";
+
+export type KeyUsage = "all" | Usage[];
+
+export type Grant<T> = "unrestricted" | T;
+
+export type Permissions = "all" | {
+    outbound?: Grant<{ urls: string[] }>;
+    inbound?: Grant<{ ports: number[] }>;
+    runtimes?: Grant<{ names: string[] }>;
+    paymentPlatforms?: Grant<{ names: string[] }>;
+    [permission: string]: unknown;
+};
+
+export interface ValidityPeriod {
+    notBefore: string;
+    notAfter: string;
+}
+
+export interface Subject {
+    displayName: string;
+    contact: { email: string; [property: string]: unknown };
+    [property: string]: unknown;
+}
+
+export interface Certificate {
+    keyUsage: KeyUsage;
+    permissions: Permissions;
+    publicKey: Key;
+    subject: Subject;
+    validityPeriod: ValidityPeriod;
+}
+
+export interface NodeDescriptor {
+    nodeId: string;
+    permissions: Permissions;
+    validityPeriod: ValidityPeriod;
+}
+
+export interface SignatureAlgorithm {
+    hash: string;
+    encryption: string;
+}
+
+export interface SignedCertificate {
+    $schema: string;
+    certificate: Certificate;
+    signature: {
+        algorithm: SignatureAlgorithm;
+        value: string;
+        signer: "self" | SignedCertificate;
+    };
+}
+
+export interface SignedNodeDescriptor {
+    $schema: string;
+    nodeDescriptor: NodeDescriptor;
+    signature: {
+        algorithm: SignatureAlgorithm;
+        value: string;
+        signer: SignedCertificate;
+    };
+    nodeSignature?: { algorithm: SignatureAlgorithm; value: string };
+}
+
+export interface ValidatedCertificate {
+    /** Fingerprints of the chain, from the certificate to the root */
+    certificateChainFingerprints: string[];
+    permissions: Permissions;
+    keyUsage: KeyUsage;
+    validityPeriod: ValidityPeriod;
+    subject: Subject;
+}
+
+export interface ValidatedNodeDescriptor {
+    /** Fingerprints of the chain, from the signing certificate to the root */
+    certificateChainFingerprints: string[];
+    permissions: Permissions;
+    nodeId: string;
+    /** Node descriptor was counter-signed with the key of the node identity */
+    possessionProven: boolean;
+}
+
+export interface ChainPosition {
+    /** 0 is the validated document, its signing certificate is at depth 1 */
+    depth: number;
+    fingerprint: string | null;
+    displayName: string | null;
+}
+
+/** Error thrown by the functions of the package, its `name` is "GolemCertificateError" */
+export interface GolemCertificateError extends Error {
+    /**
+     * Variant of the Rust `golem_certificate::Error`, "InvalidArgument" when an argument cannot be converted
+     * or "InternalError" when the result cannot be converted
+     */
+    kind: string;
+    /** Position in the certificate chain where validation failed, null when it is not known */
+    position: ChainPosition | null;
+}
+"#;
+
+/// Deserializes and validates certificate, see [`gcert::validate_certificate_str`].
+/// # Arguments
+/// * `data` serialized certificate
+/// * `timestamp` optional RFC 3339 timestamp to verify validity
+#[wasm_bindgen(js_name = validateCertificate, unchecked_return_type = "ValidatedCertificate")]
+pub fn validate_certificate(data: &str, timestamp: Option<String>) -> Result<JsValue, Error> {
+    let validated_certificate = gcert::validate_certificate_str(data, parse_timestamp(timestamp)?)?;
+    to_js(&validated_certificate)
+}
+
+/// Deserializes and validates node descriptor, see [`gcert::validate_node_descriptor_str`].
+/// # Arguments
+/// * `data` serialized node descriptor
+/// * `timestamp` optional RFC 3339 timestamp to verify validity
+#[wasm_bindgen(
+    js_name = validateNodeDescriptor,
+    unchecked_return_type = "ValidatedNodeDescriptor"
+)]
+pub fn validate_node_descriptor(data: &str, timestamp: Option<String>) -> Result<JsValue, Error> {
+    let validated_node_descriptor =
+        gcert::validate_node_descriptor_str(data, parse_timestamp(timestamp)?)?;
+    to_js(&validated_node_descriptor)
+}
+
+/// Fingerprint of the certificate, as listed in the certificate chain fingerprints of validated documents.
+/// # Arguments
+/// * `data` serialized signed certificate
+#[wasm_bindgen(js_name = fingerprint)]
+pub fn fingerprint(data: &str) -> Result<String, Error> {
+    let signed_certificate: gcert::SignedCertificate = serde_json::from_str(data)?;
+    let hash = gcert::create_hash(
+        &signed_certificate.certificate,
+        &signed_certificate.signature.algorithm.hash,
+    )?;
+    Ok(hash.encode_hex())
+}
+
+/// Creates an Ed25519 key pair.
+#[wasm_bindgen(js_name = createKeyPair, unchecked_return_type = "KeyPair")]
+pub fn create_key_pair() -> Result<JsValue, Error> {
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct KeyPair {
+        public_key: gcert::Key,
+        private_key: gcert::Key,
+    }
+
+    let key_pair = gcert::create_key_pair();
+    to_js(&KeyPair {
+        public_key: key_pair.public_key,
+        private_key: key_pair.private_key,
+    })
+}
+
+/// Signs the certificate, see [`gcert::CertificateBuilder`].
+/// # Arguments
+/// * `certificate` certificate to sign
+/// * `signer` signing certificate, the certificate is self-signed when it is not given
+/// * `private_key` private key of the signer, or of the certificate when it is self-signed
+#[wasm_bindgen(js_name = signCertificate, unchecked_return_type = "SignedCertificate")]
+pub fn sign_certificate(
+    #[wasm_bindgen(unchecked_param_type = "Certificate")] certificate: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "SignedCertificate | undefined")] signer: JsValue,
+    #[wasm_bindgen(js_name = privateKey, unchecked_param_type = "Key")] private_key: JsValue,
+) -> Result<JsValue, Error> {
+    let builder = gcert::CertificateBuilder::new(from_js::<Certificate>(certificate)?);
+    let private_key: gcert::Key = from_js(private_key)?;
+    let signed_certificate = if signer.is_undefined() || signer.is_null() {
+        builder.self_sign(&private_key)?
+    } else {
+        builder.sign(from_js(signer)?, &private_key)?
+    };
+    to_js(&signed_certificate)
+}
+
+/// Signs the node descriptor, see [`gcert::NodeDescriptorBuilder`].
+/// # Arguments
+/// * `node_descriptor` node descriptor to sign
+/// * `signer` signing certificate permitted to sign node descriptors
+/// * `private_key` private key of the signer
+#[wasm_bindgen(js_name = signNodeDescriptor, unchecked_return_type = "SignedNodeDescriptor")]
+pub fn sign_node_descriptor(
+    #[wasm_bindgen(js_name = nodeDescriptor, unchecked_param_type = "NodeDescriptor")]
+    node_descriptor: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "SignedCertificate")] signer: JsValue,
+    #[wasm_bindgen(js_name = privateKey, unchecked_param_type = "Key")] private_key: JsValue,
+) -> Result<JsValue, Error> {
+    let builder = gcert::NodeDescriptorBuilder::new(from_js::<NodeDescriptor>(node_descriptor)?);
+    let signed_node_descriptor = builder.sign(from_js(signer)?, &from_js(private_key)?)?;
+    to_js(&signed_node_descriptor)
+}
+
+/// Error thrown to JavaScript, see `GolemCertificateError` in the TypeScript declarations.
+#[derive(Debug)]
+pub struct Error {
+    message: String,
+    details: ErrorDetails,
+}
+
+#[derive(Debug, Serialize)]
+struct ErrorDetails {
+    kind: &'static str,
+    position: Option<ChainPosition>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ChainPosition {
+    depth: usize,
+    fingerprint: Option<String>,
+    display_name: Option<String>,
+}
+
+impl Error {
+    fn new(kind: &'static str, message: String) -> Self {
+        Self {
+            message,
+            details: ErrorDetails {
+                kind,
+                position: None,
+            },
+        }
+    }
+}
+
+impl From<gcert::Error> for Error {
+    fn from(error: gcert::Error) -> Self {
+        let position = error.chain_position().map(|position| ChainPosition {
+            depth: position.depth,
+            fingerprint: position.fingerprint.clone(),
+            display_name: position.display_name.clone(),
+        });
+        Self {
+            message: error.to_string(),
+            details: ErrorDetails {
+                kind: error.kind(),
+                position,
+            },
+        }
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(error: serde_json::Error) -> Self {
+        gcert::Error::InvalidJson(error.to_string()).into()
+    }
+}
+
+impl From<Error> for JsValue {
+    fn from(error: Error) -> Self {
+        let js_error = js_sys::Error::new(&error.message);
+        js_error.set_name("GolemCertificateError");
+        // Details are plain strings and numbers, their serialization does not fail
+        if let Ok(details) = to_js(&error.details) {
+            js_sys::Object::assign(&js_error, &details.unchecked_into());
+        }
+        js_error.into()
+    }
+}
+
+fn parse_timestamp(timestamp: Option<String>) -> Result<Option<DateTime<Utc>>, Error> {
+    timestamp
+        .map(|timestamp| {
+            DateTime::parse_from_rfc3339(&timestamp)
+                .map(|timestamp| timestamp.with_timezone(&Utc))
+                .map_err(|e| {
+                    Error::new(
+                        "InvalidArgument",
+                        format!("Invalid timestamp {timestamp}: {e}"),
+                    )
+                })
+        })
+        .transpose()
+}
+
+// Maps are converted to plain objects, so results look like parsed json
+fn to_js<T: Serialize>(value: &T) -> Result<JsValue, Error> {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .map_err(|e| Error::new("InternalError", e.to_string()))
+}
+
+fn from_js<T: DeserializeOwned>(value: JsValue) -> Result<T, Error> {
+    serde_wasm_bindgen::from_value(value).map_err(|e| Error::new("InvalidArgument", e.to_string()))
+}
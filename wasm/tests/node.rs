@@ -0,0 +1,162 @@
+//! Run with `wasm-pack test --node wasm`, or with `wasm-bindgen-test-runner` set as the cargo runner of the wasm32 target.
+#![cfg(target_arch = "wasm32")]
+
+use golem_certificate as gcert;
+use golem_certificate_wasm::{
+    create_key_pair, fingerprint, sign_certificate, validate_certificate, validate_node_descriptor,
+};
+use serde::Serialize;
+use serde_json::{json, Value};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+// Fixtures are valid in 2023-2024, so a fixed point in time keeps the tests stable.
+const TIMESTAMP: &str = "2024-01-01T00:00:00Z";
+
+macro_rules! fixtures {
+    ($directory:literal: $($name:literal),* $(,)?) => {
+        [$(($name, include_str!(concat!("../../tests/resources/", $directory, "/", $name)))),*]
+    };
+}
+
+const CERTIFICATES: [(&str, &str); 11] = fixtures!("certificate":
+    "cert_cannot_sign_other_cert.signed.json",
+    "expired.signed.json",
+    "extended_validity_period.signed.json",
+    "happy_path.signed.json",
+    "happy_path_smartcard_leaf.signed.json",
+    "happy_path_smartcard_root.signed.json",
+    "invalid_key_usage.signed.json",
+    "invalid_permissions.signed.json",
+    "invalid_public_key.signed.json",
+    "invalid_signature.signed.json",
+    "not_signed.json",
+);
+
+const NODE_DESCRIPTORS: [(&str, &str); 9] = fixtures!("node_descriptor":
+    "cert_cannot_sign_node.signed.json",
+    "expired.signed.json",
+    "happy_path.signed.json",
+    "invalid_cert_chain_signature.signed.json",
+    "invalid_permissions_chain.signed.json",
+    "invalid_signature.signed.json",
+    "node_signed.signed.json",
+    "node_signed_by_other_node.signed.json",
+    "not_signed.json",
+);
+
+fn from_js(value: JsValue) -> Value {
+    serde_wasm_bindgen::from_value(value).unwrap()
+}
+
+/// Name, kind and position of a thrown error, as javascript code would read them.
+/// The message is not enumerable, so it is not among the details.
+fn error_details(error: JsValue) -> Value {
+    from_js(error)
+}
+
+fn expected_error_details(error: gcert::Error) -> Value {
+    let position = error.chain_position().map(|position| {
+        json!({
+            "depth": position.depth,
+            "fingerprint": position.fingerprint,
+            "displayName": position.display_name,
+        })
+    });
+    json!({
+        "name": "GolemCertificateError",
+        "kind": error.kind(),
+        "position": position,
+    })
+}
+
+fn expected_outcome<T: Serialize>(result: gcert::Result<T>) -> Result<Value, Value> {
+    result
+        .map(|validated| serde_json::to_value(validated).unwrap())
+        .map_err(expected_error_details)
+}
+
+#[wasm_bindgen_test]
+fn validate_certificate_fixtures_as_in_rust() {
+    for (name, data) in CERTIFICATES {
+        let outcome = validate_certificate(data, Some(TIMESTAMP.into()))
+            .map(from_js)
+            .map_err(|error| error_details(error.into()));
+
+        let expected = expected_outcome(gcert::validate_certificate_str(
+            data,
+            Some(TIMESTAMP.parse().unwrap()),
+        ));
+        assert_eq!(outcome, expected, "{name}");
+    }
+}
+
+#[wasm_bindgen_test]
+fn validate_node_descriptor_fixtures_as_in_rust() {
+    for (name, data) in NODE_DESCRIPTORS {
+        let outcome = validate_node_descriptor(data, Some(TIMESTAMP.into()))
+            .map(from_js)
+            .map_err(|error| error_details(error.into()));
+
+        let expected = expected_outcome(gcert::validate_node_descriptor_str(
+            data,
+            Some(TIMESTAMP.parse().unwrap()),
+        ));
+        assert_eq!(outcome, expected, "{name}");
+    }
+}
+
+#[wasm_bindgen_test]
+fn throw_error_with_kind_and_position_of_the_failing_certificate() {
+    let (_, data) = NODE_DESCRIPTORS[3];
+
+    let error: JsValue = validate_node_descriptor(data, None).unwrap_err().into();
+
+    let message = error.dyn_ref::<js_sys::Error>().unwrap().message();
+    assert!(String::from(message).starts_with("Invalid signature value"));
+    let details = error_details(error);
+    assert_eq!(details["kind"], "InvalidSignatureValue");
+    assert_eq!(details["position"]["depth"], 1);
+}
+
+#[wasm_bindgen_test]
+fn throw_invalid_argument_for_invalid_timestamp() {
+    let (_, data) = CERTIFICATES[3];
+
+    let error = validate_certificate(data, Some("yesterday".into())).unwrap_err();
+
+    let details = error_details(error.into());
+    assert_eq!(details["kind"], "InvalidArgument");
+    assert_eq!(details["position"], Value::Null);
+}
+
+#[wasm_bindgen_test]
+fn validate_self_signed_certificate() {
+    let key_pair = from_js(create_key_pair().unwrap());
+    let certificate = json!({
+        "keyUsage": "all",
+        "permissions": "all",
+        "publicKey": key_pair["publicKey"],
+        "subject": { "displayName": "Test", "contact": { "email": "test@example.net" } },
+        "validityPeriod": { "notBefore": "2023-01-01T00:00:00Z", "notAfter": "2025-01-01T00:00:00Z" },
+    });
+    let to_js = |value: &Value| {
+        value
+            .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+            .unwrap()
+    };
+
+    let signed = sign_certificate(
+        to_js(&certificate),
+        JsValue::UNDEFINED,
+        to_js(&key_pair["privateKey"]),
+    )
+    .unwrap();
+
+    let data = serde_json::to_string(&from_js(signed)).unwrap();
+    let validated = from_js(validate_certificate(&data, Some(TIMESTAMP.into())).unwrap());
+    assert_eq!(
+        validated["certificateChainFingerprints"],
+        json!([fingerprint(&data).unwrap()])
+    );
+}